@@ -11,6 +11,9 @@ const polygon_radius = 1.0;
 const polygon_sides = 7;
 const num_polygons = 200;
 
+// Handle returned by start_simple_tunnel, owns this canvas's state
+let tunnel = null;
+
 // Create simple controls
 const container = document.createElement("div");
 container.style.padding = "20px";
@@ -22,6 +25,8 @@ canvas.height = 630;
 canvas.style.border = "1px solid #333";
 canvas.style.display = "block";
 canvas.style.marginBottom = "20px";
+// Touch drags steer the outside view instead of scrolling the page
+canvas.style.touchAction = "none";
 
 const controls = document.createElement("div");
 controls.style.display = "flex";
@@ -38,7 +43,7 @@ speedSlider.max = "0.5";
 speedSlider.step = "0.01";
 speedSlider.value = "0.01";
 speedSlider.addEventListener("input", (e) => {
-  wasm.set_speed(parseFloat(e.target.value));
+  tunnel?.set_speed(parseFloat(e.target.value));
 });
 
 // Show/hide toggles
@@ -46,7 +51,7 @@ const longitudeCheck = document.createElement("input");
 longitudeCheck.type = "checkbox";
 longitudeCheck.checked = true;
 longitudeCheck.addEventListener("change", (e) => {
-  wasm.set_show_longitude(e.target.checked);
+  tunnel?.set_show_longitude(e.target.checked);
 });
 const longitudeLabel = document.createElement("label");
 longitudeLabel.textContent = "Longitude";
@@ -56,7 +61,7 @@ const latitudeCheck = document.createElement("input");
 latitudeCheck.type = "checkbox";
 latitudeCheck.checked = true;
 latitudeCheck.addEventListener("change", (e) => {
-  wasm.set_show_latitude(e.target.checked);
+  tunnel?.set_show_latitude(e.target.checked);
 });
 const latitudeLabel = document.createElement("label");
 latitudeLabel.textContent = "Latitude";
@@ -66,7 +71,7 @@ const tunnelCheck = document.createElement("input");
 tunnelCheck.type = "checkbox";
 tunnelCheck.checked = true;
 tunnelCheck.addEventListener("change", (e) => {
-  wasm.set_show_tunnel(e.target.checked);
+  tunnel?.set_show_tunnel(e.target.checked);
 });
 const tunnelLabel = document.createElement("label");
 tunnelLabel.textContent = "Tunnel";
@@ -86,19 +91,396 @@ polygonsDisplay.textContent = num_polygons.toString();
 polygonsSlider.addEventListener("input", (e) => {
   const value = parseInt(e.target.value);
   polygonsDisplay.textContent = value.toString();
-  wasm.set_num_polygons(value);
+  tunnel?.set_num_polygons(value);
 });
 
-// Outside view toggle
-const outsideCheck = document.createElement("input");
-outsideCheck.type = "checkbox";
-outsideCheck.checked = false;
-outsideCheck.addEventListener("change", (e) => {
-  wasm.set_outside_view(e.target.checked);
-});
+// Camera mode: inside, chase or outside, gliding between them
 const outsideLabel = document.createElement("label");
-outsideLabel.textContent = "Outside View";
-outsideLabel.prepend(outsideCheck);
+outsideLabel.textContent = "Camera: ";
+const cameraSelect = document.createElement("select");
+for (const [name, value] of [
+  ["Inside", wasm.CameraMode.Inside],
+  ["Chase", wasm.CameraMode.Chase],
+  ["Outside", wasm.CameraMode.Outside],
+]) {
+  const option = document.createElement("option");
+  option.textContent = name;
+  option.value = value;
+  cameraSelect.appendChild(option);
+}
+cameraSelect.addEventListener("change", (e) => {
+  tunnel?.set_camera_mode(parseInt(e.target.value));
+});
+outsideLabel.appendChild(cameraSelect);
+
+// Orbit camera: auto-rotate and re-framing
+const autoRotateCheck = document.createElement("input");
+autoRotateCheck.type = "checkbox";
+autoRotateCheck.checked = false;
+autoRotateCheck.addEventListener("change", (e) => {
+  tunnel?.set_auto_rotate(e.target.checked ? 0.3 : 0);
+});
+const autoRotateLabel = document.createElement("label");
+autoRotateLabel.textContent = "Auto-rotate";
+autoRotateLabel.prepend(autoRotateCheck);
+const frameButton = document.createElement("button");
+frameButton.textContent = "Frame curve";
+frameButton.addEventListener("click", () => tunnel?.reset_orbit());
+
+// Ring frame selector
+const frameLabel = document.createElement("label");
+frameLabel.textContent = "Frame: ";
+const frameSelect = document.createElement("select");
+for (const [name, value] of [
+  ["Radial", wasm.FrameMode.Radial],
+  ["Frenet", wasm.FrameMode.Frenet],
+  ["Rotation minimizing", wasm.FrameMode.RotationMinimizing],
+]) {
+  const option = document.createElement("option");
+  option.textContent = name;
+  option.value = value;
+  frameSelect.appendChild(option);
+}
+frameSelect.addEventListener("change", (e) => {
+  tunnel?.set_frame_mode(parseInt(e.target.value));
+});
+frameLabel.appendChild(frameSelect);
+
+// Arc-length toggle: uniform ring spacing and constant camera speed
+const arcCheck = document.createElement("input");
+arcCheck.type = "checkbox";
+arcCheck.checked = false;
+arcCheck.addEventListener("change", (e) => {
+  const mode = e.target.checked
+    ? wasm.Parameterization.ArcLength
+    : wasm.Parameterization.Parameter;
+  tunnel?.set_ring_spacing(mode);
+  tunnel?.set_camera_travel(mode);
+});
+const arcLabel = document.createElement("label");
+arcLabel.textContent = "Arc length";
+arcLabel.prepend(arcCheck);
+
+// Grid line width: 0 keeps 1px GL lines, above that the grid is tubes
+const wireLabel = document.createElement("label");
+wireLabel.textContent = "Line width: ";
+const wireSlider = document.createElement("input");
+wireSlider.type = "range";
+wireSlider.min = "0";
+wireSlider.max = "0.1";
+wireSlider.step = "0.005";
+wireSlider.value = "0";
+wireSlider.addEventListener("input", (e) => {
+  tunnel?.set_wire_width(parseFloat(e.target.value));
+});
+wireLabel.appendChild(wireSlider);
+
+// Lighting: on/off, material sliders and coloured lights on the curve
+const lightCheck = document.createElement("input");
+lightCheck.type = "checkbox";
+lightCheck.checked = false;
+lightCheck.addEventListener("change", (e) => {
+  tunnel?.set_lighting(e.target.checked);
+});
+const lightLabel = document.createElement("label");
+lightLabel.textContent = "Lighting";
+lightLabel.prepend(lightCheck);
+
+const lightSliders = [
+  ["Ambient", 0.35, (v) => tunnel?.set_ambient(v)],
+  ["Diffuse", 0.65, (v) => tunnel?.set_diffuse(v)],
+  ["Specular", 0.3, (v) => tunnel?.set_specular(v)],
+].map(([name, value, set]) => {
+  const label = document.createElement("label");
+  label.textContent = `${name}: `;
+  const slider = document.createElement("input");
+  slider.type = "range";
+  slider.min = "0";
+  slider.max = "1";
+  slider.step = "0.05";
+  slider.value = value.toString();
+  slider.addEventListener("input", (e) => set(parseFloat(e.target.value)));
+  label.appendChild(slider);
+  return label;
+});
+
+const pointLightCheck = document.createElement("input");
+pointLightCheck.type = "checkbox";
+pointLightCheck.checked = false;
+pointLightCheck.addEventListener("change", (e) => {
+  if (e.target.checked) {
+    tunnel?.set_point_lights(
+      new Float64Array([0.0, 2.1, 4.2]),
+      new Float32Array([1, 0.4, 0.2, 0.2, 1, 0.5, 0.3, 0.5, 1])
+    );
+  } else {
+    tunnel?.set_point_lights(new Float64Array(), new Float32Array());
+  }
+});
+const pointLightLabel = document.createElement("label");
+pointLightLabel.textContent = "Curve lights";
+pointLightLabel.prepend(pointLightCheck);
+
+// Fog for the inside view. Off in the library; the page turns it on.
+const fogLabel = document.createElement("label");
+fogLabel.textContent = "Fog: ";
+const fogSelect = document.createElement("select");
+for (const [name, value] of [
+  ["Off", wasm.FogMode.Off],
+  ["Exponential", wasm.FogMode.Exponential],
+  ["Linear", wasm.FogMode.Linear],
+]) {
+  const option = document.createElement("option");
+  option.textContent = name;
+  option.value = value;
+  fogSelect.appendChild(option);
+}
+fogSelect.addEventListener("change", (e) => {
+  tunnel?.set_fog_mode(parseInt(e.target.value));
+});
+fogSelect.value = wasm.FogMode.Exponential;
+fogLabel.appendChild(fogSelect);
+const fogSlider = document.createElement("input");
+fogSlider.type = "range";
+fogSlider.min = "0";
+fogSlider.max = "0.2";
+fogSlider.step = "0.005";
+fogSlider.value = "0.06";
+fogSlider.addEventListener("input", (e) => {
+  tunnel?.set_fog_density(parseFloat(e.target.value));
+});
+fogLabel.appendChild(fogSlider);
+
+// Ring colours
+const colormapLabel = document.createElement("label");
+colormapLabel.textContent = "Colours: ";
+const colormapSelect = document.createElement("select");
+for (const [name, value] of [
+  ["Sine", wasm.Colormap.Sine],
+  ["Viridis", wasm.Colormap.Viridis],
+  ["Magma", wasm.Colormap.Magma],
+  ["Turbo", wasm.Colormap.Turbo],
+  ["Rainbow", wasm.Colormap.Rainbow],
+]) {
+  const option = document.createElement("option");
+  option.textContent = name;
+  option.value = value;
+  colormapSelect.appendChild(option);
+}
+colormapSelect.addEventListener("change", (e) => {
+  tunnel?.set_colormap(parseInt(e.target.value));
+});
+colormapLabel.appendChild(colormapSelect);
+const metricSelect = document.createElement("select");
+for (const [name, value] of [
+  ["along the tunnel", wasm.ColorMetric.RingIndex],
+  ["by curvature", wasm.ColorMetric.Curvature],
+  ["by torsion", wasm.ColorMetric.Torsion],
+  ["by speed", wasm.ColorMetric.Speed],
+  ["by height", wasm.ColorMetric.Height],
+  ["by latitude", wasm.ColorMetric.Latitude],
+  ["by longitude", wasm.ColorMetric.Longitude],
+  ["by proximity", wasm.ColorMetric.Proximity],
+]) {
+  const option = document.createElement("option");
+  option.textContent = name;
+  option.value = value;
+  metricSelect.appendChild(option);
+}
+metricSelect.addEventListener("change", (e) => {
+  tunnel?.set_color_by(parseInt(e.target.value));
+});
+colormapLabel.appendChild(metricSelect);
+
+// Colour cycling on the GPU
+const paletteCheck = document.createElement("input");
+paletteCheck.type = "checkbox";
+paletteCheck.checked = false;
+paletteCheck.addEventListener("change", (e) => {
+  tunnel?.set_palette_animation(e.target.checked);
+});
+const paletteLabel = document.createElement("label");
+paletteLabel.textContent = "Flow colours";
+paletteLabel.prepend(paletteCheck);
+const pulseCheck = document.createElement("input");
+pulseCheck.type = "checkbox";
+pulseCheck.checked = false;
+pulseCheck.addEventListener("change", (e) => {
+  tunnel?.set_pulse(0.25, 0.02, e.target.checked ? 2.0 : 0.0);
+});
+const pulseLabel = document.createElement("label");
+pulseLabel.textContent = "Pulse";
+pulseLabel.prepend(pulseCheck);
+
+// Blending of the translucent walls
+const transparencyLabel = document.createElement("label");
+transparencyLabel.textContent = "Transparency: ";
+const transparencySelect = document.createElement("select");
+for (const [name, value] of [
+  ["Two-pass", wasm.Transparency.TwoPass],
+  ["Order-independent", wasm.Transparency.WeightedBlended],
+]) {
+  const option = document.createElement("option");
+  option.textContent = name;
+  option.value = value;
+  transparencySelect.appendChild(option);
+}
+transparencySelect.addEventListener("change", (e) => {
+  tunnel?.set_transparency(parseInt(e.target.value));
+  // Falls back when float render targets are missing
+  if (tunnel) transparencySelect.value = tunnel.transparency;
+});
+transparencyLabel.appendChild(transparencySelect);
+
+// Video or image on the walls, with tiling and scroll
+const textureInput = document.createElement("input");
+textureInput.type = "file";
+textureInput.accept = "video/*,image/*";
+textureInput.addEventListener("change", (e) => {
+  const file = e.target.files[0];
+  if (!file || !tunnel) return;
+  const url = URL.createObjectURL(file);
+  if (file.type.startsWith("video/")) {
+    const video = document.createElement("video");
+    video.src = url;
+    video.loop = true;
+    video.muted = true;
+    video.play();
+    tunnel.set_wall_video(video);
+  } else {
+    const image = new Image();
+    image.src = url;
+    tunnel.set_wall_image(image);
+  }
+  // Textures are lost at the default 5% opacity
+  tunnel.set_wall_opacity(0.6);
+});
+const textureLabel = document.createElement("label");
+textureLabel.textContent = "Wall texture: ";
+textureLabel.appendChild(textureInput);
+
+const textureSliders = [
+  ["Tiling", 1, 8, 1, 1, (v) => tunnel?.set_texture_tiling(v, v * 4)],
+  ["Scroll", -1, 1, 0.05, 0, (v) => tunnel?.set_texture_scroll(0, v)],
+].map(([name, min, max, step, value, set]) => {
+  const label = document.createElement("label");
+  label.textContent = `${name}: `;
+  const slider = document.createElement("input");
+  slider.type = "range";
+  slider.min = min.toString();
+  slider.max = max.toString();
+  slider.step = step.toString();
+  slider.value = value.toString();
+  slider.addEventListener("input", (e) => set(parseFloat(e.target.value)));
+  label.appendChild(slider);
+  return label;
+});
+
+// Warn when the tube folds over or runs into itself
+const warning = document.createElement("div");
+warning.style.color = "orange";
+const checkTunnel = () => {
+  const report = tunnel?.analyze();
+  if (!report) return;
+  const problems = [];
+  if (report.folds.length > 0)
+    problems.push(
+      `folds in ${report.folds.length / 2} places (keep the radius under ${report.max_fold_free_radius.toFixed(2)})`
+    );
+  if (report.proximity.length > 0)
+    problems.push(`touches itself in ${report.proximity.length / 2} places`);
+  if (report.inverted_triangles.length > 0)
+    problems.push(`${report.inverted_triangles.length} inverted faces`);
+  warning.textContent = problems.length > 0 ? `Tube ${problems.join(", ")}` : "";
+  report.free();
+};
+
+// Cross-section profile selector
+const profiles = {
+  Polygon: () => wasm.Profile.regular(polygon_radius, polygon_sides),
+  Star: () => wasm.Profile.star(polygon_radius, 0.5 * polygon_radius, 5),
+  Superellipse: () =>
+    wasm.Profile.superellipse(polygon_radius, 0.6 * polygon_radius, 4.0, 32),
+  "Rounded rect": () =>
+    wasm.Profile.rounded_rect(2 * polygon_radius, polygon_radius, 0.25, 4),
+  Heart: () =>
+    wasm.Profile.from_svg_path(
+      "M 0 3 C 0 0 -5 0 -5 -2 C -5 -5 -1 -5 0 -2 C 1 -5 5 -5 5 -2 C 5 0 0 0 0 3 Z"
+    ).fit(polygon_radius),
+};
+const profileLabel = document.createElement("label");
+profileLabel.textContent = "Profile: ";
+const profileSelect = document.createElement("select");
+for (const name of Object.keys(profiles)) {
+  const option = document.createElement("option");
+  option.textContent = name;
+  profileSelect.appendChild(option);
+}
+profileSelect.addEventListener("change", (e) => {
+  const profile = profiles[e.target.value]();
+  tunnel?.set_profile(profile);
+  profile.free();
+  checkTunnel();
+});
+profileLabel.appendChild(profileSelect);
+
+// Mesh downloads
+const download = (data, filename, type) => {
+  const url = URL.createObjectURL(new Blob([data], { type }));
+  const link = document.createElement("a");
+  link.href = url;
+  link.download = filename;
+  link.click();
+  URL.revokeObjectURL(url);
+};
+const objButton = document.createElement("button");
+objButton.textContent = "Download OBJ";
+objButton.addEventListener("click", () => {
+  if (tunnel) download(tunnel.export_obj(), "tunnel.obj", "text/plain");
+});
+const stlButton = document.createElement("button");
+stlButton.textContent = "Download STL";
+stlButton.addEventListener("click", () => {
+  if (tunnel)
+    download(tunnel.export_stl_binary(), "tunnel.stl", "model/stl");
+});
+
+const solidButton = document.createElement("button");
+solidButton.textContent = "Download solid STL";
+solidButton.addEventListener("click", () => {
+  if (!tunnel) return;
+  const collisions = tunnel.self_intersections();
+  if (collisions.length > 0)
+    console.warn(
+      `Tube runs into itself in ${collisions.length / 2} places; the print will fuse there`
+    );
+  try {
+    download(tunnel.export_solid_stl(0.15), "tunnel-solid.stl", "model/stl");
+  } catch (error) {
+    warning.textContent = `Can't build the solid: ${error}`;
+  }
+});
+const solid3mfButton = document.createElement("button");
+solid3mfButton.textContent = "Download solid 3MF";
+solid3mfButton.addEventListener("click", () => {
+  if (!tunnel) return;
+  try {
+    download(
+      tunnel.export_solid_3mf(0.15),
+      "tunnel-solid.3mf",
+      "model/3mf"
+    );
+  } catch (error) {
+    warning.textContent = `Can't build the solid: ${error}`;
+  }
+});
+const glbButton = document.createElement("button");
+glbButton.textContent = "Download glTF";
+glbButton.addEventListener("click", () => {
+  // One loop of the inside view in 60 seconds
+  if (tunnel)
+    download(tunnel.export_glb(60, 600), "tunnel.glb", "model/gltf-binary");
+});
 
 controls.appendChild(speedLabel);
 controls.appendChild(speedSlider);
@@ -109,9 +491,31 @@ controls.appendChild(longitudeLabel);
 controls.appendChild(latitudeLabel);
 controls.appendChild(tunnelLabel);
 controls.appendChild(outsideLabel);
+controls.appendChild(autoRotateLabel);
+controls.appendChild(frameButton);
+controls.appendChild(frameLabel);
+controls.appendChild(arcLabel);
+controls.appendChild(profileLabel);
+controls.appendChild(wireLabel);
+controls.appendChild(lightLabel);
+lightSliders.forEach((label) => controls.appendChild(label));
+controls.appendChild(pointLightLabel);
+controls.appendChild(fogLabel);
+controls.appendChild(colormapLabel);
+controls.appendChild(paletteLabel);
+controls.appendChild(pulseLabel);
+controls.appendChild(transparencyLabel);
+controls.appendChild(textureLabel);
+textureSliders.forEach((label) => controls.appendChild(label));
+controls.appendChild(objButton);
+controls.appendChild(stlButton);
+controls.appendChild(solidButton);
+controls.appendChild(solid3mfButton);
+controls.appendChild(glbButton);
 
 container.appendChild(canvas);
 container.appendChild(controls);
+container.appendChild(warning);
 document.body.appendChild(container);
 
 // Initialize
@@ -120,7 +524,7 @@ document.body.appendChild(container);
     await init(mirrorwasm);
     console.log("WASM initialized successfully");
 
-    const result = wasm.start_simple_tunnel(
+    tunnel = wasm.start_simple_tunnel(
       "canvas",
       A,
       B,
@@ -129,7 +533,9 @@ document.body.appendChild(container);
       polygon_sides,
       num_polygons
     );
-    console.log("Tunnel started:", result);
+    console.log("Tunnel started");
+    tunnel.set_fog_mode(parseInt(fogSelect.value));
+    checkTunnel();
   } catch (error) {
     console.error("Error initializing tunnel:", error);
     document.body.innerHTML += `<div style="color: red; padding: 20px;">Error: ${
@@ -145,7 +551,7 @@ document.body.appendChild(container);
       case "ArrowLeft":
         // Decrease speed
         currentSpeed = Math.max(-0.5, currentSpeed - 0.005);
-        wasm.set_speed(currentSpeed);
+        tunnel?.set_speed(currentSpeed);
         speedSlider.value = currentSpeed.toString();
         event.preventDefault();
         break;
@@ -153,7 +559,7 @@ document.body.appendChild(container);
       case "ArrowRight":
         // Increase speed
         currentSpeed = Math.min(0.5, currentSpeed + 0.005);
-        wasm.set_speed(currentSpeed);
+        tunnel?.set_speed(currentSpeed);
         speedSlider.value = currentSpeed.toString();
         event.preventDefault();
         break;
@@ -161,7 +567,7 @@ document.body.appendChild(container);
       case "Space":
         // Stop/pause (set speed to 0)
         currentSpeed = 0.0;
-        wasm.set_speed(currentSpeed);
+        tunnel?.set_speed(currentSpeed);
         speedSlider.value = currentSpeed.toString();
         event.preventDefault();
         break;
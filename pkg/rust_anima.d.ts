@@ -1,36 +1,746 @@
 /* tslint:disable */
 /* eslint-disable */
-export function set_speed(speed: number): void;
-export function set_show_longitude(show: boolean): void;
-export function set_show_latitude(show: boolean): void;
-export function set_show_tunnel(show: boolean): void;
-export function set_num_polygons(num: number): void;
-export function set_outside_view(outside: boolean): void;
-export function start_simple_tunnel(canvas_id: string, a: number, b: number, r: number, polygon_radius: number, polygon_sides: number, num_polygons: number): void;
+export function start_simple_tunnel(canvas_id: string, a: number, b: number, r: number, polygon_radius: number, polygon_sides: number, num_polygons: number): TunnelHandle;
+/**
+ * Where the camera watches the curve from
+ */
+export enum CameraMode {
+  /**
+   * On the curve, looking along the tangent
+   */
+  Inside = 0,
+  /**
+   * Trailing behind and above the current point, looking at it
+   */
+  Chase = 1,
+  /**
+   * Orbiting the whole curve
+   */
+  Outside = 2,
+}
+/**
+ * The scalar along the curve that ring colours follow
+ */
+export enum ColorMetric {
+  /**
+   * Position of the ring along the tunnel
+   */
+  RingIndex = 0,
+  /**
+   * κ; bright where the curve bends hardest
+   */
+  Curvature = 1,
+  /**
+   * τ; how fast the curve leaves its osculating plane
+   */
+  Torsion = 2,
+  /**
+   * Parametric speed |r'(t)|
+   */
+  Speed = 3,
+  /**
+   * z of `position(t)`
+   */
+  Height = 4,
+  /**
+   * Angle above the xy plane as seen from the origin
+   */
+  Latitude = 5,
+  /**
+   * Angle around the z axis. Uses the palette's full range regardless
+   * of the curve, so cyclic colormaps wrap without a seam.
+   */
+  Longitude = 6,
+  /**
+   * Distance to the nearest other part of the curve; 0 at crossings
+   */
+  Proximity = 7,
+}
+/**
+ * Named colour scales over [0, 1]
+ */
+export enum Colormap {
+  /**
+   * The original ring colours: three sines of different frequency.
+   * Cyclic, so closed loops have no seam.
+   */
+  Sine = 0,
+  /**
+   * Perceptually uniform dark blue to yellow (matplotlib)
+   */
+  Viridis = 1,
+  /**
+   * Perceptually uniform black through purple to pale yellow (matplotlib)
+   */
+  Magma = 2,
+  /**
+   * High-contrast blue to red rainbow (Google)
+   */
+  Turbo = 3,
+  /**
+   * Full HSV hue circle; cyclic
+   */
+  Rainbow = 4,
+}
+/**
+ * How fog thickens with view-space depth
+ */
+export enum FogMode {
+  /**
+   * No fog; the default, so the tunnel looks as it always has until
+   * fog is asked for
+   */
+  Off = 0,
+  /**
+   * Clear up to `start`, solid from `end`
+   */
+  Linear = 1,
+  /**
+   * Visibility exp(-density · depth)
+   */
+  Exponential = 2,
+}
+/**
+ * How the ring orientation is chosen along the curve
+ */
+export enum FrameMode {
+  /**
+   * Normal points away from the origin (the curve's own `frame`)
+   */
+  Radial = 0,
+  /**
+   * Normal follows the curve's bending direction
+   */
+  Frenet = 1,
+  /**
+   * Parallel transport, with the leftover twist spread over the loop
+   */
+  RotationMinimizing = 2,
+}
+/**
+ * How progress along the curve is measured
+ */
+export enum Parameterization {
+  /**
+   * Equal steps in t; spacing follows the local speed |r'(t)|
+   */
+  Parameter = 0,
+  /**
+   * Equal steps in distance along the curve
+   */
+  ArcLength = 1,
+}
+/**
+ * How the translucent tunnel walls are composited
+ */
+export enum Transparency {
+  /**
+   * Back faces, then front faces, blended in triangle order
+   */
+  TwoPass = 0,
+  /**
+   * Weighted blended order-independent transparency (McGuire & Bavoil
+   * 2013). Needs renderable float textures; falls back to `TwoPass`.
+   */
+  WeightedBlended = 1,
+}
+/**
+ * Spherical Lissajous curve
+ *
+ * θ(t) = a·t + δθ, or θ(t) = c + A·sin(a·t + δθ) with a polar band
+ * φ(t) = b·t + δφ
+ * position = (rx sinθ cosφ, ry sinθ sinφ, rz cosθ)
+ */
 export class Lissajou3D {
   free(): void;
-  constructor(a: number, b: number, r: number);
+  /**
+   * Unnormalized derivative of the given order (0 = position, up to 3)
+   * as `[x, y, z]`
+   */
+  derivative(t: number, order: number): Float64Array | undefined;
+  /**
+   * Distance between the start and the end of one period. Only the
+   * positions are compared: an open curve can come back through its
+   * starting point heading elsewhere, typically at a pole, and still
+   * report a gap of about 0. Use `is_closed`, which also compares the
+   * tangents, to decide whether the tunnel joins up.
+   */
+  closure_gap(): number;
+  /**
+   * JS constructor. Everything after `r` is optional: phases for θ and φ,
+   * a polar band (centre defaults to the equator, amplitude to π/2) and
+   * an `[rx, ry, rz]` ellipsoid that replaces `r`.
+   */
+  constructor(a: number, b: number, r: number, phase_theta?: number | null, phase_phi?: number | null, polar_center?: number | null, polar_amplitude?: number | null, radii?: Float64Array | null);
+  /**
+   * Parametric speed |r'(t)|
+   */
+  speed(t: number): number;
+  /**
+   * Parameter span of one loop (see `is_closed`)
+   */
+  period(): number;
+  /**
+   * Torsion τ(t)
+   */
+  torsion(t: number): number;
+  /**
+   * Curvature κ(t)
+   */
+  curvature(t: number): number;
+  /**
+   * Whether the curve returns to its start after `period`. False when
+   * a:b has no rational approximation with a small denominator.
+   */
+  is_closed(): boolean;
+  readonly phase_theta: number;
   readonly a: number;
   readonly b: number;
+  /**
+   * Largest of the three radii
+   */
   readonly r: number;
+  readonly radii: Float64Array;
+  readonly phase_phi: number;
+}
+/**
+ * Closed 2D cross-section swept along the curve.
+ *
+ * Points live in the ring plane: x along the frame normal, y along the
+ * binormal. They are stored counter-clockwise without repeating the first
+ * point, so every profile produces the same triangle winding.
+ */
+export class Profile {
+  private constructor();
+  free(): void;
+  /**
+   * Profile from interleaved `[x0, y0, x1, y1, ...]` coordinates
+   */
+  static from_coords(coords: Float64Array): Profile;
+  /**
+   * Rectangle centred on the origin with circular corners
+   */
+  static rounded_rect(width: number, height: number, corner_radius: number, corner_segments: number): Profile;
+  /**
+   * |x/a|^n + |y/b|^n = 1. n = 2 is an ellipse, large n tends to a rectangle
+   */
+  static superellipse(a: number, b: number, exponent: number, segments: number): Profile;
+  /**
+   * Profile from the first subpath of an SVG path string, e.g.
+   * `"M 0 0 L 10 0 Q 10 10 0 10 Z"`. SVG's y axis points down, so it is
+   * flipped here.
+   */
+  static from_svg_path(d: string): Profile;
+  /**
+   * Centre on the bounding box and scale so the farthest point is `radius`
+   */
+  fit(radius: number): Profile;
+  /**
+   * Star alternating between `outer` and `inner` radius
+   */
+  static star(outer: number, inner: number, tips: number): Profile;
+  /**
+   * Interleaved `[x0, y0, x1, y1, ...]`
+   */
+  coords(): Float64Array;
+  /**
+   * Regular n-gon, first vertex on the x axis
+   */
+  static regular(radius: number, sides: number): Profile;
+  is_empty(): boolean;
+  readonly len: number;
+  /**
+   * Distance from the origin to the farthest point
+   */
+  readonly radius: number;
+}
+/**
+ * A running tunnel animation on one canvas.
+ *
+ * Each handle owns its own settings, so several canvases can run side by
+ * side. Calling `stop()` (or `free()`) cancels the animation loop and
+ * deletes the GL buffers.
+ */
+export class TunnelHandle {
+  private constructor();
+  free(): void;
+  /**
+   * Current mesh plus an animated camera replaying one loop of the inside
+   * view over `duration` seconds, as a glTF binary (.glb)
+   */
+  export_glb(duration: number, samples: number): Uint8Array;
+  /**
+   * Current mesh as Wavefront OBJ text
+   */
+  export_obj(): string;
+  /**
+   * Frame the whole curve again in the outside view
+   */
+  reset_orbit(): void;
+  set_ambient(ambient: number): void;
+  set_diffuse(diffuse: number): void;
+  /**
+   * Sweep a different cross-section, e.g. `Profile.star(1.2, 0.6, 5)`
+   */
+  set_profile(profile: Profile): void;
+  /**
+   * What the ring colours follow along the curve, e.g.
+   * `ColorMetric.Curvature` to pick out the tight bends
+   */
+  set_color_by(metric: ColorMetric): void;
+  /**
+   * Colour the rings with a named colour scale
+   */
+  set_colormap(colormap: Colormap): void;
+  /**
+   * Fog for the inside view: off, linear or exponential in depth
+   */
+  set_fog_mode(mode: FogMode): void;
+  /**
+   * Colour the rings with a gradient through `[x0, r0, g0, b0, x1, ...]`,
+   * positions in [0, 1] along the curve
+   */
+  set_gradient(stops: Float32Array): void;
+  /**
+   * Turn the Blinn-Phong shading of walls and grid tubes on or off
+   */
+  set_lighting(enabled: boolean): void;
+  set_specular(specular: number): void;
+  set_fog_color(r: number, g: number, b: number): void;
+  /**
+   * Linear fog: clear up to `start`, solid from `end`, in view-space depth
+   */
+  set_fog_range(start: number, end: number): void;
+  /**
+   * Strength of the light attached to the camera
+   */
+  set_headlight(intensity: number): void;
+  /**
+   * Blinn-Phong exponent; higher is a tighter highlight
+   */
+  set_shininess(shininess: number): void;
+  /**
+   * Ring orientation for both the mesh and the inside camera
+   */
+  set_frame_mode(mode: FrameMode): void;
+  /**
+   * Put an `<img>` on the walls once it has loaded
+   */
+  set_wall_image(image: HTMLImageElement): void;
+  /**
+   * Stream a `<video>` onto the walls, re-uploading its current frame
+   * every frame. The video must be same-origin or served with CORS.
+   */
+  set_wall_video(video: HTMLVideoElement): void;
+  /**
+   * Draw the grid as tubes `width` across instead of 1px lines. 0
+   * switches back to lines.
+   */
+  set_wire_width(width: number): void;
+  /**
+   * Spin the outside view around the curve at `speed` radians per
+   * second whenever nobody is dragging; 0 stops
+   */
+  set_auto_rotate(speed: number): void;
+  /**
+   * Switch between the inside, chase and outside cameras. The view
+   * glides to the new camera over `transition_duration` seconds.
+   */
+  set_camera_mode(mode: CameraMode): void;
+  /**
+   * Exponential fog: visibility exp(-density · depth)
+   */
+  set_fog_density(density: number): void;
+  set_show_tunnel(show: boolean): void;
+  /**
+   * Blend between the ring colours (0) and the texture (1)
+   */
+  set_texture_mix(mix: number): void;
+  /**
+   * Stream a `<canvas>` onto the walls every frame, e.g. a 2D sketch
+   */
+  set_wall_canvas(canvas: HTMLCanvasElement): void;
+  /**
+   * Same solid as `export_solid_stl`, as a 3MF package in millimetres
+   */
+  export_solid_3mf(thickness: number): Uint8Array;
+  /**
+   * Current tube as a watertight solid with walls `thickness` thick, as
+   * binary STL for printing. Throws if the wall is too thick for the
+   * profile.
+   */
+  export_solid_stl(thickness: number): Uint8Array;
+  /**
+   * Current mesh as ASCII STL text
+   */
+  export_stl_ascii(): string;
+  /**
+   * How far the chase camera trails behind the current point and rises
+   * above it, in tube radii
+   */
+  set_chase_offset(distance: number, height: number): void;
+  set_num_polygons(num: number): void;
+  /**
+   * Outside view on, or back to the inside view
+   */
+  set_outside_view(outside: boolean): void;
+  /**
+   * Up to 4 point lights on the curve at parameters `params`, with
+   * colours `[r0, g0, b0, r1, ...]`. No colours means white lights.
+   */
+  set_point_lights(params: Float64Array, colors: Float32Array): void;
+  /**
+   * Place rings at equal steps of t or of distance along the curve
+   */
+  set_ring_spacing(spacing: Parameterization): void;
+  /**
+   * How the translucent walls are blended. `WeightedBlended` composites
+   * crossing loops correctly but needs float render targets; without
+   * them the walls stay on `TwoPass` and a warning is logged.
+   */
+  set_transparency(mode: Transparency): void;
+  /**
+   * Opacity of the tunnel walls, 0.05 by default. Textured walls usually
+   * want more.
+   */
+  set_wall_opacity(opacity: number): void;
+  /**
+   * Current mesh as binary STL, returned to JS as a `Uint8Array`
+   */
+  export_stl_binary(): Uint8Array;
+  /**
+   * Advance the camera at constant speed in t or in distance
+   */
+  set_camera_travel(travel: Parameterization): void;
+  /**
+   * How long the orbit camera glides after a drag: the fraction of the
+   * motion left after 1/60 s, from 0 (stops dead) to just under 1
+   */
+  set_orbit_damping(damping: number): void;
+  /**
+   * Palette lengths per second that the colours flow along the tunnel;
+   * negative flows backwards
+   */
+  set_palette_speed(speed: number): void;
+  set_show_latitude(show: boolean): void;
+  /**
+   * Go back to plain ring colours
+   */
+  clear_wall_texture(): void;
+  /**
+   * Parameter ranges where the tube collides with itself, as
+   * `[t_start0, t_end0, t_start1, t_end1, ...]`
+   */
+  self_intersections(): Float64Array;
+  /**
+   * Times the palette repeats along the tunnel while animating
+   */
+  set_palette_repeat(repeat: number): void;
+  set_show_longitude(show: boolean): void;
+  /**
+   * Scroll the texture by `u` and `v` texture widths per second
+   */
+  set_texture_scroll(u: number, v: number): void;
+  /**
+   * Repeats of the texture around the profile (`u`) and along the curve (`v`)
+   */
+  set_texture_tiling(u: number, v: number): void;
+  /**
+   * Scale along the frame normal and binormal as `[t0, s0, t1, s1, ...]`
+   */
+  set_scale_keyframes(keys_x: Float64Array, keys_y: Float64Array): void;
+  /**
+   * Twist around the tangent in radians as `[t0, angle0, t1, angle1, ...]`
+   */
+  set_twist_keyframes(keys: Float64Array): void;
+  /**
+   * Limits of the angle around the vertical axis in radians. Infinite
+   * bounds (the default) let the camera circle freely.
+   */
+  set_orbit_yaw_limits(min: number, max: number): void;
+  /**
+   * Radius scale along the curve as `[t0, s0, t1, s1, ...]`. An empty
+   * array restores a constant tube.
+   */
+  set_radius_keyframes(keys: Float64Array): void;
+  /**
+   * Colour on the GPU from the palette texture, so palettes flow, pulse
+   * and swap without rebuilding the mesh
+   */
+  set_palette_animation(enabled: boolean): void;
+  /**
+   * Elevation limits of the orbit camera in radians, within ±π/2
+   */
+  set_orbit_pitch_limits(min: number, max: number): void;
+  /**
+   * Seconds a camera mode switch takes; 0 cuts straight to the new view
+   */
+  set_transition_duration(seconds: number): void;
+  /**
+   * Nearest and farthest the orbit camera may zoom, in world units.
+   * Framing a curve (`set_curve`, `reset_orbit`) sets them to a tenth and
+   * ten times the framing distance.
+   */
+  set_orbit_distance_limits(min: number, max: number): void;
+  /**
+   * Cancel the animation loop and release the GL buffers. Safe to call twice.
+   */
+  stop(): void;
+  /**
+   * Put the camera back at the start of the curve
+   */
+  reset(): void;
+  /**
+   * Folds, self-proximity and mesh defects for the current parameters,
+   * with the largest radius that avoids them
+   */
+  analyze(): TunnelReport;
+  /**
+   * Glow where the walls turn edge-on to the camera
+   */
+  set_rim(rim: number): void;
+  set_time(time: number): void;
+  /**
+   * Follow a different curve, e.g. one built with phases or a polar band
+   */
+  set_curve(curve: Lissajou3D): void;
+  /**
+   * A bright band racing along the tunnel at `speed` trips per second,
+   * `width` of the tunnel wide, brightening by up to `strength`. A
+   * strength of 0 turns it off.
+   */
+  set_pulse(speed: number, width: number, strength: number): void;
+  set_speed(speed: number): void;
+  readonly frame_mode: FrameMode;
+  readonly wire_width: number;
+  readonly auto_rotate: number;
+  readonly camera_mode: CameraMode;
+  readonly fog_density: number;
+  readonly pulse_speed: number;
+  readonly pulse_width: number;
+  readonly show_tunnel: boolean;
+  readonly texture_mix: number;
+  readonly chase_height: number;
+  readonly num_polygons: number;
+  readonly outside_view: boolean;
+  readonly ring_spacing: Parameterization;
+  readonly transparency: Transparency;
+  readonly wall_opacity: number;
+  readonly camera_travel: Parameterization;
+  readonly orbit_damping: number;
+  readonly palette_speed: number;
+  readonly show_latitude: boolean;
+  readonly chase_distance: number;
+  readonly palette_repeat: number;
+  readonly pulse_strength: number;
+  readonly show_longitude: boolean;
+  readonly texture_scroll: Float32Array;
+  readonly texture_tiling: Float32Array;
+  readonly palette_animation: boolean;
+  readonly transition_duration: number;
+  readonly rim: number;
+  readonly time: number;
+  readonly speed: number;
+  readonly ambient: number;
+  readonly diffuse: number;
+  readonly fog_end: number;
+  /**
+   * Whether the animation loop is still scheduled
+   */
+  readonly running: boolean;
+  readonly color_by: ColorMetric;
+  readonly fog_mode: FogMode;
+  readonly lighting: boolean;
+  readonly specular: number;
+  readonly fog_color: Float32Array;
+  readonly fog_start: number;
+  readonly headlight: number;
+  readonly shininess: number;
+}
+/**
+ * What can go wrong with a tunnel for a given curve and options
+ */
+export class TunnelReport {
+  private constructor();
+  free(): void;
+  /**
+   * Largest profile radius, before the scale tracks, that neither folds
+   * nor touches another part of the curve. 0 if the curve crosses itself.
+   */
+  readonly max_safe_radius: number;
+  /**
+   * Indices (into `triangles / 3`) of triangles wound against the rest
+   */
+  readonly inverted_triangles: Uint32Array;
+  /**
+   * Indices (into `triangles / 3`) of triangles with no area
+   */
+  readonly degenerate_triangles: Uint32Array;
+  /**
+   * Largest profile radius, before the scale tracks, that doesn't fold.
+   * The useful clamp for curves that cross themselves.
+   */
+  readonly max_fold_free_radius: number;
+  /**
+   * Fold ranges as `[t_start0, t_end0, t_start1, t_end1, ...]`
+   */
+  readonly folds: Float64Array;
+  /**
+   * Whether nothing at all was found
+   */
+  readonly is_ok: boolean;
+  /**
+   * Proximity ranges as `[t_start0, t_end0, t_start1, t_end1, ...]`
+   */
+  readonly proximity: Float64Array;
 }
 
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
   readonly memory: WebAssembly.Memory;
-  readonly set_show_longitude: (a: number) => void;
-  readonly set_show_latitude: (a: number) => void;
-  readonly set_show_tunnel: (a: number) => void;
-  readonly set_outside_view: (a: number) => void;
-  readonly start_simple_tunnel: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number];
-  readonly set_speed: (a: number) => void;
-  readonly set_num_polygons: (a: number) => void;
+  readonly __wbg_tunnelhandle_free: (a: number, b: number) => void;
+  readonly start_simple_tunnel: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number, number];
+  readonly tunnelhandle_ambient: (a: number) => number;
+  readonly tunnelhandle_analyze: (a: number) => number;
+  readonly tunnelhandle_auto_rotate: (a: number) => number;
+  readonly tunnelhandle_camera_mode: (a: number) => number;
+  readonly tunnelhandle_camera_travel: (a: number) => number;
+  readonly tunnelhandle_chase_distance: (a: number) => number;
+  readonly tunnelhandle_chase_height: (a: number) => number;
+  readonly tunnelhandle_clear_wall_texture: (a: number) => void;
+  readonly tunnelhandle_color_by: (a: number) => number;
+  readonly tunnelhandle_diffuse: (a: number) => number;
+  readonly tunnelhandle_export_glb: (a: number, b: number, c: number) => [number, number];
+  readonly tunnelhandle_export_obj: (a: number) => [number, number];
+  readonly tunnelhandle_export_solid_3mf: (a: number, b: number) => [number, number, number, number];
+  readonly tunnelhandle_export_solid_stl: (a: number, b: number) => [number, number, number, number];
+  readonly tunnelhandle_export_stl_ascii: (a: number) => [number, number];
+  readonly tunnelhandle_export_stl_binary: (a: number) => [number, number];
+  readonly tunnelhandle_fog_color: (a: number) => [number, number];
+  readonly tunnelhandle_fog_density: (a: number) => number;
+  readonly tunnelhandle_fog_end: (a: number) => number;
+  readonly tunnelhandle_fog_mode: (a: number) => number;
+  readonly tunnelhandle_fog_start: (a: number) => number;
+  readonly tunnelhandle_frame_mode: (a: number) => number;
+  readonly tunnelhandle_headlight: (a: number) => number;
+  readonly tunnelhandle_lighting: (a: number) => number;
+  readonly tunnelhandle_num_polygons: (a: number) => number;
+  readonly tunnelhandle_orbit_damping: (a: number) => number;
+  readonly tunnelhandle_outside_view: (a: number) => number;
+  readonly tunnelhandle_palette_animation: (a: number) => number;
+  readonly tunnelhandle_palette_repeat: (a: number) => number;
+  readonly tunnelhandle_palette_speed: (a: number) => number;
+  readonly tunnelhandle_pulse_speed: (a: number) => number;
+  readonly tunnelhandle_pulse_strength: (a: number) => number;
+  readonly tunnelhandle_pulse_width: (a: number) => number;
+  readonly tunnelhandle_reset: (a: number) => void;
+  readonly tunnelhandle_reset_orbit: (a: number) => void;
+  readonly tunnelhandle_rim: (a: number) => number;
+  readonly tunnelhandle_ring_spacing: (a: number) => number;
+  readonly tunnelhandle_running: (a: number) => number;
+  readonly tunnelhandle_self_intersections: (a: number) => [number, number];
+  readonly tunnelhandle_set_ambient: (a: number, b: number) => void;
+  readonly tunnelhandle_set_auto_rotate: (a: number, b: number) => void;
+  readonly tunnelhandle_set_camera_mode: (a: number, b: number) => void;
+  readonly tunnelhandle_set_camera_travel: (a: number, b: number) => void;
+  readonly tunnelhandle_set_chase_offset: (a: number, b: number, c: number) => void;
+  readonly tunnelhandle_set_color_by: (a: number, b: number) => void;
+  readonly tunnelhandle_set_colormap: (a: number, b: number) => void;
+  readonly tunnelhandle_set_curve: (a: number, b: number) => void;
+  readonly tunnelhandle_set_diffuse: (a: number, b: number) => void;
+  readonly tunnelhandle_set_fog_color: (a: number, b: number, c: number, d: number) => void;
+  readonly tunnelhandle_set_fog_density: (a: number, b: number) => void;
+  readonly tunnelhandle_set_fog_mode: (a: number, b: number) => void;
+  readonly tunnelhandle_set_fog_range: (a: number, b: number, c: number) => void;
+  readonly tunnelhandle_set_frame_mode: (a: number, b: number) => void;
+  readonly tunnelhandle_set_gradient: (a: number, b: number, c: number) => [number, number];
+  readonly tunnelhandle_set_headlight: (a: number, b: number) => void;
+  readonly tunnelhandle_set_lighting: (a: number, b: number) => void;
+  readonly tunnelhandle_set_num_polygons: (a: number, b: number) => void;
+  readonly tunnelhandle_set_orbit_damping: (a: number, b: number) => void;
+  readonly tunnelhandle_set_orbit_distance_limits: (a: number, b: number, c: number) => void;
+  readonly tunnelhandle_set_orbit_pitch_limits: (a: number, b: number, c: number) => void;
+  readonly tunnelhandle_set_orbit_yaw_limits: (a: number, b: number, c: number) => void;
+  readonly tunnelhandle_set_outside_view: (a: number, b: number) => void;
+  readonly tunnelhandle_set_palette_animation: (a: number, b: number) => void;
+  readonly tunnelhandle_set_palette_repeat: (a: number, b: number) => void;
+  readonly tunnelhandle_set_palette_speed: (a: number, b: number) => void;
+  readonly tunnelhandle_set_point_lights: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly tunnelhandle_set_profile: (a: number, b: number) => void;
+  readonly tunnelhandle_set_pulse: (a: number, b: number, c: number, d: number) => void;
+  readonly tunnelhandle_set_radius_keyframes: (a: number, b: number, c: number) => [number, number];
+  readonly tunnelhandle_set_rim: (a: number, b: number) => void;
+  readonly tunnelhandle_set_ring_spacing: (a: number, b: number) => void;
+  readonly tunnelhandle_set_scale_keyframes: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+  readonly tunnelhandle_set_shininess: (a: number, b: number) => void;
+  readonly tunnelhandle_set_show_latitude: (a: number, b: number) => void;
+  readonly tunnelhandle_set_show_longitude: (a: number, b: number) => void;
+  readonly tunnelhandle_set_show_tunnel: (a: number, b: number) => void;
+  readonly tunnelhandle_set_specular: (a: number, b: number) => void;
+  readonly tunnelhandle_set_speed: (a: number, b: number) => void;
+  readonly tunnelhandle_set_texture_mix: (a: number, b: number) => void;
+  readonly tunnelhandle_set_texture_scroll: (a: number, b: number, c: number) => void;
+  readonly tunnelhandle_set_texture_tiling: (a: number, b: number, c: number) => void;
+  readonly tunnelhandle_set_time: (a: number, b: number) => void;
+  readonly tunnelhandle_set_transition_duration: (a: number, b: number) => void;
+  readonly tunnelhandle_set_transparency: (a: number, b: number) => void;
+  readonly tunnelhandle_set_twist_keyframes: (a: number, b: number, c: number) => [number, number];
+  readonly tunnelhandle_set_wall_canvas: (a: number, b: any) => void;
+  readonly tunnelhandle_set_wall_image: (a: number, b: any) => void;
+  readonly tunnelhandle_set_wall_opacity: (a: number, b: number) => void;
+  readonly tunnelhandle_set_wall_video: (a: number, b: any) => void;
+  readonly tunnelhandle_set_wire_width: (a: number, b: number) => void;
+  readonly tunnelhandle_shininess: (a: number) => number;
+  readonly tunnelhandle_show_latitude: (a: number) => number;
+  readonly tunnelhandle_show_longitude: (a: number) => number;
+  readonly tunnelhandle_show_tunnel: (a: number) => number;
+  readonly tunnelhandle_specular: (a: number) => number;
+  readonly tunnelhandle_speed: (a: number) => number;
+  readonly tunnelhandle_stop: (a: number) => void;
+  readonly tunnelhandle_texture_mix: (a: number) => number;
+  readonly tunnelhandle_texture_scroll: (a: number) => [number, number];
+  readonly tunnelhandle_texture_tiling: (a: number) => [number, number];
+  readonly tunnelhandle_time: (a: number) => number;
+  readonly tunnelhandle_transition_duration: (a: number) => number;
+  readonly tunnelhandle_transparency: (a: number) => number;
+  readonly tunnelhandle_wall_opacity: (a: number) => number;
+  readonly tunnelhandle_wire_width: (a: number) => number;
   readonly __wbg_lissajou3d_free: (a: number, b: number) => void;
-  readonly lissajou3d_new: (a: number, b: number, c: number) => number;
   readonly lissajou3d_a: (a: number) => number;
   readonly lissajou3d_b: (a: number) => number;
+  readonly lissajou3d_closure_gap: (a: number) => number;
+  readonly lissajou3d_curvature: (a: number, b: number) => number;
+  readonly lissajou3d_derivative: (a: number, b: number, c: number) => [number, number];
+  readonly lissajou3d_is_closed: (a: number) => number;
+  readonly lissajou3d_period: (a: number) => number;
+  readonly lissajou3d_phase_phi: (a: number) => number;
+  readonly lissajou3d_phase_theta: (a: number) => number;
   readonly lissajou3d_r: (a: number) => number;
+  readonly lissajou3d_radii: (a: number) => [number, number];
+  readonly lissajou3d_speed: (a: number, b: number) => number;
+  readonly lissajou3d_torsion: (a: number, b: number) => number;
+  readonly lissajou3d_with_options: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number) => number;
+  readonly __wbg_tunnelreport_free: (a: number, b: number) => void;
+  readonly tunnelreport_degenerate_triangles: (a: number) => [number, number];
+  readonly tunnelreport_folds: (a: number) => [number, number];
+  readonly tunnelreport_inverted_triangles: (a: number) => [number, number];
+  readonly tunnelreport_is_ok: (a: number) => number;
+  readonly tunnelreport_max_fold_free_radius: (a: number) => number;
+  readonly tunnelreport_max_safe_radius: (a: number) => number;
+  readonly tunnelreport_proximity: (a: number) => [number, number];
+  readonly __wbg_profile_free: (a: number, b: number) => void;
+  readonly profile_coords: (a: number) => [number, number];
+  readonly profile_fit: (a: number, b: number) => number;
+  readonly profile_from_coords: (a: number, b: number) => [number, number, number];
+  readonly profile_from_svg_path: (a: number, b: number) => [number, number, number];
+  readonly profile_is_empty: (a: number) => number;
+  readonly profile_len: (a: number) => number;
+  readonly profile_radius: (a: number) => number;
+  readonly profile_regular: (a: number, b: number) => [number, number, number];
+  readonly profile_rounded_rect: (a: number, b: number, c: number, d: number) => [number, number, number];
+  readonly profile_star: (a: number, b: number, c: number) => [number, number, number];
+  readonly profile_superellipse: (a: number, b: number, c: number, d: number) => [number, number, number];
   readonly __wbindgen_exn_store: (a: number) => void;
   readonly __externref_table_alloc: () => number;
   readonly __wbindgen_export_2: WebAssembly.Table;
@@ -39,7 +749,8 @@ export interface InitOutput {
   readonly __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
   readonly __wbindgen_export_6: WebAssembly.Table;
   readonly __externref_table_dealloc: (a: number) => void;
-  readonly _dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h17542a08b0794ff2: (a: number, b: number, c: number) => void;
+  readonly closure22_externref_shim: (a: number, b: number, c: any) => void;
+  readonly _dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h0edc57afd33ee676: (a: number, b: number, c: number) => void;
   readonly __wbindgen_start: () => void;
 }
 
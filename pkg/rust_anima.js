@@ -1,5 +1,23 @@
 let wasm;
 
+const cachedTextDecoder = (typeof TextDecoder !== 'undefined' ? new TextDecoder('utf-8', { ignoreBOM: true, fatal: true }) : { decode: () => { throw Error('TextDecoder not available') } } );
+
+if (typeof TextDecoder !== 'undefined') { cachedTextDecoder.decode(); };
+
+let cachedUint8ArrayMemory0 = null;
+
+function getUint8ArrayMemory0() {
+    if (cachedUint8ArrayMemory0 === null || cachedUint8ArrayMemory0.byteLength === 0) {
+        cachedUint8ArrayMemory0 = new Uint8Array(wasm.memory.buffer);
+    }
+    return cachedUint8ArrayMemory0;
+}
+
+function getStringFromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return cachedTextDecoder.decode(getUint8ArrayMemory0().subarray(ptr, ptr + len));
+}
+
 function addToExternrefTable0(obj) {
     const idx = wasm.__externref_table_alloc();
     wasm.__wbindgen_export_2.set(idx, obj);
@@ -19,24 +37,6 @@ function isLikeNone(x) {
     return x === undefined || x === null;
 }
 
-const cachedTextDecoder = (typeof TextDecoder !== 'undefined' ? new TextDecoder('utf-8', { ignoreBOM: true, fatal: true }) : { decode: () => { throw Error('TextDecoder not available') } } );
-
-if (typeof TextDecoder !== 'undefined') { cachedTextDecoder.decode(); };
-
-let cachedUint8ArrayMemory0 = null;
-
-function getUint8ArrayMemory0() {
-    if (cachedUint8ArrayMemory0 === null || cachedUint8ArrayMemory0.byteLength === 0) {
-        cachedUint8ArrayMemory0 = new Uint8Array(wasm.memory.buffer);
-    }
-    return cachedUint8ArrayMemory0;
-}
-
-function getStringFromWasm0(ptr, len) {
-    ptr = ptr >>> 0;
-    return cachedTextDecoder.decode(getUint8ArrayMemory0().subarray(ptr, ptr + len));
-}
-
 let WASM_VECTOR_LEN = 0;
 
 const cachedTextEncoder = (typeof TextEncoder !== 'undefined' ? new TextEncoder('utf-8') : { encode: () => { throw Error('TextEncoder not available') } } );
@@ -102,6 +102,11 @@ function getDataViewMemory0() {
     return cachedDataViewMemory0;
 }
 
+function getArrayU8FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getUint8ArrayMemory0().subarray(ptr / 1, ptr / 1 + len);
+}
+
 let cachedFloat32ArrayMemory0 = null;
 
 function getFloat32ArrayMemory0() {
@@ -153,182 +158,1587 @@ function debugString(val) {
     if (type == 'number' || type == 'boolean' || val == null) {
         return  `${val}`;
     }
-    if (type == 'string') {
-        return `"${val}"`;
+    if (type == 'string') {
+        return `"${val}"`;
+    }
+    if (type == 'symbol') {
+        const description = val.description;
+        if (description == null) {
+            return 'Symbol';
+        } else {
+            return `Symbol(${description})`;
+        }
+    }
+    if (type == 'function') {
+        const name = val.name;
+        if (typeof name == 'string' && name.length > 0) {
+            return `Function(${name})`;
+        } else {
+            return 'Function';
+        }
+    }
+    // objects
+    if (Array.isArray(val)) {
+        const length = val.length;
+        let debug = '[';
+        if (length > 0) {
+            debug += debugString(val[0]);
+        }
+        for(let i = 1; i < length; i++) {
+            debug += ', ' + debugString(val[i]);
+        }
+        debug += ']';
+        return debug;
+    }
+    // Test for built-in
+    const builtInMatches = /\[object ([^\]]+)\]/.exec(toString.call(val));
+    let className;
+    if (builtInMatches && builtInMatches.length > 1) {
+        className = builtInMatches[1];
+    } else {
+        // Failed to match the standard '[object ClassName]'
+        return toString.call(val);
+    }
+    if (className == 'Object') {
+        // we're a user defined class or Object
+        // JSON.stringify avoids problems with cycles, and is generally much
+        // easier than looping through ownProperties of `val`.
+        try {
+            return 'Object(' + JSON.stringify(val) + ')';
+        } catch (_) {
+            return 'Object';
+        }
+    }
+    // errors
+    if (val instanceof Error) {
+        return `${val.name}: ${val.message}\n${val.stack}`;
+    }
+    // TODO we could test for more things here, like `Set`s and `Map`s.
+    return className;
+}
+
+function _assertClass(instance, klass) {
+    if (!(instance instanceof klass)) {
+        throw new Error(`expected instance of ${klass.name}`);
+    }
+}
+
+function passArrayF32ToWasm0(arg, malloc) {
+    const ptr = malloc(arg.length * 4, 4) >>> 0;
+    getFloat32ArrayMemory0().set(arg, ptr / 4);
+    WASM_VECTOR_LEN = arg.length;
+    return ptr;
+}
+
+function takeFromExternrefTable0(idx) {
+    const value = wasm.__wbindgen_export_2.get(idx);
+    wasm.__externref_table_dealloc(idx);
+    return value;
+}
+
+let cachedFloat64ArrayMemory0 = null;
+
+function getFloat64ArrayMemory0() {
+    if (cachedFloat64ArrayMemory0 === null || cachedFloat64ArrayMemory0.byteLength === 0) {
+        cachedFloat64ArrayMemory0 = new Float64Array(wasm.memory.buffer);
+    }
+    return cachedFloat64ArrayMemory0;
+}
+
+function passArrayF64ToWasm0(arg, malloc) {
+    const ptr = malloc(arg.length * 8, 8) >>> 0;
+    getFloat64ArrayMemory0().set(arg, ptr / 8);
+    WASM_VECTOR_LEN = arg.length;
+    return ptr;
+}
+
+function getArrayF64FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getFloat64ArrayMemory0().subarray(ptr / 8, ptr / 8 + len);
+}
+/**
+ * @param {string} canvas_id
+ * @param {number} a
+ * @param {number} b
+ * @param {number} r
+ * @param {number} polygon_radius
+ * @param {number} polygon_sides
+ * @param {number} num_polygons
+ * @returns {TunnelHandle}
+ */
+export function start_simple_tunnel(canvas_id, a, b, r, polygon_radius, polygon_sides, num_polygons) {
+    const ptr0 = passStringToWasm0(canvas_id, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.start_simple_tunnel(ptr0, len0, a, b, r, polygon_radius, polygon_sides, num_polygons);
+    if (ret[2]) {
+        throw takeFromExternrefTable0(ret[1]);
+    }
+    return TunnelHandle.__wrap(ret[0]);
+}
+
+let cachedUint32ArrayMemory0 = null;
+
+function getUint32ArrayMemory0() {
+    if (cachedUint32ArrayMemory0 === null || cachedUint32ArrayMemory0.byteLength === 0) {
+        cachedUint32ArrayMemory0 = new Uint32Array(wasm.memory.buffer);
+    }
+    return cachedUint32ArrayMemory0;
+}
+
+function getArrayU32FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getUint32ArrayMemory0().subarray(ptr / 4, ptr / 4 + len);
+}
+function __wbg_adapter_22(arg0, arg1, arg2) {
+    wasm.closure22_externref_shim(arg0, arg1, arg2);
+}
+
+function __wbg_adapter_25(arg0, arg1, arg2) {
+    wasm._dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h0edc57afd33ee676(arg0, arg1, arg2);
+}
+
+/**
+ * Where the camera watches the curve from
+ * @enum {0 | 1 | 2}
+ */
+export const CameraMode = Object.freeze({
+    /**
+     * On the curve, looking along the tangent
+     */
+    Inside: 0, "0": "Inside",
+    /**
+     * Trailing behind and above the current point, looking at it
+     */
+    Chase: 1, "1": "Chase",
+    /**
+     * Orbiting the whole curve
+     */
+    Outside: 2, "2": "Outside",
+});
+/**
+ * The scalar along the curve that ring colours follow
+ * @enum {0 | 1 | 2 | 3 | 4 | 5 | 6 | 7}
+ */
+export const ColorMetric = Object.freeze({
+    /**
+     * Position of the ring along the tunnel
+     */
+    RingIndex: 0, "0": "RingIndex",
+    /**
+     * κ; bright where the curve bends hardest
+     */
+    Curvature: 1, "1": "Curvature",
+    /**
+     * τ; how fast the curve leaves its osculating plane
+     */
+    Torsion: 2, "2": "Torsion",
+    /**
+     * Parametric speed |r'(t)|
+     */
+    Speed: 3, "3": "Speed",
+    /**
+     * z of `position(t)`
+     */
+    Height: 4, "4": "Height",
+    /**
+     * Angle above the xy plane as seen from the origin
+     */
+    Latitude: 5, "5": "Latitude",
+    /**
+     * Angle around the z axis. Uses the palette's full range regardless
+     * of the curve, so cyclic colormaps wrap without a seam.
+     */
+    Longitude: 6, "6": "Longitude",
+    /**
+     * Distance to the nearest other part of the curve; 0 at crossings
+     */
+    Proximity: 7, "7": "Proximity",
+});
+/**
+ * Named colour scales over [0, 1]
+ * @enum {0 | 1 | 2 | 3 | 4}
+ */
+export const Colormap = Object.freeze({
+    /**
+     * The original ring colours: three sines of different frequency.
+     * Cyclic, so closed loops have no seam.
+     */
+    Sine: 0, "0": "Sine",
+    /**
+     * Perceptually uniform dark blue to yellow (matplotlib)
+     */
+    Viridis: 1, "1": "Viridis",
+    /**
+     * Perceptually uniform black through purple to pale yellow (matplotlib)
+     */
+    Magma: 2, "2": "Magma",
+    /**
+     * High-contrast blue to red rainbow (Google)
+     */
+    Turbo: 3, "3": "Turbo",
+    /**
+     * Full HSV hue circle; cyclic
+     */
+    Rainbow: 4, "4": "Rainbow",
+});
+/**
+ * How fog thickens with view-space depth
+ * @enum {0 | 1 | 2}
+ */
+export const FogMode = Object.freeze({
+    /**
+     * No fog; the default, so the tunnel looks as it always has until
+     * fog is asked for
+     */
+    Off: 0, "0": "Off",
+    /**
+     * Clear up to `start`, solid from `end`
+     */
+    Linear: 1, "1": "Linear",
+    /**
+     * Visibility exp(-density · depth)
+     */
+    Exponential: 2, "2": "Exponential",
+});
+/**
+ * How the ring orientation is chosen along the curve
+ * @enum {0 | 1 | 2}
+ */
+export const FrameMode = Object.freeze({
+    /**
+     * Normal points away from the origin (the curve's own `frame`)
+     */
+    Radial: 0, "0": "Radial",
+    /**
+     * Normal follows the curve's bending direction
+     */
+    Frenet: 1, "1": "Frenet",
+    /**
+     * Parallel transport, with the leftover twist spread over the loop
+     */
+    RotationMinimizing: 2, "2": "RotationMinimizing",
+});
+/**
+ * How progress along the curve is measured
+ * @enum {0 | 1}
+ */
+export const Parameterization = Object.freeze({
+    /**
+     * Equal steps in t; spacing follows the local speed |r'(t)|
+     */
+    Parameter: 0, "0": "Parameter",
+    /**
+     * Equal steps in distance along the curve
+     */
+    ArcLength: 1, "1": "ArcLength",
+});
+/**
+ * How the translucent tunnel walls are composited
+ * @enum {0 | 1}
+ */
+export const Transparency = Object.freeze({
+    /**
+     * Back faces, then front faces, blended in triangle order
+     */
+    TwoPass: 0, "0": "TwoPass",
+    /**
+     * Weighted blended order-independent transparency (McGuire & Bavoil
+     * 2013). Needs renderable float textures; falls back to `TwoPass`.
+     */
+    WeightedBlended: 1, "1": "WeightedBlended",
+});
+
+const Lissajou3DFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_lissajou3d_free(ptr >>> 0, 1));
+/**
+ * Spherical Lissajous curve
+ *
+ * θ(t) = a·t + δθ, or θ(t) = c + A·sin(a·t + δθ) with a polar band
+ * φ(t) = b·t + δφ
+ * position = (rx sinθ cosφ, ry sinθ sinφ, rz cosθ)
+ */
+export class Lissajou3D {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        Lissajou3DFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_lissajou3d_free(ptr, 0);
+    }
+    /**
+     * Unnormalized derivative of the given order (0 = position, up to 3)
+     * as `[x, y, z]`
+     * @param {number} t
+     * @param {number} order
+     * @returns {Float64Array | undefined}
+     */
+    derivative(t, order) {
+        const ret = wasm.lissajou3d_derivative(this.__wbg_ptr, t, order);
+        let v1;
+        if (ret[0] !== 0) {
+            v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+            wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+        }
+        return v1;
+    }
+    /**
+     * Distance between the start and the end of one period. Only the
+     * positions are compared: an open curve can come back through its
+     * starting point heading elsewhere, typically at a pole, and still
+     * report a gap of about 0. Use `is_closed`, which also compares the
+     * tangents, to decide whether the tunnel joins up.
+     * @returns {number}
+     */
+    closure_gap() {
+        const ret = wasm.lissajou3d_closure_gap(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get phase_theta() {
+        const ret = wasm.lissajou3d_phase_theta(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * JS constructor. Everything after `r` is optional: phases for θ and φ,
+     * a polar band (centre defaults to the equator, amplitude to π/2) and
+     * an `[rx, ry, rz]` ellipsoid that replaces `r`.
+     * @param {number} a
+     * @param {number} b
+     * @param {number} r
+     * @param {number | null} [phase_theta]
+     * @param {number | null} [phase_phi]
+     * @param {number | null} [polar_center]
+     * @param {number | null} [polar_amplitude]
+     * @param {Float64Array | null} [radii]
+     */
+    constructor(a, b, r, phase_theta, phase_phi, polar_center, polar_amplitude, radii) {
+        var ptr0 = isLikeNone(radii) ? 0 : passArrayF64ToWasm0(radii, wasm.__wbindgen_malloc);
+        var len0 = WASM_VECTOR_LEN;
+        const ret = wasm.lissajou3d_with_options(a, b, r, !isLikeNone(phase_theta), isLikeNone(phase_theta) ? 0 : phase_theta, !isLikeNone(phase_phi), isLikeNone(phase_phi) ? 0 : phase_phi, !isLikeNone(polar_center), isLikeNone(polar_center) ? 0 : polar_center, !isLikeNone(polar_amplitude), isLikeNone(polar_amplitude) ? 0 : polar_amplitude, ptr0, len0);
+        this.__wbg_ptr = ret >>> 0;
+        Lissajou3DFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @returns {number}
+     */
+    get a() {
+        const ret = wasm.lissajou3d_a(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get b() {
+        const ret = wasm.lissajou3d_b(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Largest of the three radii
+     * @returns {number}
+     */
+    get r() {
+        const ret = wasm.lissajou3d_r(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {Float64Array}
+     */
+    get radii() {
+        const ret = wasm.lissajou3d_radii(this.__wbg_ptr);
+        var v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+        return v1;
+    }
+    /**
+     * Parametric speed |r'(t)|
+     * @param {number} t
+     * @returns {number}
+     */
+    speed(t) {
+        const ret = wasm.lissajou3d_speed(this.__wbg_ptr, t);
+        return ret;
+    }
+    /**
+     * Parameter span of one loop (see `is_closed`)
+     * @returns {number}
+     */
+    period() {
+        const ret = wasm.lissajou3d_period(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Torsion τ(t)
+     * @param {number} t
+     * @returns {number}
+     */
+    torsion(t) {
+        const ret = wasm.lissajou3d_torsion(this.__wbg_ptr, t);
+        return ret;
+    }
+    /**
+     * Curvature κ(t)
+     * @param {number} t
+     * @returns {number}
+     */
+    curvature(t) {
+        const ret = wasm.lissajou3d_curvature(this.__wbg_ptr, t);
+        return ret;
+    }
+    /**
+     * Whether the curve returns to its start after `period`. False when
+     * a:b has no rational approximation with a small denominator.
+     * @returns {boolean}
+     */
+    is_closed() {
+        const ret = wasm.lissajou3d_is_closed(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get phase_phi() {
+        const ret = wasm.lissajou3d_phase_phi(this.__wbg_ptr);
+        return ret;
+    }
+}
+
+const ProfileFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_profile_free(ptr >>> 0, 1));
+/**
+ * Closed 2D cross-section swept along the curve.
+ *
+ * Points live in the ring plane: x along the frame normal, y along the
+ * binormal. They are stored counter-clockwise without repeating the first
+ * point, so every profile produces the same triangle winding.
+ */
+export class Profile {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(Profile.prototype);
+        obj.__wbg_ptr = ptr;
+        ProfileFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        ProfileFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_profile_free(ptr, 0);
+    }
+    /**
+     * Profile from interleaved `[x0, y0, x1, y1, ...]` coordinates
+     * @param {Float64Array} coords
+     * @returns {Profile}
+     */
+    static from_coords(coords) {
+        const ptr0 = passArrayF64ToWasm0(coords, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.profile_from_coords(ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Profile.__wrap(ret[0]);
+    }
+    /**
+     * Rectangle centred on the origin with circular corners
+     * @param {number} width
+     * @param {number} height
+     * @param {number} corner_radius
+     * @param {number} corner_segments
+     * @returns {Profile}
+     */
+    static rounded_rect(width, height, corner_radius, corner_segments) {
+        const ret = wasm.profile_rounded_rect(width, height, corner_radius, corner_segments);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Profile.__wrap(ret[0]);
+    }
+    /**
+     * |x/a|^n + |y/b|^n = 1. n = 2 is an ellipse, large n tends to a rectangle
+     * @param {number} a
+     * @param {number} b
+     * @param {number} exponent
+     * @param {number} segments
+     * @returns {Profile}
+     */
+    static superellipse(a, b, exponent, segments) {
+        const ret = wasm.profile_superellipse(a, b, exponent, segments);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Profile.__wrap(ret[0]);
+    }
+    /**
+     * Profile from the first subpath of an SVG path string, e.g.
+     * `"M 0 0 L 10 0 Q 10 10 0 10 Z"`. SVG's y axis points down, so it is
+     * flipped here.
+     * @param {string} d
+     * @returns {Profile}
+     */
+    static from_svg_path(d) {
+        const ptr0 = passStringToWasm0(d, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.profile_from_svg_path(ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Profile.__wrap(ret[0]);
+    }
+    /**
+     * Centre on the bounding box and scale so the farthest point is `radius`
+     * @param {number} radius
+     * @returns {Profile}
+     */
+    fit(radius) {
+        const ret = wasm.profile_fit(this.__wbg_ptr, radius);
+        return Profile.__wrap(ret);
+    }
+    /**
+     * @returns {number}
+     */
+    get len() {
+        const ret = wasm.profile_len(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Star alternating between `outer` and `inner` radius
+     * @param {number} outer
+     * @param {number} inner
+     * @param {number} tips
+     * @returns {Profile}
+     */
+    static star(outer, inner, tips) {
+        const ret = wasm.profile_star(outer, inner, tips);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Profile.__wrap(ret[0]);
+    }
+    /**
+     * Interleaved `[x0, y0, x1, y1, ...]`
+     * @returns {Float64Array}
+     */
+    coords() {
+        const ret = wasm.profile_coords(this.__wbg_ptr);
+        var v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+        return v1;
+    }
+    /**
+     * Distance from the origin to the farthest point
+     * @returns {number}
+     */
+    get radius() {
+        const ret = wasm.profile_radius(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Regular n-gon, first vertex on the x axis
+     * @param {number} radius
+     * @param {number} sides
+     * @returns {Profile}
+     */
+    static regular(radius, sides) {
+        const ret = wasm.profile_regular(radius, sides);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return Profile.__wrap(ret[0]);
+    }
+    /**
+     * @returns {boolean}
+     */
+    is_empty() {
+        const ret = wasm.profile_is_empty(this.__wbg_ptr);
+        return ret !== 0;
+    }
+}
+
+const TunnelHandleFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_tunnelhandle_free(ptr >>> 0, 1));
+/**
+ * A running tunnel animation on one canvas.
+ *
+ * Each handle owns its own settings, so several canvases can run side by
+ * side. Calling `stop()` (or `free()`) cancels the animation loop and
+ * deletes the GL buffers.
+ */
+export class TunnelHandle {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(TunnelHandle.prototype);
+        obj.__wbg_ptr = ptr;
+        TunnelHandleFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        TunnelHandleFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_tunnelhandle_free(ptr, 0);
+    }
+    /**
+     * Current mesh plus an animated camera replaying one loop of the inside
+     * view over `duration` seconds, as a glTF binary (.glb)
+     * @param {number} duration
+     * @param {number} samples
+     * @returns {Uint8Array}
+     */
+    export_glb(duration, samples) {
+        const ret = wasm.tunnelhandle_export_glb(this.__wbg_ptr, duration, samples);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * Current mesh as Wavefront OBJ text
+     * @returns {string}
+     */
+    export_obj() {
+        let deferred1_0;
+        let deferred1_1;
+        try {
+            const ret = wasm.tunnelhandle_export_obj(this.__wbg_ptr);
+            deferred1_0 = ret[0];
+            deferred1_1 = ret[1];
+            return getStringFromWasm0(ret[0], ret[1]);
+        } finally {
+            wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+        }
+    }
+    /**
+     * @returns {FrameMode}
+     */
+    get frame_mode() {
+        const ret = wasm.tunnelhandle_frame_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get wire_width() {
+        const ret = wasm.tunnelhandle_wire_width(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get auto_rotate() {
+        const ret = wasm.tunnelhandle_auto_rotate(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {CameraMode}
+     */
+    get camera_mode() {
+        const ret = wasm.tunnelhandle_camera_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get fog_density() {
+        const ret = wasm.tunnelhandle_fog_density(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get pulse_speed() {
+        const ret = wasm.tunnelhandle_pulse_speed(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get pulse_width() {
+        const ret = wasm.tunnelhandle_pulse_width(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Frame the whole curve again in the outside view
+     */
+    reset_orbit() {
+        wasm.tunnelhandle_reset_orbit(this.__wbg_ptr);
+    }
+    /**
+     * @param {number} ambient
+     */
+    set_ambient(ambient) {
+        wasm.tunnelhandle_set_ambient(this.__wbg_ptr, ambient);
+    }
+    /**
+     * @param {number} diffuse
+     */
+    set_diffuse(diffuse) {
+        wasm.tunnelhandle_set_diffuse(this.__wbg_ptr, diffuse);
+    }
+    /**
+     * Sweep a different cross-section, e.g. `Profile.star(1.2, 0.6, 5)`
+     * @param {Profile} profile
+     */
+    set_profile(profile) {
+        _assertClass(profile, Profile);
+        wasm.tunnelhandle_set_profile(this.__wbg_ptr, profile.__wbg_ptr);
+    }
+    /**
+     * @returns {boolean}
+     */
+    get show_tunnel() {
+        const ret = wasm.tunnelhandle_show_tunnel(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get texture_mix() {
+        const ret = wasm.tunnelhandle_texture_mix(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get chase_height() {
+        const ret = wasm.tunnelhandle_chase_height(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get num_polygons() {
+        const ret = wasm.tunnelhandle_num_polygons(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {boolean}
+     */
+    get outside_view() {
+        const ret = wasm.tunnelhandle_outside_view(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {Parameterization}
+     */
+    get ring_spacing() {
+        const ret = wasm.tunnelhandle_ring_spacing(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * What the ring colours follow along the curve, e.g.
+     * `ColorMetric.Curvature` to pick out the tight bends
+     * @param {ColorMetric} metric
+     */
+    set_color_by(metric) {
+        wasm.tunnelhandle_set_color_by(this.__wbg_ptr, metric);
+    }
+    /**
+     * Colour the rings with a named colour scale
+     * @param {Colormap} colormap
+     */
+    set_colormap(colormap) {
+        wasm.tunnelhandle_set_colormap(this.__wbg_ptr, colormap);
+    }
+    /**
+     * Fog for the inside view: off, linear or exponential in depth
+     * @param {FogMode} mode
+     */
+    set_fog_mode(mode) {
+        wasm.tunnelhandle_set_fog_mode(this.__wbg_ptr, mode);
+    }
+    /**
+     * Colour the rings with a gradient through `[x0, r0, g0, b0, x1, ...]`,
+     * positions in [0, 1] along the curve
+     * @param {Float32Array} stops
+     */
+    set_gradient(stops) {
+        const ptr0 = passArrayF32ToWasm0(stops, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.tunnelhandle_set_gradient(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * Turn the Blinn-Phong shading of walls and grid tubes on or off
+     * @param {boolean} enabled
+     */
+    set_lighting(enabled) {
+        wasm.tunnelhandle_set_lighting(this.__wbg_ptr, enabled);
+    }
+    /**
+     * @param {number} specular
+     */
+    set_specular(specular) {
+        wasm.tunnelhandle_set_specular(this.__wbg_ptr, specular);
+    }
+    /**
+     * @returns {Transparency}
+     */
+    get transparency() {
+        const ret = wasm.tunnelhandle_transparency(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get wall_opacity() {
+        const ret = wasm.tunnelhandle_wall_opacity(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {Parameterization}
+     */
+    get camera_travel() {
+        const ret = wasm.tunnelhandle_camera_travel(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get orbit_damping() {
+        const ret = wasm.tunnelhandle_orbit_damping(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get palette_speed() {
+        const ret = wasm.tunnelhandle_palette_speed(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} r
+     * @param {number} g
+     * @param {number} b
+     */
+    set_fog_color(r, g, b) {
+        wasm.tunnelhandle_set_fog_color(this.__wbg_ptr, r, g, b);
+    }
+    /**
+     * Linear fog: clear up to `start`, solid from `end`, in view-space depth
+     * @param {number} start
+     * @param {number} end
+     */
+    set_fog_range(start, end) {
+        wasm.tunnelhandle_set_fog_range(this.__wbg_ptr, start, end);
+    }
+    /**
+     * Strength of the light attached to the camera
+     * @param {number} intensity
+     */
+    set_headlight(intensity) {
+        wasm.tunnelhandle_set_headlight(this.__wbg_ptr, intensity);
+    }
+    /**
+     * Blinn-Phong exponent; higher is a tighter highlight
+     * @param {number} shininess
+     */
+    set_shininess(shininess) {
+        wasm.tunnelhandle_set_shininess(this.__wbg_ptr, shininess);
+    }
+    /**
+     * @returns {boolean}
+     */
+    get show_latitude() {
+        const ret = wasm.tunnelhandle_show_latitude(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {number}
+     */
+    get chase_distance() {
+        const ret = wasm.tunnelhandle_chase_distance(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get palette_repeat() {
+        const ret = wasm.tunnelhandle_palette_repeat(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get pulse_strength() {
+        const ret = wasm.tunnelhandle_pulse_strength(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Ring orientation for both the mesh and the inside camera
+     * @param {FrameMode} mode
+     */
+    set_frame_mode(mode) {
+        wasm.tunnelhandle_set_frame_mode(this.__wbg_ptr, mode);
+    }
+    /**
+     * Put an `<img>` on the walls once it has loaded
+     * @param {HTMLImageElement} image
+     */
+    set_wall_image(image) {
+        wasm.tunnelhandle_set_wall_image(this.__wbg_ptr, image);
+    }
+    /**
+     * Stream a `<video>` onto the walls, re-uploading its current frame
+     * every frame. The video must be same-origin or served with CORS.
+     * @param {HTMLVideoElement} video
+     */
+    set_wall_video(video) {
+        wasm.tunnelhandle_set_wall_video(this.__wbg_ptr, video);
+    }
+    /**
+     * Draw the grid as tubes `width` across instead of 1px lines. 0
+     * switches back to lines.
+     * @param {number} width
+     */
+    set_wire_width(width) {
+        wasm.tunnelhandle_set_wire_width(this.__wbg_ptr, width);
+    }
+    /**
+     * @returns {boolean}
+     */
+    get show_longitude() {
+        const ret = wasm.tunnelhandle_show_longitude(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {Float32Array}
+     */
+    get texture_scroll() {
+        const ret = wasm.tunnelhandle_texture_scroll(this.__wbg_ptr);
+        var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @returns {Float32Array}
+     */
+    get texture_tiling() {
+        const ret = wasm.tunnelhandle_texture_tiling(this.__wbg_ptr);
+        var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * Spin the outside view around the curve at `speed` radians per
+     * second whenever nobody is dragging; 0 stops
+     * @param {number} speed
+     */
+    set_auto_rotate(speed) {
+        wasm.tunnelhandle_set_auto_rotate(this.__wbg_ptr, speed);
+    }
+    /**
+     * Switch between the inside, chase and outside cameras. The view
+     * glides to the new camera over `transition_duration` seconds.
+     * @param {CameraMode} mode
+     */
+    set_camera_mode(mode) {
+        wasm.tunnelhandle_set_camera_mode(this.__wbg_ptr, mode);
+    }
+    /**
+     * Exponential fog: visibility exp(-density · depth)
+     * @param {number} density
+     */
+    set_fog_density(density) {
+        wasm.tunnelhandle_set_fog_density(this.__wbg_ptr, density);
+    }
+    /**
+     * @param {boolean} show
+     */
+    set_show_tunnel(show) {
+        wasm.tunnelhandle_set_show_tunnel(this.__wbg_ptr, show);
+    }
+    /**
+     * Blend between the ring colours (0) and the texture (1)
+     * @param {number} mix
+     */
+    set_texture_mix(mix) {
+        wasm.tunnelhandle_set_texture_mix(this.__wbg_ptr, mix);
+    }
+    /**
+     * Stream a `<canvas>` onto the walls every frame, e.g. a 2D sketch
+     * @param {HTMLCanvasElement} canvas
+     */
+    set_wall_canvas(canvas) {
+        wasm.tunnelhandle_set_wall_canvas(this.__wbg_ptr, canvas);
+    }
+    /**
+     * Same solid as `export_solid_stl`, as a 3MF package in millimetres
+     * @param {number} thickness
+     * @returns {Uint8Array}
+     */
+    export_solid_3mf(thickness) {
+        const ret = wasm.tunnelhandle_export_solid_3mf(this.__wbg_ptr, thickness);
+        if (ret[3]) {
+            throw takeFromExternrefTable0(ret[2]);
+        }
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * Current tube as a watertight solid with walls `thickness` thick, as
+     * binary STL for printing. Throws if the wall is too thick for the
+     * profile.
+     * @param {number} thickness
+     * @returns {Uint8Array}
+     */
+    export_solid_stl(thickness) {
+        const ret = wasm.tunnelhandle_export_solid_stl(this.__wbg_ptr, thickness);
+        if (ret[3]) {
+            throw takeFromExternrefTable0(ret[2]);
+        }
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * Current mesh as ASCII STL text
+     * @returns {string}
+     */
+    export_stl_ascii() {
+        let deferred1_0;
+        let deferred1_1;
+        try {
+            const ret = wasm.tunnelhandle_export_stl_ascii(this.__wbg_ptr);
+            deferred1_0 = ret[0];
+            deferred1_1 = ret[1];
+            return getStringFromWasm0(ret[0], ret[1]);
+        } finally {
+            wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+        }
+    }
+    /**
+     * How far the chase camera trails behind the current point and rises
+     * above it, in tube radii
+     * @param {number} distance
+     * @param {number} height
+     */
+    set_chase_offset(distance, height) {
+        wasm.tunnelhandle_set_chase_offset(this.__wbg_ptr, distance, height);
+    }
+    /**
+     * @param {number} num
+     */
+    set_num_polygons(num) {
+        wasm.tunnelhandle_set_num_polygons(this.__wbg_ptr, num);
+    }
+    /**
+     * Outside view on, or back to the inside view
+     * @param {boolean} outside
+     */
+    set_outside_view(outside) {
+        wasm.tunnelhandle_set_outside_view(this.__wbg_ptr, outside);
+    }
+    /**
+     * Up to 4 point lights on the curve at parameters `params`, with
+     * colours `[r0, g0, b0, r1, ...]`. No colours means white lights.
+     * @param {Float64Array} params
+     * @param {Float32Array} colors
+     */
+    set_point_lights(params, colors) {
+        const ptr0 = passArrayF64ToWasm0(params, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passArrayF32ToWasm0(colors, wasm.__wbindgen_malloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.tunnelhandle_set_point_lights(this.__wbg_ptr, ptr0, len0, ptr1, len1);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * Place rings at equal steps of t or of distance along the curve
+     * @param {Parameterization} spacing
+     */
+    set_ring_spacing(spacing) {
+        wasm.tunnelhandle_set_ring_spacing(this.__wbg_ptr, spacing);
+    }
+    /**
+     * How the translucent walls are blended. `WeightedBlended` composites
+     * crossing loops correctly but needs float render targets; without
+     * them the walls stay on `TwoPass` and a warning is logged.
+     * @param {Transparency} mode
+     */
+    set_transparency(mode) {
+        wasm.tunnelhandle_set_transparency(this.__wbg_ptr, mode);
+    }
+    /**
+     * Opacity of the tunnel walls, 0.05 by default. Textured walls usually
+     * want more.
+     * @param {number} opacity
+     */
+    set_wall_opacity(opacity) {
+        wasm.tunnelhandle_set_wall_opacity(this.__wbg_ptr, opacity);
+    }
+    /**
+     * Current mesh as binary STL, returned to JS as a `Uint8Array`
+     * @returns {Uint8Array}
+     */
+    export_stl_binary() {
+        const ret = wasm.tunnelhandle_export_stl_binary(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @returns {boolean}
+     */
+    get palette_animation() {
+        const ret = wasm.tunnelhandle_palette_animation(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Advance the camera at constant speed in t or in distance
+     * @param {Parameterization} travel
+     */
+    set_camera_travel(travel) {
+        wasm.tunnelhandle_set_camera_travel(this.__wbg_ptr, travel);
+    }
+    /**
+     * How long the orbit camera glides after a drag: the fraction of the
+     * motion left after 1/60 s, from 0 (stops dead) to just under 1
+     * @param {number} damping
+     */
+    set_orbit_damping(damping) {
+        wasm.tunnelhandle_set_orbit_damping(this.__wbg_ptr, damping);
+    }
+    /**
+     * Palette lengths per second that the colours flow along the tunnel;
+     * negative flows backwards
+     * @param {number} speed
+     */
+    set_palette_speed(speed) {
+        wasm.tunnelhandle_set_palette_speed(this.__wbg_ptr, speed);
+    }
+    /**
+     * @param {boolean} show
+     */
+    set_show_latitude(show) {
+        wasm.tunnelhandle_set_show_latitude(this.__wbg_ptr, show);
+    }
+    /**
+     * Go back to plain ring colours
+     */
+    clear_wall_texture() {
+        wasm.tunnelhandle_clear_wall_texture(this.__wbg_ptr);
+    }
+    /**
+     * Parameter ranges where the tube collides with itself, as
+     * `[t_start0, t_end0, t_start1, t_end1, ...]`
+     * @returns {Float64Array}
+     */
+    self_intersections() {
+        const ret = wasm.tunnelhandle_self_intersections(this.__wbg_ptr);
+        var v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+        return v1;
+    }
+    /**
+     * Times the palette repeats along the tunnel while animating
+     * @param {number} repeat
+     */
+    set_palette_repeat(repeat) {
+        wasm.tunnelhandle_set_palette_repeat(this.__wbg_ptr, repeat);
+    }
+    /**
+     * @param {boolean} show
+     */
+    set_show_longitude(show) {
+        wasm.tunnelhandle_set_show_longitude(this.__wbg_ptr, show);
+    }
+    /**
+     * Scroll the texture by `u` and `v` texture widths per second
+     * @param {number} u
+     * @param {number} v
+     */
+    set_texture_scroll(u, v) {
+        wasm.tunnelhandle_set_texture_scroll(this.__wbg_ptr, u, v);
+    }
+    /**
+     * Repeats of the texture around the profile (`u`) and along the curve (`v`)
+     * @param {number} u
+     * @param {number} v
+     */
+    set_texture_tiling(u, v) {
+        wasm.tunnelhandle_set_texture_tiling(this.__wbg_ptr, u, v);
+    }
+    /**
+     * Scale along the frame normal and binormal as `[t0, s0, t1, s1, ...]`
+     * @param {Float64Array} keys_x
+     * @param {Float64Array} keys_y
+     */
+    set_scale_keyframes(keys_x, keys_y) {
+        const ptr0 = passArrayF64ToWasm0(keys_x, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passArrayF64ToWasm0(keys_y, wasm.__wbindgen_malloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.tunnelhandle_set_scale_keyframes(this.__wbg_ptr, ptr0, len0, ptr1, len1);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * Twist around the tangent in radians as `[t0, angle0, t1, angle1, ...]`
+     * @param {Float64Array} keys
+     */
+    set_twist_keyframes(keys) {
+        const ptr0 = passArrayF64ToWasm0(keys, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.tunnelhandle_set_twist_keyframes(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * @returns {number}
+     */
+    get transition_duration() {
+        const ret = wasm.tunnelhandle_transition_duration(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Limits of the angle around the vertical axis in radians. Infinite
+     * bounds (the default) let the camera circle freely.
+     * @param {number} min
+     * @param {number} max
+     */
+    set_orbit_yaw_limits(min, max) {
+        wasm.tunnelhandle_set_orbit_yaw_limits(this.__wbg_ptr, min, max);
+    }
+    /**
+     * Radius scale along the curve as `[t0, s0, t1, s1, ...]`. An empty
+     * array restores a constant tube.
+     * @param {Float64Array} keys
+     */
+    set_radius_keyframes(keys) {
+        const ptr0 = passArrayF64ToWasm0(keys, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.tunnelhandle_set_radius_keyframes(this.__wbg_ptr, ptr0, len0);
+        if (ret[1]) {
+            throw takeFromExternrefTable0(ret[0]);
+        }
+    }
+    /**
+     * Colour on the GPU from the palette texture, so palettes flow, pulse
+     * and swap without rebuilding the mesh
+     * @param {boolean} enabled
+     */
+    set_palette_animation(enabled) {
+        wasm.tunnelhandle_set_palette_animation(this.__wbg_ptr, enabled);
+    }
+    /**
+     * Elevation limits of the orbit camera in radians, within ±π/2
+     * @param {number} min
+     * @param {number} max
+     */
+    set_orbit_pitch_limits(min, max) {
+        wasm.tunnelhandle_set_orbit_pitch_limits(this.__wbg_ptr, min, max);
+    }
+    /**
+     * Seconds a camera mode switch takes; 0 cuts straight to the new view
+     * @param {number} seconds
+     */
+    set_transition_duration(seconds) {
+        wasm.tunnelhandle_set_transition_duration(this.__wbg_ptr, seconds);
+    }
+    /**
+     * Nearest and farthest the orbit camera may zoom, in world units.
+     * Framing a curve (`set_curve`, `reset_orbit`) sets them to a tenth and
+     * ten times the framing distance.
+     * @param {number} min
+     * @param {number} max
+     */
+    set_orbit_distance_limits(min, max) {
+        wasm.tunnelhandle_set_orbit_distance_limits(this.__wbg_ptr, min, max);
+    }
+    /**
+     * @returns {number}
+     */
+    get rim() {
+        const ret = wasm.tunnelhandle_rim(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Cancel the animation loop and release the GL buffers. Safe to call twice.
+     */
+    stop() {
+        wasm.tunnelhandle_stop(this.__wbg_ptr);
+    }
+    /**
+     * @returns {number}
+     */
+    get time() {
+        const ret = wasm.tunnelhandle_time(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Put the camera back at the start of the curve
+     */
+    reset() {
+        wasm.tunnelhandle_reset(this.__wbg_ptr);
+    }
+    /**
+     * @returns {number}
+     */
+    get speed() {
+        const ret = wasm.tunnelhandle_speed(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get ambient() {
+        const ret = wasm.tunnelhandle_ambient(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Folds, self-proximity and mesh defects for the current parameters,
+     * with the largest radius that avoids them
+     * @returns {TunnelReport}
+     */
+    analyze() {
+        const ret = wasm.tunnelhandle_analyze(this.__wbg_ptr);
+        return TunnelReport.__wrap(ret);
+    }
+    /**
+     * @returns {number}
+     */
+    get diffuse() {
+        const ret = wasm.tunnelhandle_diffuse(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get fog_end() {
+        const ret = wasm.tunnelhandle_fog_end(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Whether the animation loop is still scheduled
+     * @returns {boolean}
+     */
+    get running() {
+        const ret = wasm.tunnelhandle_running(this.__wbg_ptr);
+        return ret !== 0;
     }
-    if (type == 'symbol') {
-        const description = val.description;
-        if (description == null) {
-            return 'Symbol';
-        } else {
-            return `Symbol(${description})`;
-        }
+    /**
+     * Glow where the walls turn edge-on to the camera
+     * @param {number} rim
+     */
+    set_rim(rim) {
+        wasm.tunnelhandle_set_rim(this.__wbg_ptr, rim);
     }
-    if (type == 'function') {
-        const name = val.name;
-        if (typeof name == 'string' && name.length > 0) {
-            return `Function(${name})`;
-        } else {
-            return 'Function';
-        }
+    /**
+     * @returns {ColorMetric}
+     */
+    get color_by() {
+        const ret = wasm.tunnelhandle_color_by(this.__wbg_ptr);
+        return ret;
     }
-    // objects
-    if (Array.isArray(val)) {
-        const length = val.length;
-        let debug = '[';
-        if (length > 0) {
-            debug += debugString(val[0]);
-        }
-        for(let i = 1; i < length; i++) {
-            debug += ', ' + debugString(val[i]);
-        }
-        debug += ']';
-        return debug;
+    /**
+     * @returns {FogMode}
+     */
+    get fog_mode() {
+        const ret = wasm.tunnelhandle_fog_mode(this.__wbg_ptr);
+        return ret;
     }
-    // Test for built-in
-    const builtInMatches = /\[object ([^\]]+)\]/.exec(toString.call(val));
-    let className;
-    if (builtInMatches && builtInMatches.length > 1) {
-        className = builtInMatches[1];
-    } else {
-        // Failed to match the standard '[object ClassName]'
-        return toString.call(val);
+    /**
+     * @returns {boolean}
+     */
+    get lighting() {
+        const ret = wasm.tunnelhandle_lighting(this.__wbg_ptr);
+        return ret !== 0;
     }
-    if (className == 'Object') {
-        // we're a user defined class or Object
-        // JSON.stringify avoids problems with cycles, and is generally much
-        // easier than looping through ownProperties of `val`.
-        try {
-            return 'Object(' + JSON.stringify(val) + ')';
-        } catch (_) {
-            return 'Object';
-        }
+    /**
+     * @param {number} time
+     */
+    set_time(time) {
+        wasm.tunnelhandle_set_time(this.__wbg_ptr, time);
     }
-    // errors
-    if (val instanceof Error) {
-        return `${val.name}: ${val.message}\n${val.stack}`;
+    /**
+     * @returns {number}
+     */
+    get specular() {
+        const ret = wasm.tunnelhandle_specular(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {Float32Array}
+     */
+    get fog_color() {
+        const ret = wasm.tunnelhandle_fog_color(this.__wbg_ptr);
+        var v1 = getArrayF32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @returns {number}
+     */
+    get fog_start() {
+        const ret = wasm.tunnelhandle_fog_start(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    get headlight() {
+        const ret = wasm.tunnelhandle_headlight(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Follow a different curve, e.g. one built with phases or a polar band
+     * @param {Lissajou3D} curve
+     */
+    set_curve(curve) {
+        _assertClass(curve, Lissajou3D);
+        wasm.tunnelhandle_set_curve(this.__wbg_ptr, curve.__wbg_ptr);
+    }
+    /**
+     * A bright band racing along the tunnel at `speed` trips per second,
+     * `width` of the tunnel wide, brightening by up to `strength`. A
+     * strength of 0 turns it off.
+     * @param {number} speed
+     * @param {number} width
+     * @param {number} strength
+     */
+    set_pulse(speed, width, strength) {
+        wasm.tunnelhandle_set_pulse(this.__wbg_ptr, speed, width, strength);
+    }
+    /**
+     * @param {number} speed
+     */
+    set_speed(speed) {
+        wasm.tunnelhandle_set_speed(this.__wbg_ptr, speed);
+    }
+    /**
+     * @returns {number}
+     */
+    get shininess() {
+        const ret = wasm.tunnelhandle_shininess(this.__wbg_ptr);
+        return ret;
     }
-    // TODO we could test for more things here, like `Set`s and `Map`s.
-    return className;
-}
-/**
- * @param {number} speed
- */
-export function set_speed(speed) {
-    wasm.set_speed(speed);
-}
-
-/**
- * @param {boolean} show
- */
-export function set_show_longitude(show) {
-    wasm.set_show_longitude(show);
-}
-
-/**
- * @param {boolean} show
- */
-export function set_show_latitude(show) {
-    wasm.set_show_latitude(show);
-}
-
-/**
- * @param {boolean} show
- */
-export function set_show_tunnel(show) {
-    wasm.set_show_tunnel(show);
-}
-
-/**
- * @param {number} num
- */
-export function set_num_polygons(num) {
-    wasm.set_num_polygons(num);
-}
-
-/**
- * @param {boolean} outside
- */
-export function set_outside_view(outside) {
-    wasm.set_outside_view(outside);
 }
 
-function takeFromExternrefTable0(idx) {
-    const value = wasm.__wbindgen_export_2.get(idx);
-    wasm.__externref_table_dealloc(idx);
-    return value;
-}
+const TunnelReportFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_tunnelreport_free(ptr >>> 0, 1));
 /**
- * @param {string} canvas_id
- * @param {number} a
- * @param {number} b
- * @param {number} r
- * @param {number} polygon_radius
- * @param {number} polygon_sides
- * @param {number} num_polygons
+ * What can go wrong with a tunnel for a given curve and options
  */
-export function start_simple_tunnel(canvas_id, a, b, r, polygon_radius, polygon_sides, num_polygons) {
-    const ptr0 = passStringToWasm0(canvas_id, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-    const len0 = WASM_VECTOR_LEN;
-    const ret = wasm.start_simple_tunnel(ptr0, len0, a, b, r, polygon_radius, polygon_sides, num_polygons);
-    if (ret[1]) {
-        throw takeFromExternrefTable0(ret[0]);
+export class TunnelReport {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(TunnelReport.prototype);
+        obj.__wbg_ptr = ptr;
+        TunnelReportFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
     }
-}
-
-function __wbg_adapter_22(arg0, arg1, arg2) {
-    wasm._dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h17542a08b0794ff2(arg0, arg1, arg2);
-}
-
-const Lissajou3DFinalization = (typeof FinalizationRegistry === 'undefined')
-    ? { register: () => {}, unregister: () => {} }
-    : new FinalizationRegistry(ptr => wasm.__wbg_lissajou3d_free(ptr >>> 0, 1));
-
-export class Lissajou3D {
 
     __destroy_into_raw() {
         const ptr = this.__wbg_ptr;
         this.__wbg_ptr = 0;
-        Lissajou3DFinalization.unregister(this);
+        TunnelReportFinalization.unregister(this);
         return ptr;
     }
 
     free() {
         const ptr = this.__destroy_into_raw();
-        wasm.__wbg_lissajou3d_free(ptr, 0);
+        wasm.__wbg_tunnelreport_free(ptr, 0);
     }
     /**
-     * @param {number} a
-     * @param {number} b
-     * @param {number} r
+     * Largest profile radius, before the scale tracks, that neither folds
+     * nor touches another part of the curve. 0 if the curve crosses itself.
+     * @returns {number}
      */
-    constructor(a, b, r) {
-        const ret = wasm.lissajou3d_new(a, b, r);
-        this.__wbg_ptr = ret >>> 0;
-        Lissajou3DFinalization.register(this, this.__wbg_ptr, this);
-        return this;
+    get max_safe_radius() {
+        const ret = wasm.tunnelreport_max_safe_radius(this.__wbg_ptr);
+        return ret;
     }
     /**
-     * @returns {number}
+     * Indices (into `triangles / 3`) of triangles wound against the rest
+     * @returns {Uint32Array}
      */
-    get a() {
-        const ret = wasm.lissajou3d_a(this.__wbg_ptr);
-        return ret;
+    get inverted_triangles() {
+        const ret = wasm.tunnelreport_inverted_triangles(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
     }
     /**
-     * @returns {number}
+     * Indices (into `triangles / 3`) of triangles with no area
+     * @returns {Uint32Array}
      */
-    get b() {
-        const ret = wasm.lissajou3d_b(this.__wbg_ptr);
-        return ret;
+    get degenerate_triangles() {
+        const ret = wasm.tunnelreport_degenerate_triangles(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
     }
     /**
+     * Largest profile radius, before the scale tracks, that doesn't fold.
+     * The useful clamp for curves that cross themselves.
      * @returns {number}
      */
-    get r() {
-        const ret = wasm.lissajou3d_r(this.__wbg_ptr);
+    get max_fold_free_radius() {
+        const ret = wasm.tunnelreport_max_fold_free_radius(this.__wbg_ptr);
         return ret;
     }
+    /**
+     * Fold ranges as `[t_start0, t_end0, t_start1, t_end1, ...]`
+     * @returns {Float64Array}
+     */
+    get folds() {
+        const ret = wasm.tunnelreport_folds(this.__wbg_ptr);
+        var v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+        return v1;
+    }
+    /**
+     * Whether nothing at all was found
+     * @returns {boolean}
+     */
+    get is_ok() {
+        const ret = wasm.tunnelreport_is_ok(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Proximity ranges as `[t_start0, t_end0, t_start1, t_end1, ...]`
+     * @returns {Float64Array}
+     */
+    get proximity() {
+        const ret = wasm.tunnelreport_proximity(this.__wbg_ptr);
+        var v1 = getArrayF64FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 8, 8);
+        return v1;
+    }
 }
 
 async function __wbg_load(module, imports) {
@@ -365,12 +1775,27 @@ async function __wbg_load(module, imports) {
 function __wbg_get_imports() {
     const imports = {};
     imports.wbg = {};
+    imports.wbg.__wbg_activeTexture_0f19d8acfa0a14c2 = function(arg0, arg1) {
+        arg0.activeTexture(arg1 >>> 0);
+    };
+    imports.wbg.__wbg_addEventListener_90e553fdce254421 = function() { return handleError(function (arg0, arg1, arg2, arg3) {
+        arg0.addEventListener(getStringFromWasm0(arg1, arg2), arg3);
+    }, arguments) };
     imports.wbg.__wbg_attachShader_94e758c8b5283eb2 = function(arg0, arg1, arg2) {
         arg0.attachShader(arg1, arg2);
     };
     imports.wbg.__wbg_bindBuffer_f32f587f1c2962a7 = function(arg0, arg1, arg2) {
         arg0.bindBuffer(arg1 >>> 0, arg2);
     };
+    imports.wbg.__wbg_bindFramebuffer_bd02c8cc707d670f = function(arg0, arg1, arg2) {
+        arg0.bindFramebuffer(arg1 >>> 0, arg2);
+    };
+    imports.wbg.__wbg_bindRenderbuffer_53eedd88e52b4cb5 = function(arg0, arg1, arg2) {
+        arg0.bindRenderbuffer(arg1 >>> 0, arg2);
+    };
+    imports.wbg.__wbg_bindTexture_bc8eb316247f739d = function(arg0, arg1, arg2) {
+        arg0.bindTexture(arg1 >>> 0, arg2);
+    };
     imports.wbg.__wbg_blendFunc_9454884a3cfd2911 = function(arg0, arg1, arg2) {
         arg0.blendFunc(arg1 >>> 0, arg2 >>> 0);
     };
@@ -381,34 +1806,102 @@ function __wbg_get_imports() {
         const ret = arg0.buffer;
         return ret;
     };
+    imports.wbg.__wbg_button_f75c56aec440ea04 = function(arg0) {
+        const ret = arg0.button;
+        return ret;
+    };
     imports.wbg.__wbg_call_672a4d21634d4a24 = function() { return handleError(function (arg0, arg1) {
         const ret = arg0.call(arg1);
         return ret;
     }, arguments) };
+    imports.wbg.__wbg_cancelAnimationFrame_089b48301c362fde = function() { return handleError(function (arg0, arg1) {
+        arg0.cancelAnimationFrame(arg1);
+    }, arguments) };
+    imports.wbg.__wbg_checkFramebufferStatus_1aea9a28f6bc4fa6 = function(arg0, arg1) {
+        const ret = arg0.checkFramebufferStatus(arg1 >>> 0);
+        return ret;
+    };
     imports.wbg.__wbg_clearColor_f0fa029dfbcc1982 = function(arg0, arg1, arg2, arg3, arg4) {
         arg0.clearColor(arg1, arg2, arg3, arg4);
     };
     imports.wbg.__wbg_clear_f8d5f3c348d37d95 = function(arg0, arg1) {
         arg0.clear(arg1 >>> 0);
     };
+    imports.wbg.__wbg_clientHeight_216178c194000db4 = function(arg0) {
+        const ret = arg0.clientHeight;
+        return ret;
+    };
+    imports.wbg.__wbg_clientX_5eb380a5f1fec6fd = function(arg0) {
+        const ret = arg0.clientX;
+        return ret;
+    };
+    imports.wbg.__wbg_clientY_d8b9c7f0c4e2e677 = function(arg0) {
+        const ret = arg0.clientY;
+        return ret;
+    };
+    imports.wbg.__wbg_colorMask_6dac12039c7145ae = function(arg0, arg1, arg2, arg3, arg4) {
+        arg0.colorMask(arg1 !== 0, arg2 !== 0, arg3 !== 0, arg4 !== 0);
+    };
     imports.wbg.__wbg_compileShader_2307c9d370717dd5 = function(arg0, arg1) {
         arg0.compileShader(arg1);
     };
+    imports.wbg.__wbg_complete_7a623be264d76fd2 = function(arg0) {
+        const ret = arg0.complete;
+        return ret;
+    };
     imports.wbg.__wbg_createBuffer_7a9ec3d654073660 = function(arg0) {
         const ret = arg0.createBuffer();
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
     };
+    imports.wbg.__wbg_createFramebuffer_7824f69bba778885 = function(arg0) {
+        const ret = arg0.createFramebuffer();
+        return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+    };
     imports.wbg.__wbg_createProgram_8ff56c485f3233d0 = function(arg0) {
         const ret = arg0.createProgram();
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
     };
+    imports.wbg.__wbg_createRenderbuffer_fd347ae14f262eaa = function(arg0) {
+        const ret = arg0.createRenderbuffer();
+        return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+    };
     imports.wbg.__wbg_createShader_4a256a8cc9c1ce4f = function(arg0, arg1) {
         const ret = arg0.createShader(arg1 >>> 0);
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
     };
+    imports.wbg.__wbg_createTexture_9c536c79b635fdef = function(arg0) {
+        const ret = arg0.createTexture();
+        return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
+    };
     imports.wbg.__wbg_cullFace_fbae6dd4d5e61ba4 = function(arg0, arg1) {
         arg0.cullFace(arg1 >>> 0);
     };
+    imports.wbg.__wbg_deleteBuffer_a7822433fc95dfb8 = function(arg0, arg1) {
+        arg0.deleteBuffer(arg1);
+    };
+    imports.wbg.__wbg_deleteFramebuffer_cd3285ee5a702a7a = function(arg0, arg1) {
+        arg0.deleteFramebuffer(arg1);
+    };
+    imports.wbg.__wbg_deleteProgram_3fa626bbc0001eb7 = function(arg0, arg1) {
+        arg0.deleteProgram(arg1);
+    };
+    imports.wbg.__wbg_deleteRenderbuffer_8808192853211567 = function(arg0, arg1) {
+        arg0.deleteRenderbuffer(arg1);
+    };
+    imports.wbg.__wbg_deleteShader_c65a44796c5004d8 = function(arg0, arg1) {
+        arg0.deleteShader(arg1);
+    };
+    imports.wbg.__wbg_deleteTexture_a30f5ca0163c4110 = function(arg0, arg1) {
+        arg0.deleteTexture(arg1);
+    };
+    imports.wbg.__wbg_deltaMode_9bfd9fe3f6b4b240 = function(arg0) {
+        const ret = arg0.deltaMode;
+        return ret;
+    };
+    imports.wbg.__wbg_deltaY_f9318542caea0c36 = function(arg0) {
+        const ret = arg0.deltaY;
+        return ret;
+    };
     imports.wbg.__wbg_depthMask_5fe84e2801488eda = function(arg0, arg1) {
         arg0.depthMask(arg1 !== 0);
     };
@@ -419,9 +1912,20 @@ function __wbg_get_imports() {
         const ret = arg0.document;
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
     };
+    imports.wbg.__wbg_drawArrays_6acaa2669c105f3a = function(arg0, arg1, arg2, arg3) {
+        arg0.drawArrays(arg1 >>> 0, arg2, arg3);
+    };
     imports.wbg.__wbg_drawElements_16199ef1cc58b16a = function(arg0, arg1, arg2, arg3, arg4) {
         arg0.drawElements(arg1 >>> 0, arg2, arg3 >>> 0, arg4);
     };
+    imports.wbg.__wbg_drawingBufferHeight_1d398e1fc5bb18e9 = function(arg0) {
+        const ret = arg0.drawingBufferHeight;
+        return ret;
+    };
+    imports.wbg.__wbg_drawingBufferWidth_78f7e4a8892a90fe = function(arg0) {
+        const ret = arg0.drawingBufferWidth;
+        return ret;
+    };
     imports.wbg.__wbg_enableVertexAttribArray_607be07574298e5e = function(arg0, arg1) {
         arg0.enableVertexAttribArray(arg1 >>> 0);
     };
@@ -442,6 +1946,12 @@ function __wbg_get_imports() {
             wasm.__wbindgen_free(deferred0_0, deferred0_1, 1);
         }
     };
+    imports.wbg.__wbg_framebufferRenderbuffer_2fdd12e89ad81eb9 = function(arg0, arg1, arg2, arg3, arg4) {
+        arg0.framebufferRenderbuffer(arg1 >>> 0, arg2 >>> 0, arg3 >>> 0, arg4);
+    };
+    imports.wbg.__wbg_framebufferTexture2D_81a565732bd5d8fe = function(arg0, arg1, arg2, arg3, arg4, arg5) {
+        arg0.framebufferTexture2D(arg1 >>> 0, arg2 >>> 0, arg3 >>> 0, arg4, arg5);
+    };
     imports.wbg.__wbg_getAttribLocation_9db82d01924fa43d = function(arg0, arg1, arg2, arg3) {
         const ret = arg0.getAttribLocation(arg1, getStringFromWasm0(arg2, arg3));
         return ret;
@@ -498,6 +2008,16 @@ function __wbg_get_imports() {
         const ret = result;
         return ret;
     };
+    imports.wbg.__wbg_instanceof_PointerEvent_fa811ce337d22dfb = function(arg0) {
+        let result;
+        try {
+            result = arg0 instanceof PointerEvent;
+        } catch (_) {
+            result = false;
+        }
+        const ret = result;
+        return ret;
+    };
     imports.wbg.__wbg_instanceof_WebGlRenderingContext_b9cbe798424f6d4c = function(arg0) {
         let result;
         try {
@@ -508,6 +2028,16 @@ function __wbg_get_imports() {
         const ret = result;
         return ret;
     };
+    imports.wbg.__wbg_instanceof_WheelEvent_aca2c29f190c8326 = function(arg0) {
+        let result;
+        try {
+            result = arg0 instanceof WheelEvent;
+        } catch (_) {
+            result = false;
+        }
+        const ret = result;
+        return ret;
+    };
     imports.wbg.__wbg_instanceof_Window_def73ea0955fc569 = function(arg0) {
         let result;
         try {
@@ -521,6 +2051,10 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_linkProgram_e002979fe36e5b2a = function(arg0, arg1) {
         arg0.linkProgram(arg1);
     };
+    imports.wbg.__wbg_naturalWidth_2d1cd85377acd810 = function(arg0) {
+        const ret = arg0.naturalWidth;
+        return ret;
+    };
     imports.wbg.__wbg_new_8a6f238a6ece86ea = function() {
         const ret = new Error();
         return ret;
@@ -537,13 +2071,37 @@ function __wbg_get_imports() {
         const ret = new Uint32Array(arg0, arg1 >>> 0, arg2 >>> 0);
         return ret;
     };
+    imports.wbg.__wbg_pointerId_585e63ee80a49927 = function(arg0) {
+        const ret = arg0.pointerId;
+        return ret;
+    };
+    imports.wbg.__wbg_preventDefault_c2314fd813c02b3c = function(arg0) {
+        arg0.preventDefault();
+    };
+    imports.wbg.__wbg_readyState_017c08aa5e73517e = function(arg0) {
+        const ret = arg0.readyState;
+        return ret;
+    };
+    imports.wbg.__wbg_removeEventListener_056dfe8c3d6c58f9 = function() { return handleError(function (arg0, arg1, arg2, arg3) {
+        arg0.removeEventListener(getStringFromWasm0(arg1, arg2), arg3);
+    }, arguments) };
+    imports.wbg.__wbg_renderbufferStorage_73e01ea83b8afab4 = function(arg0, arg1, arg2, arg3, arg4) {
+        arg0.renderbufferStorage(arg1 >>> 0, arg2 >>> 0, arg3, arg4);
+    };
     imports.wbg.__wbg_requestAnimationFrame_d7fd890aaefc3246 = function() { return handleError(function (arg0, arg1) {
         const ret = arg0.requestAnimationFrame(arg1);
         return ret;
     }, arguments) };
+    imports.wbg.__wbg_setPointerCapture_c04dafaf4d00ffad = function() { return handleError(function (arg0, arg1) {
+        arg0.setPointerCapture(arg1);
+    }, arguments) };
     imports.wbg.__wbg_shaderSource_ad0087e637a35191 = function(arg0, arg1, arg2, arg3) {
         arg0.shaderSource(arg1, getStringFromWasm0(arg2, arg3));
     };
+    imports.wbg.__wbg_shiftKey_2bebb3b703254f47 = function(arg0) {
+        const ret = arg0.shiftKey;
+        return ret;
+    };
     imports.wbg.__wbg_stack_0ed75d68575b0f3c = function(arg0, arg1) {
         const ret = arg1.stack;
         const ptr1 = passStringToWasm0(ret, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
@@ -567,9 +2125,36 @@ function __wbg_get_imports() {
         const ret = typeof window === 'undefined' ? null : window;
         return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
     };
+    imports.wbg.__wbg_texImage2D_297bb8474cd4e8b8 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4, arg5, arg6, arg7, arg8, arg9, arg10) {
+        arg0.texImage2D(arg1 >>> 0, arg2, arg3, arg4, arg5, arg6, arg7 >>> 0, arg8 >>> 0, arg9 === 0 ? undefined : getArrayU8FromWasm0(arg9, arg10));
+    }, arguments) };
+    imports.wbg.__wbg_texImage2D_7fafc5db9861e235 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4, arg5, arg6) {
+        arg0.texImage2D(arg1 >>> 0, arg2, arg3, arg4 >>> 0, arg5 >>> 0, arg6);
+    }, arguments) };
+    imports.wbg.__wbg_texImage2D_b2bb443de3966051 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4, arg5, arg6) {
+        arg0.texImage2D(arg1 >>> 0, arg2, arg3, arg4 >>> 0, arg5 >>> 0, arg6);
+    }, arguments) };
+    imports.wbg.__wbg_texImage2D_d40a00b9c1254ee4 = function() { return handleError(function (arg0, arg1, arg2, arg3, arg4, arg5, arg6) {
+        arg0.texImage2D(arg1 >>> 0, arg2, arg3, arg4 >>> 0, arg5 >>> 0, arg6);
+    }, arguments) };
+    imports.wbg.__wbg_texParameteri_ef50743cb94d507e = function(arg0, arg1, arg2, arg3) {
+        arg0.texParameteri(arg1 >>> 0, arg2 >>> 0, arg3);
+    };
     imports.wbg.__wbg_uniform1f_21390b04609a9fa5 = function(arg0, arg1, arg2) {
         arg0.uniform1f(arg1, arg2);
     };
+    imports.wbg.__wbg_uniform1i_5ddd9d8ccbd390bb = function(arg0, arg1, arg2) {
+        arg0.uniform1i(arg1, arg2);
+    };
+    imports.wbg.__wbg_uniform2f_b69b5369bc019bd5 = function(arg0, arg1, arg2, arg3) {
+        arg0.uniform2f(arg1, arg2, arg3);
+    };
+    imports.wbg.__wbg_uniform2fv_656fce9525420996 = function(arg0, arg1, arg2, arg3) {
+        arg0.uniform2fv(arg1, getArrayF32FromWasm0(arg2, arg3));
+    };
+    imports.wbg.__wbg_uniform3fv_8153c834ce667125 = function(arg0, arg1, arg2, arg3) {
+        arg0.uniform3fv(arg1, getArrayF32FromWasm0(arg2, arg3));
+    };
     imports.wbg.__wbg_uniformMatrix4fv_da94083874f202ad = function(arg0, arg1, arg2, arg3, arg4) {
         arg0.uniformMatrix4fv(arg1, arg2 !== 0, getArrayF32FromWasm0(arg3, arg4));
     };
@@ -579,6 +2164,9 @@ function __wbg_get_imports() {
     imports.wbg.__wbg_vertexAttribPointer_7a2a506cdbe3aebc = function(arg0, arg1, arg2, arg3, arg4, arg5, arg6) {
         arg0.vertexAttribPointer(arg1 >>> 0, arg2, arg3 >>> 0, arg4 !== 0, arg5, arg6);
     };
+    imports.wbg.__wbg_warn_4ca3906c248c47c4 = function(arg0) {
+        console.warn(arg0);
+    };
     imports.wbg.__wbg_width_5dde457d606ba683 = function(arg0) {
         const ret = arg0.width;
         return ret;
@@ -597,8 +2185,12 @@ function __wbg_get_imports() {
         const ret = false;
         return ret;
     };
-    imports.wbg.__wbindgen_closure_wrapper107 = function(arg0, arg1, arg2) {
-        const ret = makeMutClosure(arg0, arg1, 8, __wbg_adapter_22);
+    imports.wbg.__wbindgen_closure_wrapper376 = function(arg0, arg1, arg2) {
+        const ret = makeMutClosure(arg0, arg1, 23, __wbg_adapter_22);
+        return ret;
+    };
+    imports.wbg.__wbindgen_closure_wrapper378 = function(arg0, arg1, arg2) {
+        const ret = makeMutClosure(arg0, arg1, 23, __wbg_adapter_25);
         return ret;
     };
     imports.wbg.__wbindgen_debug_string = function(arg0, arg1) {
@@ -646,6 +2238,8 @@ function __wbg_finalize_init(instance, module) {
     __wbg_init.__wbindgen_wasm_module = module;
     cachedDataViewMemory0 = null;
     cachedFloat32ArrayMemory0 = null;
+    cachedFloat64ArrayMemory0 = null;
+    cachedUint32ArrayMemory0 = null;
     cachedUint8ArrayMemory0 = null;
 
 
@@ -1,18 +1,148 @@
 /* tslint:disable */
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
-export const set_show_longitude: (a: number) => void;
-export const set_show_latitude: (a: number) => void;
-export const set_show_tunnel: (a: number) => void;
-export const set_outside_view: (a: number) => void;
-export const start_simple_tunnel: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number];
-export const set_speed: (a: number) => void;
-export const set_num_polygons: (a: number) => void;
+export const __wbg_tunnelhandle_free: (a: number, b: number) => void;
+export const start_simple_tunnel: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => [number, number, number];
+export const tunnelhandle_ambient: (a: number) => number;
+export const tunnelhandle_analyze: (a: number) => number;
+export const tunnelhandle_auto_rotate: (a: number) => number;
+export const tunnelhandle_camera_mode: (a: number) => number;
+export const tunnelhandle_camera_travel: (a: number) => number;
+export const tunnelhandle_chase_distance: (a: number) => number;
+export const tunnelhandle_chase_height: (a: number) => number;
+export const tunnelhandle_clear_wall_texture: (a: number) => void;
+export const tunnelhandle_color_by: (a: number) => number;
+export const tunnelhandle_diffuse: (a: number) => number;
+export const tunnelhandle_export_glb: (a: number, b: number, c: number) => [number, number];
+export const tunnelhandle_export_obj: (a: number) => [number, number];
+export const tunnelhandle_export_solid_3mf: (a: number, b: number) => [number, number, number, number];
+export const tunnelhandle_export_solid_stl: (a: number, b: number) => [number, number, number, number];
+export const tunnelhandle_export_stl_ascii: (a: number) => [number, number];
+export const tunnelhandle_export_stl_binary: (a: number) => [number, number];
+export const tunnelhandle_fog_color: (a: number) => [number, number];
+export const tunnelhandle_fog_density: (a: number) => number;
+export const tunnelhandle_fog_end: (a: number) => number;
+export const tunnelhandle_fog_mode: (a: number) => number;
+export const tunnelhandle_fog_start: (a: number) => number;
+export const tunnelhandle_frame_mode: (a: number) => number;
+export const tunnelhandle_headlight: (a: number) => number;
+export const tunnelhandle_lighting: (a: number) => number;
+export const tunnelhandle_num_polygons: (a: number) => number;
+export const tunnelhandle_orbit_damping: (a: number) => number;
+export const tunnelhandle_outside_view: (a: number) => number;
+export const tunnelhandle_palette_animation: (a: number) => number;
+export const tunnelhandle_palette_repeat: (a: number) => number;
+export const tunnelhandle_palette_speed: (a: number) => number;
+export const tunnelhandle_pulse_speed: (a: number) => number;
+export const tunnelhandle_pulse_strength: (a: number) => number;
+export const tunnelhandle_pulse_width: (a: number) => number;
+export const tunnelhandle_reset: (a: number) => void;
+export const tunnelhandle_reset_orbit: (a: number) => void;
+export const tunnelhandle_rim: (a: number) => number;
+export const tunnelhandle_ring_spacing: (a: number) => number;
+export const tunnelhandle_running: (a: number) => number;
+export const tunnelhandle_self_intersections: (a: number) => [number, number];
+export const tunnelhandle_set_ambient: (a: number, b: number) => void;
+export const tunnelhandle_set_auto_rotate: (a: number, b: number) => void;
+export const tunnelhandle_set_camera_mode: (a: number, b: number) => void;
+export const tunnelhandle_set_camera_travel: (a: number, b: number) => void;
+export const tunnelhandle_set_chase_offset: (a: number, b: number, c: number) => void;
+export const tunnelhandle_set_color_by: (a: number, b: number) => void;
+export const tunnelhandle_set_colormap: (a: number, b: number) => void;
+export const tunnelhandle_set_curve: (a: number, b: number) => void;
+export const tunnelhandle_set_diffuse: (a: number, b: number) => void;
+export const tunnelhandle_set_fog_color: (a: number, b: number, c: number, d: number) => void;
+export const tunnelhandle_set_fog_density: (a: number, b: number) => void;
+export const tunnelhandle_set_fog_mode: (a: number, b: number) => void;
+export const tunnelhandle_set_fog_range: (a: number, b: number, c: number) => void;
+export const tunnelhandle_set_frame_mode: (a: number, b: number) => void;
+export const tunnelhandle_set_gradient: (a: number, b: number, c: number) => [number, number];
+export const tunnelhandle_set_headlight: (a: number, b: number) => void;
+export const tunnelhandle_set_lighting: (a: number, b: number) => void;
+export const tunnelhandle_set_num_polygons: (a: number, b: number) => void;
+export const tunnelhandle_set_orbit_damping: (a: number, b: number) => void;
+export const tunnelhandle_set_orbit_distance_limits: (a: number, b: number, c: number) => void;
+export const tunnelhandle_set_orbit_pitch_limits: (a: number, b: number, c: number) => void;
+export const tunnelhandle_set_orbit_yaw_limits: (a: number, b: number, c: number) => void;
+export const tunnelhandle_set_outside_view: (a: number, b: number) => void;
+export const tunnelhandle_set_palette_animation: (a: number, b: number) => void;
+export const tunnelhandle_set_palette_repeat: (a: number, b: number) => void;
+export const tunnelhandle_set_palette_speed: (a: number, b: number) => void;
+export const tunnelhandle_set_point_lights: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const tunnelhandle_set_profile: (a: number, b: number) => void;
+export const tunnelhandle_set_pulse: (a: number, b: number, c: number, d: number) => void;
+export const tunnelhandle_set_radius_keyframes: (a: number, b: number, c: number) => [number, number];
+export const tunnelhandle_set_rim: (a: number, b: number) => void;
+export const tunnelhandle_set_ring_spacing: (a: number, b: number) => void;
+export const tunnelhandle_set_scale_keyframes: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const tunnelhandle_set_shininess: (a: number, b: number) => void;
+export const tunnelhandle_set_show_latitude: (a: number, b: number) => void;
+export const tunnelhandle_set_show_longitude: (a: number, b: number) => void;
+export const tunnelhandle_set_show_tunnel: (a: number, b: number) => void;
+export const tunnelhandle_set_specular: (a: number, b: number) => void;
+export const tunnelhandle_set_speed: (a: number, b: number) => void;
+export const tunnelhandle_set_texture_mix: (a: number, b: number) => void;
+export const tunnelhandle_set_texture_scroll: (a: number, b: number, c: number) => void;
+export const tunnelhandle_set_texture_tiling: (a: number, b: number, c: number) => void;
+export const tunnelhandle_set_time: (a: number, b: number) => void;
+export const tunnelhandle_set_transition_duration: (a: number, b: number) => void;
+export const tunnelhandle_set_transparency: (a: number, b: number) => void;
+export const tunnelhandle_set_twist_keyframes: (a: number, b: number, c: number) => [number, number];
+export const tunnelhandle_set_wall_canvas: (a: number, b: any) => void;
+export const tunnelhandle_set_wall_image: (a: number, b: any) => void;
+export const tunnelhandle_set_wall_opacity: (a: number, b: number) => void;
+export const tunnelhandle_set_wall_video: (a: number, b: any) => void;
+export const tunnelhandle_set_wire_width: (a: number, b: number) => void;
+export const tunnelhandle_shininess: (a: number) => number;
+export const tunnelhandle_show_latitude: (a: number) => number;
+export const tunnelhandle_show_longitude: (a: number) => number;
+export const tunnelhandle_show_tunnel: (a: number) => number;
+export const tunnelhandle_specular: (a: number) => number;
+export const tunnelhandle_speed: (a: number) => number;
+export const tunnelhandle_stop: (a: number) => void;
+export const tunnelhandle_texture_mix: (a: number) => number;
+export const tunnelhandle_texture_scroll: (a: number) => [number, number];
+export const tunnelhandle_texture_tiling: (a: number) => [number, number];
+export const tunnelhandle_time: (a: number) => number;
+export const tunnelhandle_transition_duration: (a: number) => number;
+export const tunnelhandle_transparency: (a: number) => number;
+export const tunnelhandle_wall_opacity: (a: number) => number;
+export const tunnelhandle_wire_width: (a: number) => number;
 export const __wbg_lissajou3d_free: (a: number, b: number) => void;
-export const lissajou3d_new: (a: number, b: number, c: number) => number;
 export const lissajou3d_a: (a: number) => number;
 export const lissajou3d_b: (a: number) => number;
+export const lissajou3d_closure_gap: (a: number) => number;
+export const lissajou3d_curvature: (a: number, b: number) => number;
+export const lissajou3d_derivative: (a: number, b: number, c: number) => [number, number];
+export const lissajou3d_is_closed: (a: number) => number;
+export const lissajou3d_period: (a: number) => number;
+export const lissajou3d_phase_phi: (a: number) => number;
+export const lissajou3d_phase_theta: (a: number) => number;
 export const lissajou3d_r: (a: number) => number;
+export const lissajou3d_radii: (a: number) => [number, number];
+export const lissajou3d_speed: (a: number, b: number) => number;
+export const lissajou3d_torsion: (a: number, b: number) => number;
+export const lissajou3d_with_options: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number) => number;
+export const __wbg_tunnelreport_free: (a: number, b: number) => void;
+export const tunnelreport_degenerate_triangles: (a: number) => [number, number];
+export const tunnelreport_folds: (a: number) => [number, number];
+export const tunnelreport_inverted_triangles: (a: number) => [number, number];
+export const tunnelreport_is_ok: (a: number) => number;
+export const tunnelreport_max_fold_free_radius: (a: number) => number;
+export const tunnelreport_max_safe_radius: (a: number) => number;
+export const tunnelreport_proximity: (a: number) => [number, number];
+export const __wbg_profile_free: (a: number, b: number) => void;
+export const profile_coords: (a: number) => [number, number];
+export const profile_fit: (a: number, b: number) => number;
+export const profile_from_coords: (a: number, b: number) => [number, number, number];
+export const profile_from_svg_path: (a: number, b: number) => [number, number, number];
+export const profile_is_empty: (a: number) => number;
+export const profile_len: (a: number) => number;
+export const profile_radius: (a: number) => number;
+export const profile_regular: (a: number, b: number) => [number, number, number];
+export const profile_rounded_rect: (a: number, b: number, c: number, d: number) => [number, number, number];
+export const profile_star: (a: number, b: number, c: number) => [number, number, number];
+export const profile_superellipse: (a: number, b: number, c: number, d: number) => [number, number, number];
 export const __wbindgen_exn_store: (a: number) => void;
 export const __externref_table_alloc: () => number;
 export const __wbindgen_export_2: WebAssembly.Table;
@@ -21,5 +151,6 @@ export const __wbindgen_malloc: (a: number, b: number) => number;
 export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
 export const __wbindgen_export_6: WebAssembly.Table;
 export const __externref_table_dealloc: (a: number) => void;
-export const _dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h17542a08b0794ff2: (a: number, b: number, c: number) => void;
+export const closure22_externref_shim: (a: number, b: number, c: any) => void;
+export const _dyn_core__ops__function__FnMut__A____Output___R_as_wasm_bindgen__closure__WasmClosure___describe__invoke__h0edc57afd33ee676: (a: number, b: number, c: number) => void;
 export const __wbindgen_start: () => void;
@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+use crate::arclength::ArcLengthTable;
+use crate::curve::ParametricCurve;
+use crate::math::V3D;
+use crate::mesh::{Mesh, TunnelOptions};
+
+// Curve samples per loop for the fold and proximity searches
+const ANALYSIS_SAMPLES: usize = 1024;
+
+/// What can go wrong with a tunnel for a given curve and options
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Debug, Default)]
+pub struct TunnelReport {
+    folds: Vec<(f64, f64)>,
+    proximity: Vec<(f64, f64)>,
+    degenerate_triangles: Vec<u32>,
+    inverted_triangles: Vec<u32>,
+    max_safe_radius: f64,
+    max_fold_free_radius: f64,
+}
+
+#[cfg_attr(feature = "web", wasm_bindgen)]
+impl TunnelReport {
+    /// Fold ranges as `[t_start0, t_end0, t_start1, t_end1, ...]`
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn folds(&self) -> Vec<f64> {
+        flatten(&self.folds)
+    }
+
+    /// Proximity ranges as `[t_start0, t_end0, t_start1, t_end1, ...]`
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn proximity(&self) -> Vec<f64> {
+        flatten(&self.proximity)
+    }
+
+    /// Indices (into `triangles / 3`) of triangles with no area
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn degenerate_triangles(&self) -> Vec<u32> {
+        self.degenerate_triangles.clone()
+    }
+
+    /// Indices (into `triangles / 3`) of triangles wound against the rest
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn inverted_triangles(&self) -> Vec<u32> {
+        self.inverted_triangles.clone()
+    }
+
+    /// Largest profile radius, before the scale tracks, that neither folds
+    /// nor touches another part of the curve. 0 if the curve crosses itself.
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn max_safe_radius(&self) -> f64 {
+        self.max_safe_radius
+    }
+
+    /// Largest profile radius, before the scale tracks, that doesn't fold.
+    /// The useful clamp for curves that cross themselves.
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn max_fold_free_radius(&self) -> f64 {
+        self.max_fold_free_radius
+    }
+
+    /// Whether nothing at all was found
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn is_ok(&self) -> bool {
+        self.folds.is_empty()
+            && self.proximity.is_empty()
+            && self.degenerate_triangles.is_empty()
+            && self.inverted_triangles.is_empty()
+    }
+}
+
+impl TunnelReport {
+    /// Parameter ranges where the tube is wider than the radius of
+    /// curvature 1/κ, so rings on the inside of the bend fold over
+    pub fn fold_ranges(&self) -> &[(f64, f64)] {
+        &self.folds
+    }
+
+    /// Parameter ranges closer than the tube's diameter to a distant part
+    /// of the curve
+    pub fn proximity_ranges(&self) -> &[(f64, f64)] {
+        &self.proximity
+    }
+}
+
+/// Check the curve against the tunnel options and `mesh` against itself
+pub fn analyze_tunnel<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    options: &TunnelOptions,
+    mesh: &Mesh,
+) -> TunnelReport {
+    let samples = CurveSamples::new(curve, options);
+    let (degenerate_triangles, inverted_triangles) = check_triangles(mesh);
+
+    let profile_radius = options.profile.radius();
+    let max_fold_free_radius = samples.fold_limit(profile_radius);
+
+    TunnelReport {
+        folds: samples.fold_ranges(),
+        proximity: samples.proximity_ranges(),
+        degenerate_triangles,
+        inverted_triangles,
+        max_safe_radius: samples.proximity_limit(profile_radius, max_fold_free_radius),
+        max_fold_free_radius,
+    }
+}
+
+/// Parameter ranges where the tube comes within its own thickness of a
+/// distant part of the curve, so the walls pass through each other.
+///
+/// Points closer along the curve than half a turn of the tube are
+/// neighbours, not collisions, and are skipped.
+pub fn proximity_ranges<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    options: &TunnelOptions,
+) -> Vec<(f64, f64)> {
+    CurveSamples::new(curve, options).proximity_ranges()
+}
+
+/// Parameter ranges where the tube radius exceeds 1/κ
+pub fn fold_ranges<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    options: &TunnelOptions,
+) -> Vec<(f64, f64)> {
+    CurveSamples::new(curve, options).fold_ranges()
+}
+
+// The curve sampled once per analysis
+struct CurveSamples {
+    closed: bool,
+    length: f64,
+    params: Vec<f64>,
+    positions: Vec<V3D>,
+    arc_lengths: Vec<f64>,
+    curvatures: Vec<f64>,
+    radii: Vec<f64>,
+}
+
+impl CurveSamples {
+    fn new<C: ParametricCurve + ?Sized>(curve: &C, options: &TunnelOptions) -> Self {
+        let period = curve.period();
+        let closed = curve.is_closed();
+        let arc = ArcLengthTable::new(curve);
+        let count = if closed {
+            ANALYSIS_SAMPLES
+        } else {
+            ANALYSIS_SAMPLES + 1
+        };
+        let params: Vec<f64> = (0..count)
+            .map(|i| period * i as f64 / ANALYSIS_SAMPLES as f64)
+            .collect();
+
+        Self {
+            closed,
+            length: arc.length(),
+            positions: params.iter().map(|&t| curve.position(t)).collect(),
+            arc_lengths: params.iter().map(|&t| arc.arc_length(t)).collect(),
+            curvatures: params.iter().map(|&t| curve.curvature(t)).collect(),
+            radii: params.iter().map(|&t| options.radius_at(t)).collect(),
+            params,
+        }
+    }
+
+    fn fold_ranges(&self) -> Vec<(f64, f64)> {
+        let flags: Vec<bool> = self
+            .radii
+            .iter()
+            .zip(&self.curvatures)
+            .map(|(r, k)| r * k > 1.0)
+            .collect();
+        flagged_ranges(&flags, &self.params)
+    }
+
+    // Distance along the curve between samples i and j
+    fn separation(&self, i: usize, j: usize) -> f64 {
+        let along = (self.arc_lengths[j] - self.arc_lengths[i]).abs();
+        if self.closed {
+            along.min(self.length - along)
+        } else {
+            along
+        }
+    }
+
+    // Call `f` for every pair of samples i < j closer than `cell`, and
+    // for some farther ones: those in the same or neighbouring cells of a
+    // grid with that spacing. Saves comparing every pair when the tube is
+    // thin next to the curve.
+    fn nearby_pairs(&self, cell: f64, mut f: impl FnMut(usize, usize)) {
+        if cell.is_nan() || cell <= 0.0 {
+            return;
+        }
+        // An unbounded cell puts everything in one, compared pairwise
+        let key = |p: &V3D| {
+            if cell.is_finite() {
+                [p.x, p.y, p.z].map(|c| (c / cell).floor() as i64)
+            } else {
+                [0; 3]
+            }
+        };
+        let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+        for (i, p) in self.positions.iter().enumerate() {
+            grid.entry(key(p)).or_default().push(i);
+        }
+
+        for (i, p) in self.positions.iter().enumerate() {
+            let [x, y, z] = key(p);
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let Some(members) = grid.get(&[x + dx, y + dy, z + dz]) else {
+                            continue;
+                        };
+                        for &j in members.iter().filter(|&&j| j > i) {
+                            f(i, j);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn proximity_ranges(&self) -> Vec<(f64, f64)> {
+        let count = self.params.len();
+        let mut flags = vec![false; count];
+        // Colliding samples are within the largest diameter of each other
+        let widest = self.radii.iter().copied().fold(0.0, f64::max);
+        self.nearby_pairs(2.0 * widest, |i, j| {
+            let reach = self.radii[i] + self.radii[j];
+            if self.separation(i, j) > 0.5 * PI * reach
+                && (self.positions[j] - self.positions[i]).magnitude() < reach
+            {
+                flags[i] = true;
+                flags[j] = true;
+            }
+        });
+        flagged_ranges(&flags, &self.params)
+    }
+
+    // The tracks' scale factor at each sample; radii are proportional to the
+    // profile radius
+    fn scales(&self, profile_radius: f64) -> Vec<f64> {
+        if profile_radius <= 0.0 {
+            return vec![0.0; self.radii.len()];
+        }
+        self.radii.iter().map(|r| r / profile_radius).collect()
+    }
+
+    // Largest profile radius with scale · radius · κ <= 1 everywhere
+    fn fold_limit(&self, profile_radius: f64) -> f64 {
+        self.scales(profile_radius)
+            .iter()
+            .zip(&self.curvatures)
+            .filter(|(s, k)| **s * **k > 0.0)
+            .map(|(s, k)| 1.0 / (s * k))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    // Largest profile radius up to `limit` with no distant pair closer than
+    // the tube's diameter. Shrinking the radius makes more pairs count as
+    // distant, hence the refinement rounds.
+    fn proximity_limit(&self, profile_radius: f64, mut limit: f64) -> f64 {
+        let scales = self.scales(profile_radius);
+        let widest = scales.iter().copied().fold(0.0, f64::max);
+        for _ in 0..8 {
+            let mut next = limit;
+            // Only pairs with gap < reach · limit can lower the limit
+            self.nearby_pairs(2.0 * widest * limit, |i, j| {
+                let reach = scales[i] + scales[j];
+                if reach <= 0.0 || self.separation(i, j) <= 0.5 * PI * reach * limit {
+                    return;
+                }
+                let gap = (self.positions[j] - self.positions[i]).magnitude();
+                next = next.min(gap / reach);
+            });
+            if next >= limit {
+                break;
+            }
+            limit = next;
+        }
+        limit
+    }
+}
+
+// Degenerate triangles have (next to) no area. Inverted ones are wound
+// against the majority, judged by the vertex normals; tunnel walls face
+// inwards and solids outwards, so the majority sets the convention.
+fn check_triangles(mesh: &Mesh) -> (Vec<u32>, Vec<u32>) {
+    let point = |i: u32| {
+        let p = mesh.vertices[i as usize].pos;
+        V3D::new(p[0] as f64, p[1] as f64, p[2] as f64)
+    };
+    let normal = |i: u32| {
+        let n = mesh.vertices[i as usize].normal;
+        V3D::new(n[0] as f64, n[1] as f64, n[2] as f64)
+    };
+
+    let (mut min, mut max) = (
+        V3D::new(f64::MAX, f64::MAX, f64::MAX),
+        V3D::new(f64::MIN, f64::MIN, f64::MIN),
+    );
+    for v in &mesh.vertices {
+        let p = V3D::new(v.pos[0] as f64, v.pos[1] as f64, v.pos[2] as f64);
+        min = V3D::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+        max = V3D::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+    }
+    let size = (max - min).magnitude();
+    let min_area = 1e-12 * size * size;
+
+    let mut degenerate = Vec::new();
+    let mut facing = Vec::new();
+    for (k, tri) in mesh.triangles.chunks_exact(3).enumerate() {
+        let face = (point(tri[1]) - point(tri[0])).cross(&(point(tri[2]) - point(tri[0])));
+        if 0.5 * face.magnitude() <= min_area {
+            degenerate.push(k as u32);
+            continue;
+        }
+        let smooth = normal(tri[0]) + normal(tri[1]) + normal(tri[2]);
+        let agreement = face.dot(&smooth);
+        if agreement != 0.0 {
+            facing.push((k as u32, agreement > 0.0));
+        }
+    }
+
+    let agreeing = facing.iter().filter(|(_, a)| *a).count();
+    let majority = 2 * agreeing >= facing.len();
+    let inverted = facing
+        .into_iter()
+        .filter(|(_, a)| *a != majority)
+        .map(|(k, _)| k)
+        .collect();
+
+    (degenerate, inverted)
+}
+
+// Merge runs of flagged samples into (first, last) parameter ranges
+fn flagged_ranges(flags: &[bool], params: &[f64]) -> Vec<(f64, f64)> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (i, &flag) in flags.iter().enumerate() {
+        match (flag, start) {
+            (true, None) => start = Some(i),
+            (false, Some(first)) => {
+                ranges.push((params[first], params[i - 1]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(first) = start {
+        ranges.push((params[first], params[params.len() - 1]));
+    }
+    ranges
+}
+
+fn flatten(ranges: &[(f64, f64)]) -> Vec<f64> {
+    ranges
+        .iter()
+        .flat_map(|&(start, end)| [start, end])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Palette;
+    use crate::lisa::Lissajou3D;
+    use crate::mesh::generate_tunnel_mesh;
+    use std::f64::consts::FRAC_PI_2;
+
+    // The equator of a sphere of radius 3: curvature 1/3 everywhere
+    fn circle() -> Lissajou3D {
+        Lissajou3D::new(0.0, 1.0, 3.0).with_phase(FRAC_PI_2, 0.0)
+    }
+
+    #[test]
+    fn circle_folds_past_its_radius() {
+        let curve = circle();
+        let options = TunnelOptions::new(1.0, 8, 50).unwrap();
+        let mesh = generate_tunnel_mesh(&curve, &options, &Palette::default());
+        let report = analyze_tunnel(&curve, &options, &mesh);
+        assert!((report.max_fold_free_radius() - 3.0).abs() < 1e-6);
+        assert!((report.max_safe_radius() - 3.0).abs() < 1e-6);
+        assert!(report.fold_ranges().is_empty());
+        assert!(report.is_ok());
+
+        let thick = TunnelOptions::new(3.5, 8, 50).unwrap();
+        let folds = fold_ranges(&curve, &thick);
+        assert_eq!(folds.len(), 1);
+        assert_eq!(folds[0].0, 0.0);
+        assert!(folds[0].1 > 0.99 * curve.period());
+    }
+
+    // The all-pairs loop the grid replaces
+    fn brute_force_proximity(samples: &CurveSamples) -> Vec<(f64, f64)> {
+        let count = samples.params.len();
+        let mut flags = vec![false; count];
+        for i in 0..count {
+            for j in (i + 1)..count {
+                let reach = samples.radii[i] + samples.radii[j];
+                if samples.separation(i, j) > 0.5 * PI * reach
+                    && (samples.positions[j] - samples.positions[i]).magnitude() < reach
+                {
+                    flags[i] = true;
+                    flags[j] = true;
+                }
+            }
+        }
+        flagged_ranges(&flags, &samples.params)
+    }
+
+    #[test]
+    fn grid_finds_the_same_collisions_as_all_pairs() {
+        let curve = Lissajou3D::new(2.0, 7.0, 10.0).with_phase(0.3, 0.0);
+        for radius in [0.2, 0.8, 2.5] {
+            let options = TunnelOptions::new(radius, 8, 50).unwrap();
+            let samples = CurveSamples::new(&curve, &options);
+            let ranges = samples.proximity_ranges();
+            assert_eq!(ranges, brute_force_proximity(&samples), "radius {}", radius);
+            if radius > 2.0 {
+                assert!(!ranges.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn crossing_curves_have_no_safe_radius() {
+        // 3:2 passes through both poles more than once
+        let curve = Lissajou3D::new(3.0, 2.0, 10.0);
+        let options = TunnelOptions::new(0.5, 8, 50).unwrap();
+        let mesh = generate_tunnel_mesh(&curve, &options, &Palette::default());
+        let report = analyze_tunnel(&curve, &options, &mesh);
+        assert!(report.max_safe_radius() < 1e-3);
+        assert!(!report.proximity_ranges().is_empty());
+    }
+
+    #[test]
+    fn flipped_and_collapsed_triangles_are_reported() {
+        let curve = circle();
+        let options = TunnelOptions::new(1.0, 8, 50).unwrap();
+        let mut mesh = generate_tunnel_mesh(&curve, &options, &Palette::default());
+        assert_eq!(check_triangles(&mesh), (vec![], vec![]));
+
+        mesh.triangles.swap(3 * 7, 3 * 7 + 1);
+        let (a, b) = (mesh.triangles[3 * 20], mesh.triangles[3 * 20 + 1]);
+        mesh.vertices[b as usize].pos = mesh.vertices[a as usize].pos;
+        let (degenerate, inverted) = check_triangles(&mesh);
+        assert!(degenerate.contains(&20));
+        assert_eq!(inverted, vec![7]);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::curve::ParametricCurve;
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+// Intervals per loop in the arc-length table
+const ARC_SAMPLES: usize = 4096;
+
+/// How progress along the curve is measured
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Parameterization {
+    /// Equal steps in t; spacing follows the local speed |r'(t)|
+    #[default]
+    Parameter,
+    /// Equal steps in distance along the curve
+    ArcLength,
+}
+
+/// Cumulative arc length s(t) over one period, with the inverse t(s).
+///
+/// Both directions accept values past one loop, so an ever-growing
+/// animation time maps straight through.
+#[derive(Clone, Debug)]
+pub struct ArcLengthTable {
+    period: f64,
+    // lengths[i] = s(period * i / ARC_SAMPLES)
+    lengths: Vec<f64>,
+}
+
+impl ArcLengthTable {
+    pub fn new<C: ParametricCurve + ?Sized>(curve: &C) -> Self {
+        let period = curve.period();
+        let h = period / ARC_SAMPLES as f64;
+        let speed = |t: f64| curve.velocity(t).magnitude();
+
+        let mut lengths = Vec::with_capacity(ARC_SAMPLES + 1);
+        lengths.push(0.0);
+        let mut s = 0.0;
+        let mut prev = speed(0.0);
+        for i in 0..ARC_SAMPLES {
+            // Simpson's rule on each interval
+            let t = h * i as f64;
+            let next = speed(t + h);
+            s += h / 6.0 * (prev + 4.0 * speed(t + 0.5 * h) + next);
+            lengths.push(s);
+            prev = next;
+        }
+
+        Self { period, lengths }
+    }
+
+    /// Length of one full loop
+    pub fn length(&self) -> f64 {
+        self.lengths[ARC_SAMPLES]
+    }
+
+    /// Distance travelled from t = 0 to `t`
+    pub fn arc_length(&self, t: f64) -> f64 {
+        let loops = (t / self.period).floor();
+        let x = (t / self.period - loops) * ARC_SAMPLES as f64;
+        let i = (x.floor() as usize).min(ARC_SAMPLES - 1);
+        let f = x - i as f64;
+        let partial = self.lengths[i] + (self.lengths[i + 1] - self.lengths[i]) * f;
+        loops * self.length() + partial
+    }
+
+    /// Parameter reached after travelling distance `s` from t = 0
+    pub fn parameter(&self, s: f64) -> f64 {
+        let length = self.length();
+        if length <= 0.0 {
+            return 0.0;
+        }
+        let loops = (s / length).floor();
+        let rest = s - loops * length;
+
+        // First sample past `rest`, then interpolate inside its interval
+        let j = self
+            .lengths
+            .partition_point(|&l| l <= rest)
+            .clamp(1, ARC_SAMPLES);
+        let (l0, l1) = (self.lengths[j - 1], self.lengths[j]);
+        let f = if l1 > l0 {
+            (rest - l0) / (l1 - l0)
+        } else {
+            0.0
+        };
+        let h = self.period / ARC_SAMPLES as f64;
+        loops * self.period + ((j - 1) as f64 + f) * h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lisa::Lissajou3D;
+    use std::f64::consts::{FRAC_PI_2, TAU};
+
+    #[test]
+    fn circle_length() {
+        // The equator: θ held at π/2, φ once round
+        let r = 3.0;
+        let circle = Lissajou3D::new(0.0, 1.0, r).with_phase(FRAC_PI_2, 0.0);
+        let table = ArcLengthTable::new(&circle);
+        assert!((table.length() - TAU * r).abs() < 1e-9);
+        assert!((table.arc_length(1.0) - r).abs() < 1e-9);
+        assert!((table.arc_length(TAU + 1.0) - (TAU * r + r)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn parameter_inverts_arc_length() {
+        let curve = Lissajou3D::new(3.0, 2.0, 10.0);
+        let table = ArcLengthTable::new(&curve);
+        let mut previous = -1.0;
+        for i in 0..=500 {
+            let t = 2.0 * curve.period() * i as f64 / 500.0;
+            let s = table.arc_length(t);
+            assert!(s > previous, "s(t) must increase");
+            previous = s;
+            assert!((table.parameter(s) - t).abs() < 1e-9, "t = {}", t);
+        }
+    }
+}
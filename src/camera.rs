@@ -0,0 +1,260 @@
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+use crate::arclength::{ArcLengthTable, Parameterization};
+use crate::curve::ParametricCurve;
+use crate::frames::CurveFrames;
+use crate::math::{Mat4, Quat, V3D};
+
+/// Where the camera watches the curve from
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CameraMode {
+    /// On the curve, looking along the tangent
+    #[default]
+    Inside,
+    /// Trailing behind and above the current point, looking at it
+    Chase,
+    /// Orbiting the whole curve
+    Outside,
+}
+
+/// Eye, look target and up vector of a camera
+#[derive(Clone, Copy, Debug)]
+pub struct CameraPose {
+    pub eye: [f32; 3],
+    pub target: [f32; 3],
+    pub up: [f32; 3],
+}
+
+impl CameraPose {
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_at(self.eye, self.target, self.up)
+    }
+
+    /// Camera orientation in the glTF convention: looking down -Z with +Y up
+    pub fn rotation(&self) -> Quat {
+        let v = |p: [f32; 3]| V3D::new(p[0] as f64, p[1] as f64, p[2] as f64);
+        let forward = (v(self.target) - v(self.eye)).normalize();
+        let right = forward.cross(&v(self.up)).normalize();
+        let up = right.cross(&forward);
+        Quat::from_axes(&right, &up, &-forward)
+    }
+
+    /// Pose a fraction `f` of the way to `other`: the eye moves in a
+    /// straight line and the orientation turns along the shorter arc
+    pub fn interpolate(&self, other: &CameraPose, f: f64) -> CameraPose {
+        let v = |p: [f32; 3]| V3D::new(p[0] as f64, p[1] as f64, p[2] as f64);
+        let a = |p: V3D| [p.x as f32, p.y as f32, p.z as f32];
+        let (eye_a, eye_b) = (v(self.eye), v(other.eye));
+        let reach_a = (v(self.target) - eye_a).magnitude();
+        let reach_b = (v(other.target) - eye_b).magnitude();
+
+        let eye = eye_a + (eye_b - eye_a) * f;
+        let rotation = self.rotation().slerp(&other.rotation(), f);
+        let forward = rotation.rotate(&V3D::new(0.0, 0.0, -1.0));
+        let up = rotation.rotate(&V3D::new(0.0, 1.0, 0.0));
+        CameraPose {
+            eye: a(eye),
+            target: a(eye + forward * (reach_a + (reach_b - reach_a) * f)),
+            up: a(up),
+        }
+    }
+}
+
+/// A timed move from a fixed pose to one that may keep moving, such as the
+/// camera of the mode being switched to
+#[derive(Clone, Copy, Debug)]
+pub struct CameraTransition {
+    from: CameraPose,
+    elapsed: f64,
+    duration: f64,
+}
+
+impl CameraTransition {
+    pub fn new(from: CameraPose, duration: f64) -> Self {
+        Self {
+            from,
+            elapsed: 0.0,
+            duration,
+        }
+    }
+
+    pub fn advance(&mut self, dt: f64) {
+        self.elapsed += dt;
+    }
+
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// How far along the move is, from 0 to 1. Eased in and out so the
+    /// camera leaves and arrives at rest.
+    pub fn progress(&self) -> f64 {
+        if self.finished() {
+            return 1.0;
+        }
+        let x = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        x * x * (3.0 - 2.0 * x)
+    }
+
+    /// Pose on the way to `to`
+    pub fn pose(&self, to: &CameraPose) -> CameraPose {
+        if self.finished() {
+            return *to;
+        }
+        self.from.interpolate(to, self.progress())
+    }
+}
+
+/// Inside view: camera sits on the curve, looks along the tangent and
+/// keeps the frame normal as up
+pub fn follow_pose<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    frames: &CurveFrames,
+    t: f64,
+) -> CameraPose {
+    let pos = curve.position(t);
+    let frame = frames.frame(curve, t);
+    let d1 = frame.tangent;
+    let d2 = frame.normal;
+
+    CameraPose {
+        eye: [pos.x as f32, pos.y as f32, pos.z as f32],
+        target: [
+            (pos.x + d1.x) as f32,
+            (pos.y + d1.y) as f32,
+            (pos.z + d1.z) as f32,
+        ],
+        up: [d2.x as f32, d2.y as f32, d2.z as f32],
+    }
+}
+
+/// Chase view: `distance` behind the current point along the tangent and
+/// `height` above it along the frame normal, looking at the point
+pub fn chase_pose<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    frames: &CurveFrames,
+    t: f64,
+    distance: f64,
+    height: f64,
+) -> CameraPose {
+    let pos = curve.position(t);
+    let frame = frames.frame(curve, t);
+    let eye = pos - frame.tangent * distance + frame.normal * height;
+
+    CameraPose {
+        eye: [eye.x as f32, eye.y as f32, eye.z as f32],
+        target: [pos.x as f32, pos.y as f32, pos.z as f32],
+        up: [
+            frame.normal.x as f32,
+            frame.normal.y as f32,
+            frame.normal.z as f32,
+        ],
+    }
+}
+
+/// `samples + 1` inside-view poses over one loop, evenly spaced in t or in
+/// distance along the curve
+pub fn follow_path<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    frames: &CurveFrames,
+    arc: &ArcLengthTable,
+    travel: Parameterization,
+    samples: usize,
+) -> Vec<CameraPose> {
+    (0..=samples)
+        .map(|i| {
+            let fraction = i as f64 / samples.max(1) as f64;
+            let t = match travel {
+                Parameterization::Parameter => curve.period() * fraction,
+                Parameterization::ArcLength => arc.parameter(arc.length() * fraction),
+            };
+            follow_pose(curve, frames, t)
+        })
+        .collect()
+}
+
+/// Centre and radius of a sphere around one loop of the curve: the middle
+/// of its bounding box and the farthest sample from it
+pub fn bounding_sphere<C: ParametricCurve + ?Sized>(curve: &C, samples: usize) -> (V3D, f64) {
+    let samples = samples.max(1);
+    let points: Vec<V3D> = (0..=samples)
+        .map(|i| curve.position(curve.period() * i as f64 / samples as f64))
+        .collect();
+    let (min, max) = points.iter().fold(
+        (
+            V3D::new(f64::MAX, f64::MAX, f64::MAX),
+            V3D::new(f64::MIN, f64::MIN, f64::MIN),
+        ),
+        |(lo, hi), p| {
+            (
+                V3D::new(lo.x.min(p.x), lo.y.min(p.y), lo.z.min(p.z)),
+                V3D::new(hi.x.max(p.x), hi.y.max(p.y), hi.z.max(p.z)),
+            )
+        },
+    );
+    let center = (min + max) * 0.5;
+    let radius = points
+        .iter()
+        .map(|p| (*p - center).magnitude())
+        .fold(0.0, f64::max);
+    (center, radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pose(eye: [f32; 3], target: [f32; 3]) -> CameraPose {
+        CameraPose {
+            eye,
+            target,
+            up: [0.0, 1.0, 0.0],
+        }
+    }
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for k in 0..3 {
+            assert!((a[k] - b[k]).abs() < 1e-5, "{:?} vs {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn progress_is_clamped_and_eased() {
+        let mut transition = CameraTransition::new(pose([0.0; 3], [0.0, 0.0, -1.0]), 2.0);
+        assert_eq!(transition.progress(), 0.0);
+        // Going backwards doesn't overshoot the start
+        transition.advance(-1.0);
+        assert_eq!(transition.progress(), 0.0);
+        transition.advance(1.5);
+        // Smoothstep of a quarter
+        assert!((transition.progress() - 0.156_25).abs() < 1e-12);
+        transition.advance(0.5);
+        assert!((transition.progress() - 0.5).abs() < 1e-12);
+        assert!(!transition.finished());
+        transition.advance(5.0);
+        assert!(transition.finished());
+        assert_eq!(transition.progress(), 1.0);
+    }
+
+    #[test]
+    fn finished_transition_lands_on_the_target() {
+        let from = pose([0.0; 3], [0.0, 0.0, -1.0]);
+        let to = pose([4.0, 1.0, 2.0], [5.0, 1.0, 2.0]);
+        let mut transition = CameraTransition::new(from, 1.0);
+        // Starts where the camera was
+        let start = transition.pose(&to);
+        assert_close(start.eye, from.eye);
+        assert_close(start.target, from.target);
+        assert_close(start.up, from.up);
+
+        transition.advance(0.5);
+        let middle = transition.pose(&to);
+        assert_close(middle.eye, [2.0, 0.5, 1.0]);
+
+        transition.advance(0.5);
+        let end = transition.pose(&to);
+        assert_eq!((end.eye, end.target, end.up), (to.eye, to.target, to.up));
+    }
+}
@@ -0,0 +1,459 @@
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+/// RGB in [0, 1] from hue in turns (0 red, 1/3 green, 2/3 blue, wrapping),
+/// saturation and value in [0, 1]
+pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> [f32; 3] {
+    let h = 6.0 * h.rem_euclid(1.0);
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    [r + m, g + m, b + m]
+}
+
+/// Hue in turns, saturation and value, inverse of `hsv_to_rgb`. Greys have
+/// hue 0.
+pub fn rgb_to_hsv([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let s = if max > 0.0 { delta / max } else { 0.0 };
+    [hue(r, g, b, max, delta), s, max]
+}
+
+/// RGB from hue in turns, saturation and lightness in [0, 1]
+pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> [f32; 3] {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let v = l + 0.5 * c;
+    let sv = if v > 0.0 { c / v } else { 0.0 };
+    hsv_to_rgb(h, sv, v)
+}
+
+/// Hue in turns, saturation and lightness, inverse of `hsl_to_rgb`
+pub fn rgb_to_hsl([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let l = 0.5 * (max + min);
+    let s = if delta > 0.0 {
+        delta / (1.0 - (2.0 * l - 1.0).abs())
+    } else {
+        0.0
+    };
+    [hue(r, g, b, max, delta), s, l]
+}
+
+// Shared hue of the HSV and HSL cylinders, in turns
+fn hue(r: f32, g: f32, b: f32, max: f32, delta: f32) -> f32 {
+    if delta <= 0.0 {
+        return 0.0;
+    }
+    let sector = if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    sector / 6.0
+}
+
+/// OKLab (L, a, b) from gamma-encoded sRGB. Björn Ottosson's matrices.
+pub fn rgb_to_oklab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    let l = 0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b;
+    let m = 0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b;
+    let s = 0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b;
+    let [l, m, s] = [l, m, s].map(f32::cbrt);
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// Gamma-encoded sRGB from OKLab, clamped to [0, 1]
+pub fn oklab_to_rgb([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = l + 0.396_337_78 * a + 0.215_803_76 * b;
+    let m_ = l - 0.105_561_346 * a - 0.063_854_17 * b;
+    let s_ = l - 0.089_484_18 * a - 1.291_485_5 * b;
+    let [l, m, s] = [l_, m_, s_].map(|x| x * x * x);
+    [
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+    ]
+    .map(|c| linear_to_srgb(c).clamp(0.0, 1.0))
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        12.92 * c
+    } else {
+        1.055 * c.max(0.0).powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Named colour scales over [0, 1]
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Colormap {
+    /// The original ring colours: three sines of different frequency.
+    /// Cyclic, so closed loops have no seam.
+    #[default]
+    Sine,
+    /// Perceptually uniform dark blue to yellow (matplotlib)
+    Viridis,
+    /// Perceptually uniform black through purple to pale yellow (matplotlib)
+    Magma,
+    /// High-contrast blue to red rainbow (Google)
+    Turbo,
+    /// Full HSV hue circle; cyclic
+    Rainbow,
+}
+
+// Degree-6 polynomial fits of the matplotlib maps by Matt Zucker,
+// coefficients from x⁰ up
+const VIRIDIS: [[f32; 3]; 7] = [
+    [0.277_727_33, 0.005_407_344_5, 0.334_099_8],
+    [0.105_093_04, 1.404_613_5, 1.384_590_2],
+    [-0.330_861_83, 0.214_847_56, 0.095_095_16],
+    [-4.634_230_6, -5.799_101, -19.332_441],
+    [6.228_27, 14.179_933, 56.690_55],
+    [4.776_385, -13.745_145, -65.353_03],
+    [-5.435_456, 4.645_852_6, 26.312_435],
+];
+const MAGMA: [[f32; 3]; 7] = [
+    [-0.002_136_485, -0.000_749_655_05, -0.005_386_128],
+    [0.251_660_54, 0.677_523_2, 2.494_026_7],
+    [8.353_717, -3.577_719_5, 0.314_467_9],
+    [-27.668_733, 14.264_731, -13.649_213],
+    [52.176_14, -27.943_607, 12.944_169],
+    [-50.768_524, 29.046_583, 4.234_153],
+    [18.655_705, -11.489_773, -5.601_961_5],
+];
+// Anton Mikhailov's polynomial approximation of Turbo
+const TURBO: [[f32; 3]; 6] = [
+    [0.135_721_38, 0.091_402_61, 0.106_673_3],
+    [4.615_392_6, 2.194_188_4, 12.641_946],
+    [-42.660_324, 4.842_966_6, -60.582_047],
+    [132.131_08, -14.185_033, 110.362_77],
+    [-152.942_4, 4.277_299, -89.903_11],
+    [59.286_38, 2.829_566, 27.348_25],
+];
+
+impl Colormap {
+    /// Colour at `x`, clamped to [0, 1] (wrapped for the cyclic maps)
+    pub fn at(self, x: f32) -> [f32; 3] {
+        match self {
+            Colormap::Sine => {
+                let delta = x * std::f32::consts::TAU;
+                [
+                    0.5 + 0.5 * delta.sin(),
+                    0.35 + 0.35 * (3.0 * delta).cos(),
+                    0.75 + 0.25 * (4.0 * delta).sin(),
+                ]
+            }
+            Colormap::Viridis => polynomial(&VIRIDIS, x),
+            Colormap::Magma => polynomial(&MAGMA, x),
+            Colormap::Turbo => polynomial(&TURBO, x),
+            Colormap::Rainbow => hsv_to_rgb(x, 1.0, 1.0),
+        }
+    }
+}
+
+fn polynomial(coefficients: &[[f32; 3]], x: f32) -> [f32; 3] {
+    let x = x.clamp(0.0, 1.0);
+    let mut rgb = [0.0; 3];
+    for c in coefficients.iter().rev() {
+        for k in 0..3 {
+            rgb[k] = rgb[k] * x + c[k];
+        }
+    }
+    rgb.map(|c| c.clamp(0.0, 1.0))
+}
+
+/// Colour stops at positions in [0, 1], blended in OKLab so the midpoints
+/// keep their lightness instead of going muddy
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient {
+    // Sorted by position, colours already in OKLab
+    stops: Vec<(f32, [f32; 3])>,
+}
+
+impl Gradient {
+    /// Stops as `(position, rgb)` in any order; at least one is needed
+    pub fn new(mut stops: Vec<(f32, [f32; 3])>) -> Result<Self, String> {
+        if stops.is_empty() {
+            return Err("a gradient needs at least one stop".into());
+        }
+        stops.sort_by(|p, q| p.0.total_cmp(&q.0));
+        Ok(Self {
+            stops: stops
+                .into_iter()
+                .map(|(x, rgb)| (x, rgb_to_oklab(rgb)))
+                .collect(),
+        })
+    }
+
+    /// Stops from interleaved `[x0, r0, g0, b0, x1, r1, ...]`
+    pub fn from_flat(values: &[f32]) -> Result<Self, String> {
+        if !values.len().is_multiple_of(4) {
+            return Err("gradient stops must be (position, r, g, b) groups".into());
+        }
+        Self::new(
+            values
+                .chunks(4)
+                .map(|s| (s[0], [s[1], s[2], s[3]]))
+                .collect(),
+        )
+    }
+
+    /// Colour at `x`; held past the first and last stops
+    pub fn at(&self, x: f32) -> [f32; 3] {
+        let stops = &self.stops;
+        let j = stops.partition_point(|s| s.0 <= x);
+        let lab = if j == 0 {
+            stops[0].1
+        } else if j == stops.len() {
+            stops[j - 1].1
+        } else {
+            let ((x0, c0), (x1, c1)) = (stops[j - 1], stops[j]);
+            let f = if x1 > x0 { (x - x0) / (x1 - x0) } else { 1.0 };
+            [0, 1, 2].map(|k| c0[k] + (c1[k] - c0[k]) * f)
+        };
+        oklab_to_rgb(lab)
+    }
+}
+
+/// Where ring colours come from: a scalar in [0, 1] goes in, RGB comes out
+#[derive(Clone, Debug, PartialEq)]
+pub enum Palette {
+    Colormap(Colormap),
+    Gradient(Gradient),
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::Colormap(Colormap::default())
+    }
+}
+
+impl Palette {
+    pub fn at(&self, x: f32) -> [f32; 3] {
+        match self {
+            Palette::Colormap(map) => map.at(x),
+            Palette::Gradient(gradient) => gradient.at(x),
+        }
+    }
+
+    /// Colours at `i / size` as RGBA bytes, for a 1D lookup texture that
+    /// wraps: the texel after the last is the colour at 1, which is texel 0
+    /// again on cyclic palettes
+    pub fn texels(&self, size: usize) -> Vec<u8> {
+        (0..size)
+            .flat_map(|i| {
+                let [r, g, b] = self.at(i as f32 / size as f32);
+                [r, g, b, 1.0].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+            })
+            .collect()
+    }
+}
+
+/// Colour cycling done on the GPU: each vertex keeps its palette position
+/// and the fragment shader looks the colour up again with a moving offset,
+/// so nothing is rebuilt or re-uploaded per frame
+#[derive(Clone, Copy, Debug)]
+pub struct PaletteAnimation {
+    pub enabled: bool,
+    /// Palette lengths per second that the colours flow along the tunnel
+    pub speed: f32,
+    /// Times the palette repeats along the tunnel
+    pub repeat: f32,
+    /// Trips along the tunnel per second of a bright pulse
+    pub pulse_speed: f32,
+    /// Width of the pulse as a fraction of the tunnel
+    pub pulse_width: f32,
+    /// Extra brightness at the centre of the pulse; 0 turns it off
+    pub pulse_strength: f32,
+}
+
+impl Default for PaletteAnimation {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            speed: 0.1,
+            repeat: 1.0,
+            pulse_speed: 0.25,
+            pulse_width: 0.02,
+            pulse_strength: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: [f32; 3], b: [f32; 3], tolerance: f32) {
+        for k in 0..3 {
+            assert!(
+                (a[k] - b[k]).abs() <= tolerance,
+                "{:?} vs {:?} differ in channel {}",
+                a,
+                b,
+                k
+            );
+        }
+    }
+
+    // A spread of colours over the cube, greys and primaries included
+    fn samples() -> Vec<[f32; 3]> {
+        let steps = [0.0, 0.2, 0.55, 1.0];
+        let mut colors = Vec::new();
+        for r in steps {
+            for g in steps {
+                for b in steps {
+                    colors.push([r, g, b]);
+                }
+            }
+        }
+        colors
+    }
+
+    #[test]
+    fn primary_and_secondary_hues() {
+        let hues = [
+            (0.0, [1.0, 0.0, 0.0]),
+            (1.0 / 6.0, [1.0, 1.0, 0.0]),
+            (2.0 / 6.0, [0.0, 1.0, 0.0]),
+            (3.0 / 6.0, [0.0, 1.0, 1.0]),
+            (4.0 / 6.0, [0.0, 0.0, 1.0]),
+            (5.0 / 6.0, [1.0, 0.0, 1.0]),
+        ];
+        for (h, rgb) in hues {
+            assert_close(hsv_to_rgb(h, 1.0, 1.0), rgb, 1e-5);
+            assert_close(hsl_to_rgb(h, 1.0, 0.5), rgb, 1e-5);
+        }
+        // No saturation is grey at the value
+        assert_close(hsv_to_rgb(0.3, 0.0, 0.4), [0.4; 3], 1e-6);
+    }
+
+    #[test]
+    fn hue_wraps_at_a_full_turn() {
+        for h in [0.0, 0.1, 0.45, 0.8] {
+            let rgb = hsv_to_rgb(h, 0.7, 0.9);
+            assert_close(hsv_to_rgb(h + 1.0, 0.7, 0.9), rgb, 1e-5);
+            assert_close(hsv_to_rgb(h - 1.0, 0.7, 0.9), rgb, 1e-5);
+        }
+        assert_close(hsv_to_rgb(1.0, 1.0, 1.0), [1.0, 0.0, 0.0], 1e-6);
+        assert_close(hsv_to_rgb(-1.0 / 3.0, 1.0, 1.0), [0.0, 0.0, 1.0], 1e-5);
+    }
+
+    #[test]
+    fn hsv_and_hsl_round_trip() {
+        for rgb in samples() {
+            let [h, s, v] = rgb_to_hsv(rgb);
+            assert_close(hsv_to_rgb(h, s, v), rgb, 1e-5);
+            let [h, s, l] = rgb_to_hsl(rgb);
+            assert_close(hsl_to_rgb(h, s, l), rgb, 1e-5);
+        }
+    }
+
+    #[test]
+    fn oklab_round_trips() {
+        for rgb in samples() {
+            assert_close(oklab_to_rgb(rgb_to_oklab(rgb)), rgb, 1e-3);
+        }
+        // White has lightness 1 and no chroma
+        assert_close(rgb_to_oklab([1.0; 3]), [1.0, 0.0, 0.0], 1e-3);
+    }
+
+    #[test]
+    fn colormap_endpoints() {
+        // Reference values of the original maps. The fits are close, not
+        // exact; the Turbo one drifts most at the very ends.
+        let ends = [
+            (
+                Colormap::Viridis,
+                [0.267, 0.005, 0.329],
+                [0.993, 0.906, 0.144],
+                0.04,
+            ),
+            (
+                Colormap::Magma,
+                [0.001, 0.0, 0.014],
+                [0.987, 0.991, 0.75],
+                0.04,
+            ),
+            (
+                Colormap::Turbo,
+                [0.19, 0.072, 0.232],
+                [0.48, 0.016, 0.011],
+                0.13,
+            ),
+        ];
+        for (map, start, end, tolerance) in ends {
+            assert_close(map.at(0.0), start, tolerance);
+            assert_close(map.at(1.0), end, tolerance);
+            // Clamped outside [0, 1]
+            assert_eq!(map.at(-0.5), map.at(0.0));
+            assert_eq!(map.at(1.5), map.at(1.0));
+        }
+        // The cyclic maps meet themselves
+        for map in [Colormap::Sine, Colormap::Rainbow] {
+            assert_close(map.at(0.0), map.at(1.0), 1e-5);
+        }
+        assert_close(Colormap::Rainbow.at(0.0), [1.0, 0.0, 0.0], 1e-6);
+    }
+
+    #[test]
+    fn gradient_interpolates_between_stops() {
+        let (red, blue) = ([1.0, 0.0, 0.0], [0.0, 0.0, 1.0]);
+        // Given out of order
+        let gradient = Gradient::new(vec![(0.8, blue), (0.2, red)]).unwrap();
+        assert_close(gradient.at(0.2), red, 1e-3);
+        assert_close(gradient.at(0.8), blue, 1e-3);
+        // Held past the ends
+        assert_close(gradient.at(0.0), red, 1e-3);
+        assert_close(gradient.at(1.0), blue, 1e-3);
+        // Halfway is the OKLab midpoint of the stops
+        let (a, b) = (rgb_to_oklab(red), rgb_to_oklab(blue));
+        let mid = [0, 1, 2].map(|k| 0.5 * (a[k] + b[k]));
+        assert_close(rgb_to_oklab(gradient.at(0.5)), mid, 1e-2);
+
+        let flat = Gradient::from_flat(&[0.8, 0.0, 0.0, 1.0, 0.2, 1.0, 0.0, 0.0]).unwrap();
+        assert_eq!(flat, gradient);
+        assert!(Gradient::from_flat(&[0.0, 1.0, 0.0]).is_err());
+        assert!(Gradient::new(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn texel_layout() {
+        let palette = Palette::Colormap(Colormap::Rainbow);
+        let size = 6;
+        let texels = palette.texels(size);
+        assert_eq!(texels.len(), 4 * size);
+        // RGBA per texel at i / size, fully opaque
+        assert_eq!(&texels[..4], &[255, 0, 0, 255]);
+        assert_eq!(&texels[8..12], &[0, 255, 0, 255]);
+        assert_eq!(&texels[16..20], &[0, 0, 255, 255]);
+        assert!(texels.chunks(4).all(|t| t[3] == 255));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::math::V3D;
+
+/// Moving coordinate system along a curve.
+/// `tangent` is the direction of travel, `normal` and `binormal` span the
+/// plane the tunnel rings are drawn in.
+#[derive(Clone, Copy, Debug)]
+pub struct Frame {
+    pub tangent: V3D,
+    pub normal: V3D,
+    pub binormal: V3D,
+}
+
+/// A closed parametric path the tunnel can follow.
+///
+/// Only `position` and `tangent` are required. The default `frame` is the
+/// radial frame: the normal is the direction away from the origin projected
+/// onto the plane perpendicular to the tangent.
+pub trait ParametricCurve {
+    fn position(&self, t: f64) -> V3D;
+
+    // Tangent (normalized)
+    fn tangent(&self, t: f64) -> V3D;
+
+    /// Unnormalized first derivative r'(t). Defaults to a central difference.
+    fn velocity(&self, t: f64) -> V3D {
+        let h = 1e-5 * self.period();
+        (self.position(t + h) - self.position(t - h)) * (0.5 / h)
+    }
+
+    /// Second derivative r''(t). Defaults to a central difference.
+    fn acceleration(&self, t: f64) -> V3D {
+        let h = 1e-4 * self.period();
+        (self.velocity(t + h) - self.velocity(t - h)) * (0.5 / h)
+    }
+
+    /// Third derivative r'''(t). Defaults to a central difference.
+    fn jerk(&self, t: f64) -> V3D {
+        let h = 1e-4 * self.period();
+        (self.acceleration(t + h) - self.acceleration(t - h)) * (0.5 / h)
+    }
+
+    /// Parametric speed |r'(t)|
+    fn speed(&self, t: f64) -> f64 {
+        self.velocity(t).magnitude()
+    }
+
+    /// Curvature κ = |r' × r''| / |r'|³
+    fn curvature(&self, t: f64) -> f64 {
+        let v = self.velocity(t);
+        let speed = v.magnitude();
+        if speed == 0.0 {
+            return 0.0;
+        }
+        v.cross(&self.acceleration(t)).magnitude() / (speed * speed * speed)
+    }
+
+    /// Torsion τ = (r' × r'') · r''' / |r' × r''|²
+    fn torsion(&self, t: f64) -> f64 {
+        let n = self.velocity(t).cross(&self.acceleration(t));
+        let n2 = n.dot(&n);
+        if n2 == 0.0 {
+            return 0.0;
+        }
+        n.dot(&self.jerk(t)) / n2
+    }
+
+    fn frame(&self, t: f64) -> Frame {
+        let tangent = self.tangent(t);
+        let radial = self.position(t).normalize();
+
+        // Project radial onto plane perpendicular to tangent
+        let dot = radial.dot(&tangent);
+        let normal = V3D::new(
+            radial.x - dot * tangent.x,
+            radial.y - dot * tangent.y,
+            radial.z - dot * tangent.z,
+        )
+        .normalize();
+
+        Frame {
+            tangent,
+            normal,
+            binormal: tangent.cross(&normal),
+        }
+    }
+
+    /// Parameter span of one full loop
+    fn period(&self) -> f64 {
+        2.0 * std::f64::consts::PI
+    }
+
+    /// Whether `position(period())` comes back to `position(0)`. Open curves
+    /// get end caps instead of a seam.
+    fn is_closed(&self) -> bool {
+        true
+    }
+
+    /// Transform matrix from trihedron: maps the XY plane onto the
+    /// normal/binormal plane at `position(t)`
+    fn transform_matrix(&self, t: f64) -> [[f64; 4]; 4] {
+        let pos = self.position(t);
+        let frame = self.frame(t);
+        frame_matrix(&pos, &frame)
+    }
+}
+
+pub fn frame_matrix(pos: &V3D, frame: &Frame) -> [[f64; 4]; 4] {
+    let (n, b, t) = (frame.normal, frame.binormal, frame.tangent);
+    [
+        [n.x, b.x, t.x, pos.x],
+        [n.y, b.y, t.y, pos.y],
+        [n.z, b.z, t.z, pos.z],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// Best rational approximation p/q of `x > 0` with q <= `max_denominator`,
+/// from the continued fraction expansion
+pub fn rational_approximation(x: f64, max_denominator: u64) -> (u64, u64) {
+    let (mut p0, mut q0, mut p1, mut q1) = (0u64, 1u64, 1u64, 0u64);
+    let mut rest = x;
+
+    loop {
+        let whole = rest.floor();
+        let a = whole as u64;
+        let (p2, q2) = (a * p1 + p0, a * q1 + q0);
+        if q2 > max_denominator {
+            break;
+        }
+        (p0, q0, p1, q1) = (p1, q1, p2, q2);
+
+        let frac = rest - whole;
+        if frac < 1e-12 || (x - p1 as f64 / q1 as f64).abs() <= 1e-12 * x {
+            break;
+        }
+        rest = 1.0 / frac;
+    }
+
+    (p1, q1.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rational_approximation_finds_convergents() {
+        assert_eq!(rational_approximation(0.75, 64), (3, 4));
+        assert_eq!(rational_approximation(2.0, 64), (2, 1));
+        assert_eq!(rational_approximation(1.5, 64), (3, 2));
+        assert_eq!(rational_approximation(std::f64::consts::PI, 64), (22, 7));
+        assert_eq!(
+            rational_approximation(std::f64::consts::SQRT_2, 64),
+            (41, 29)
+        );
+        assert_eq!(rational_approximation(std::f64::consts::SQRT_2, 1), (1, 1));
+    }
+}
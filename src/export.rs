@@ -0,0 +1,613 @@
+use std::fmt::Write;
+
+use crate::camera::CameraPose;
+use crate::mesh::Mesh;
+
+// glTF component types and buffer view targets
+const GL_FLOAT: u32 = 5126;
+const GL_UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Perspective camera with its keyframes, in seconds
+#[derive(Clone, Debug)]
+pub struct CameraTrack {
+    /// Vertical field of view in radians
+    pub yfov: f32,
+    pub aspect: f32,
+    pub znear: f32,
+    pub zfar: f32,
+    pub keys: Vec<(f32, CameraPose)>,
+}
+
+impl CameraTrack {
+    /// Poses spread evenly over `duration` seconds
+    pub fn evenly_spaced(
+        yfov: f32,
+        aspect: f32,
+        znear: f32,
+        zfar: f32,
+        poses: Vec<CameraPose>,
+        duration: f32,
+    ) -> Self {
+        let step = duration / (poses.len().max(2) - 1) as f32;
+        let keys = poses
+            .into_iter()
+            .enumerate()
+            .map(|(i, pose)| (i as f32 * step, pose))
+            .collect();
+        Self {
+            yfov,
+            aspect,
+            znear,
+            zfar,
+            keys,
+        }
+    }
+}
+
+/// Wavefront OBJ with per-vertex colours (`v x y z r g b`), normals and
+/// UVs. Triangles keep the mesh winding, so flip a tunnel mesh first to
+/// make its faces agree with its normals.
+pub fn write_obj(mesh: &Mesh) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# rust-anima tunnel");
+    let _ = writeln!(out, "o tunnel");
+
+    for v in &mesh.vertices {
+        let _ = writeln!(
+            out,
+            "v {} {} {} {} {} {}",
+            v.pos[0], v.pos[1], v.pos[2], v.color[0], v.color[1], v.color[2]
+        );
+    }
+    for v in &mesh.vertices {
+        let _ = writeln!(out, "vt {} {}", v.uv[0], v.uv[1]);
+    }
+    for v in &mesh.vertices {
+        let _ = writeln!(out, "vn {} {} {}", v.normal[0], v.normal[1], v.normal[2]);
+    }
+
+    // OBJ indices start at 1; position, UV and normal share the same index
+    for tri in mesh.triangles.chunks_exact(3) {
+        let (a, b, c) = (tri[0] + 1, tri[1] + 1, tri[2] + 1);
+        let _ = writeln!(out, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}");
+    }
+
+    out
+}
+
+/// Binary STL: 80-byte header, triangle count, then 50 bytes per triangle
+pub fn write_stl_binary(mesh: &Mesh) -> Vec<u8> {
+    let count = mesh.triangles.len() / 3;
+    let mut out = Vec::with_capacity(84 + 50 * count);
+
+    let mut header = [0u8; 80];
+    let title = b"rust-anima tunnel";
+    header[..title.len()].copy_from_slice(title);
+    out.extend_from_slice(&header);
+    out.extend_from_slice(&(count as u32).to_le_bytes());
+
+    for (normal, corners) in facets(mesh) {
+        for value in normal.iter().chain(corners.iter().flatten()) {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        // Attribute byte count, unused
+        out.extend_from_slice(&0u16.to_le_bytes());
+    }
+
+    out
+}
+
+/// ASCII STL with a solid named `name`
+pub fn write_stl_ascii(mesh: &Mesh, name: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "solid {name}");
+    for (n, corners) in facets(mesh) {
+        let _ = writeln!(out, "  facet normal {} {} {}", n[0], n[1], n[2]);
+        let _ = writeln!(out, "    outer loop");
+        for p in corners {
+            let _ = writeln!(out, "      vertex {} {} {}", p[0], p[1], p[2]);
+        }
+        let _ = writeln!(out, "    endloop");
+        let _ = writeln!(out, "  endfacet");
+    }
+    let _ = writeln!(out, "endsolid {name}");
+    out
+}
+
+// STL stores one flat normal per triangle, following the winding
+fn facets(mesh: &Mesh) -> impl Iterator<Item = ([f32; 3], [[f32; 3]; 3])> + '_ {
+    mesh.triangles.chunks_exact(3).map(|tri| {
+        let [a, b, c] = [0, 1, 2].map(|k| mesh.vertices[tri[k] as usize].pos);
+        let e1 = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let e2 = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        let n = [
+            e1[1] * e2[2] - e1[2] * e2[1],
+            e1[2] * e2[0] - e1[0] * e2[2],
+            e1[0] * e2[1] - e1[1] * e2[0],
+        ];
+        let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        let normal = if len > 0.0 {
+            [n[0] / len, n[1] / len, n[2] / len]
+        } else {
+            [0.0; 3]
+        };
+        (normal, [a, b, c])
+    })
+}
+
+/// 3D Manufacturing Format package: the mesh as a single object in
+/// millimetres, in a ZIP archive stored without compression. 3MF expects
+/// triangles wound counter-clockwise seen from outside, as the solid tube is.
+pub fn write_3mf(mesh: &Mesh) -> Vec<u8> {
+    let mut model = String::new();
+    let _ = write!(
+        model,
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<model unit="millimeter" xml:lang="en-US" "#,
+            r#"xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">"#,
+            r#"<resources><object id="1" type="model"><mesh><vertices>"#
+        )
+    );
+    for v in &mesh.vertices {
+        let _ = write!(
+            model,
+            r#"<vertex x="{}" y="{}" z="{}"/>"#,
+            v.pos[0], v.pos[1], v.pos[2]
+        );
+    }
+    model.push_str("</vertices><triangles>");
+    for tri in mesh.triangles.chunks_exact(3) {
+        let _ = write!(
+            model,
+            r#"<triangle v1="{}" v2="{}" v3="{}"/>"#,
+            tri[0], tri[1], tri[2]
+        );
+    }
+    model.push_str(
+        r#"</triangles></mesh></object></resources><build><item objectid="1"/></build></model>"#,
+    );
+
+    let content_types = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+        r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+        r#"<Default Extension="model" ContentType="application/vnd.ms-package.3dmanufacturing-3dmodel+xml"/>"#,
+        r#"</Types>"#
+    );
+    let relationships = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        r#"<Relationship Target="/3D/3dmodel.model" Id="rel0" "#,
+        r#"Type="http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel"/>"#,
+        r#"</Relationships>"#
+    );
+
+    zip_stored(&[
+        ("[Content_Types].xml", content_types.as_bytes()),
+        ("_rels/.rels", relationships.as_bytes()),
+        ("3D/3dmodel.model", model.as_bytes()),
+    ])
+}
+
+// ZIP archive with every file stored uncompressed, dated 1980-01-01
+fn zip_stored(files: &[(&str, &[u8])]) -> Vec<u8> {
+    const VERSION: u16 = 20;
+    const DOS_DATE: u16 = (1 << 5) | 1;
+
+    let mut out = Vec::new();
+    let mut directory = Vec::new();
+    for (name, data) in files {
+        let offset = out.len() as u32;
+        let crc = crc32(data);
+        // Fields shared by the local header and the directory entry:
+        // flags, method, time, date, CRC, both sizes, name length
+        let mut common = Vec::with_capacity(26);
+        for half in [0u16, 0, 0, DOS_DATE] {
+            common.extend_from_slice(&half.to_le_bytes());
+        }
+        for word in [crc, data.len() as u32, data.len() as u32] {
+            common.extend_from_slice(&word.to_le_bytes());
+        }
+        common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+
+        out.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        out.extend_from_slice(&VERSION.to_le_bytes());
+        out.extend_from_slice(&common);
+        out.extend_from_slice(&0u16.to_le_bytes()); // Extra field length
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        directory.extend_from_slice(&VERSION.to_le_bytes()); // Made by
+        directory.extend_from_slice(&VERSION.to_le_bytes()); // Needed
+        directory.extend_from_slice(&common);
+        // Extra and comment lengths, disk, internal and external attributes
+        directory.extend_from_slice(&[0; 12]);
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+    }
+
+    let directory_offset = out.len() as u32;
+    out.extend_from_slice(&directory);
+    out.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    out.extend_from_slice(&[0; 4]); // This disk, directory disk
+    out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+    out.extend_from_slice(&directory_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // Comment length
+    out
+}
+
+// CRC-32 as used by ZIP (reflected, polynomial 0xEDB88320)
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// glTF 2.0 binary (.glb) with the mesh and, if given, a camera node whose
+/// translation and rotation are animated along `camera.keys`. Walls and
+/// grid lines are separate primitives sharing one set of vertex attributes.
+pub fn write_glb(mesh: &Mesh, camera: Option<&CameraTrack>) -> Vec<u8> {
+    let mut gltf = GltfBuilder::default();
+
+    let positions: Vec<f32> = mesh.vertices.iter().flat_map(|v| v.pos).collect();
+    let normals: Vec<f32> = mesh.vertices.iter().flat_map(|v| v.normal).collect();
+    let uvs: Vec<f32> = mesh.vertices.iter().flat_map(|v| v.uv).collect();
+    let colors: Vec<f32> = mesh.vertices.iter().flat_map(|v| v.color).collect();
+
+    let attributes = format!(
+        r#"{{"POSITION":{},"NORMAL":{},"TEXCOORD_0":{},"COLOR_0":{}}}"#,
+        gltf.floats(&positions, "VEC3", Some(ARRAY_BUFFER), true),
+        gltf.floats(&normals, "VEC3", Some(ARRAY_BUFFER), false),
+        gltf.floats(&uvs, "VEC2", Some(ARRAY_BUFFER), false),
+        gltf.floats(&colors, "VEC4", Some(ARRAY_BUFFER), false),
+    );
+
+    // Material 0: translucent walls seen from both sides. Material 1: grid lines.
+    let mut primitives = Vec::new();
+    for (indices, mode, material) in [
+        (&mesh.triangles, 4, 0),
+        (&mesh.long_lines, 1, 1),
+        (&mesh.lat_lines, 1, 1),
+    ] {
+        if indices.is_empty() {
+            continue;
+        }
+        let accessor = gltf.indices(indices);
+        primitives.push(format!(
+            r#"{{"attributes":{attributes},"indices":{accessor},"mode":{mode},"material":{material}}}"#
+        ));
+    }
+
+    let mut nodes = vec![r#"{"name":"tunnel","mesh":0}"#.to_string()];
+    let mut extra = String::new();
+    if let Some(camera) = camera.filter(|c| !c.keys.is_empty()) {
+        let times: Vec<f32> = camera.keys.iter().map(|(time, _)| *time).collect();
+        let translations: Vec<f32> = camera.keys.iter().flat_map(|(_, p)| p.eye).collect();
+
+        // Keep consecutive quaternions in the same hemisphere so the
+        // interpolation takes the short way round
+        let mut rotations = Vec::with_capacity(4 * camera.keys.len());
+        let mut previous = None;
+        for (_, pose) in &camera.keys {
+            let mut q = pose.rotation();
+            if previous.is_some_and(|p| q.dot(&p) < 0.0) {
+                q = -q;
+            }
+            previous = Some(q);
+            rotations.extend([q.x as f32, q.y as f32, q.z as f32, q.w as f32]);
+        }
+
+        let input = gltf.floats(&times, "SCALAR", None, true);
+        let translation = gltf.floats(&translations, "VEC3", None, false);
+        let rotation = gltf.floats(&rotations, "VEC4", None, false);
+
+        let first = &translations[..3];
+        let first_rotation = &rotations[..4];
+        nodes.push(format!(
+            r#"{{"name":"camera","camera":0,"translation":{},"rotation":{}}}"#,
+            json_array(first),
+            json_array(first_rotation)
+        ));
+        let _ = write!(
+            extra,
+            r#","cameras":[{{"type":"perspective","perspective":{{"yfov":{},"aspectRatio":{},"znear":{},"zfar":{}}}}}]"#,
+            camera.yfov, camera.aspect, camera.znear, camera.zfar
+        );
+        let _ = write!(
+            extra,
+            concat!(
+                r#","animations":[{{"name":"fly-through","#,
+                r#""channels":[{{"sampler":0,"target":{{"node":1,"path":"translation"}}}},"#,
+                r#"{{"sampler":1,"target":{{"node":1,"path":"rotation"}}}}],"#,
+                r#""samplers":[{{"input":{input},"output":{translation},"interpolation":"LINEAR"}},"#,
+                r#"{{"input":{input},"output":{rotation},"interpolation":"LINEAR"}}]}}]"#
+            ),
+            input = input,
+            translation = translation,
+            rotation = rotation
+        );
+    }
+
+    let scene_nodes: Vec<String> = (0..nodes.len()).map(|i| i.to_string()).collect();
+    let json = format!(
+        concat!(
+            r#"{{"asset":{{"version":"2.0","generator":"rust-anima"}},"#,
+            r#""scene":0,"scenes":[{{"nodes":[{}]}}],"nodes":[{}],"#,
+            r#""meshes":[{{"name":"tunnel","primitives":[{}]}}],"#,
+            r#""materials":[{}],"#,
+            r#""buffers":[{{"byteLength":{}}}],"bufferViews":[{}],"accessors":[{}]{}}}"#
+        ),
+        scene_nodes.join(","),
+        nodes.join(","),
+        primitives.join(","),
+        concat!(
+            r#"{"name":"walls","alphaMode":"BLEND","doubleSided":true,"#,
+            r#""pbrMetallicRoughness":{"baseColorFactor":[1,1,1,1],"metallicFactor":0,"roughnessFactor":1}},"#,
+            r#"{"name":"grid","pbrMetallicRoughness":{"baseColorFactor":[1,1,1,1],"metallicFactor":0,"roughnessFactor":1}}"#
+        ),
+        gltf.bin.len(),
+        gltf.views.join(","),
+        gltf.accessors.join(","),
+        extra
+    );
+
+    glb_container(json.into_bytes(), gltf.bin)
+}
+
+// Buffer views and accessors over a single binary buffer
+#[derive(Default)]
+struct GltfBuilder {
+    bin: Vec<u8>,
+    views: Vec<String>,
+    accessors: Vec<String>,
+}
+
+impl GltfBuilder {
+    fn view(&mut self, bytes: &[u8], target: Option<u32>) -> usize {
+        let offset = self.bin.len();
+        self.bin.extend_from_slice(bytes);
+        let target = target.map_or(String::new(), |t| format!(r#","target":{t}"#));
+        self.views.push(format!(
+            r#"{{"buffer":0,"byteOffset":{offset},"byteLength":{}{target}}}"#,
+            bytes.len()
+        ));
+        self.views.len() - 1
+    }
+
+    // Float accessor; positions and animation times must carry min/max
+    fn floats(&mut self, data: &[f32], kind: &str, target: Option<u32>, bounds: bool) -> usize {
+        let width = match kind {
+            "SCALAR" => 1,
+            "VEC2" => 2,
+            "VEC3" => 3,
+            _ => 4,
+        };
+        let bytes: Vec<u8> = data.iter().flat_map(|f| f.to_le_bytes()).collect();
+        let view = self.view(&bytes, target);
+
+        let mut accessor = format!(
+            r#"{{"bufferView":{view},"componentType":{GL_FLOAT},"count":{},"type":"{kind}""#,
+            data.len() / width
+        );
+        if bounds && !data.is_empty() {
+            let mut min = vec![f32::MAX; width];
+            let mut max = vec![f32::MIN; width];
+            for chunk in data.chunks_exact(width) {
+                for (k, &value) in chunk.iter().enumerate() {
+                    min[k] = min[k].min(value);
+                    max[k] = max[k].max(value);
+                }
+            }
+            let _ = write!(
+                accessor,
+                r#","min":{},"max":{}"#,
+                json_array(&min),
+                json_array(&max)
+            );
+        }
+        accessor.push('}');
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn indices(&mut self, indices: &[u32]) -> usize {
+        let bytes: Vec<u8> = indices.iter().flat_map(|i| i.to_le_bytes()).collect();
+        let view = self.view(&bytes, Some(ELEMENT_ARRAY_BUFFER));
+        self.accessors.push(format!(
+            r#"{{"bufferView":{view},"componentType":{GL_UNSIGNED_INT},"count":{},"type":"SCALAR"}}"#,
+            indices.len()
+        ));
+        self.accessors.len() - 1
+    }
+}
+
+fn json_array(values: &[f32]) -> String {
+    let items: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    format!("[{}]", items.join(","))
+}
+
+// 12-byte header, then the JSON and BIN chunks, each padded to 4 bytes
+fn glb_container(mut json: Vec<u8>, mut bin: Vec<u8>) -> Vec<u8> {
+    while !json.len().is_multiple_of(4) {
+        json.push(b' ');
+    }
+    while !bin.len().is_multiple_of(4) {
+        bin.push(0);
+    }
+    let total = 12 + 8 + json.len() + 8 + bin.len();
+
+    let mut out = Vec::with_capacity(total);
+    out.extend_from_slice(b"glTF");
+    out.extend_from_slice(&2u32.to_le_bytes());
+    out.extend_from_slice(&(total as u32).to_le_bytes());
+    out.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    out.extend_from_slice(b"JSON");
+    out.extend_from_slice(&json);
+    out.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+    out.extend_from_slice(b"BIN\0");
+    out.extend_from_slice(&bin);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Vertex;
+
+    // Two triangles sharing an edge: the unit square in the xy plane
+    fn square() -> Mesh {
+        let vertex = |x: f32, y: f32| Vertex {
+            pos: [x, y, 0.0],
+            color: [1.0, 0.5, 0.25, 1.0],
+            normal: [0.0, 0.0, 1.0],
+            uv: [x, y],
+            scalar: 0.0,
+        };
+        Mesh {
+            vertices: vec![
+                vertex(0.0, 0.0),
+                vertex(1.0, 0.0),
+                vertex(1.0, 1.0),
+                vertex(0.0, 1.0),
+            ],
+            triangles: vec![0, 1, 2, 0, 2, 3],
+            long_lines: vec![0, 1],
+            lat_lines: vec![1, 2, 2, 3],
+        }
+    }
+
+    #[test]
+    fn stl_binary_layout() {
+        let stl = write_stl_binary(&square());
+        assert_eq!(stl.len(), 80 + 4 + 2 * 50);
+        assert!(stl.starts_with(b"rust-anima tunnel"));
+        assert_eq!(u32::from_le_bytes(stl[80..84].try_into().unwrap()), 2);
+
+        // First facet: normal (0, 0, 1), then corners 0, 1, 2
+        let float = |at: usize| f32::from_le_bytes(stl[at..at + 4].try_into().unwrap());
+        let facet: Vec<f32> = (0..12).map(|k| float(84 + 4 * k)).collect();
+        assert_eq!(
+            facet,
+            [0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0]
+        );
+        assert_eq!(&stl[132..134], &[0, 0]);
+    }
+
+    #[test]
+    fn obj_faces_count_from_one() {
+        let obj = write_obj(&square());
+        let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+        assert_eq!((count("v "), count("vt "), count("vn ")), (4, 4, 4));
+        let faces: Vec<&str> = obj.lines().filter(|l| l.starts_with("f ")).collect();
+        assert_eq!(faces, ["f 1/1/1 2/2/2 3/3/3", "f 1/1/1 3/3/3 4/4/4"]);
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn three_mf_package() {
+        let package = write_3mf(&square());
+        assert_eq!(&package[0..4], &0x0403_4b50u32.to_le_bytes());
+
+        // End of central directory: three entries
+        let end = package.len() - 22;
+        assert_eq!(word(&package, end), 0x0605_4b50);
+        assert_eq!(
+            u16::from_le_bytes([package[end + 10], package[end + 11]]),
+            3
+        );
+        let directory_size = word(&package, end + 12) as usize;
+        let directory_offset = word(&package, end + 16) as usize;
+        assert_eq!(directory_offset + directory_size, end);
+
+        // Walk the local headers and check every stored file's CRC
+        let mut at = 0;
+        let mut names = Vec::new();
+        for _ in 0..3 {
+            assert_eq!(word(&package, at), 0x0403_4b50);
+            let crc = word(&package, at + 14);
+            let size = word(&package, at + 18) as usize;
+            let name_length = u16::from_le_bytes([package[at + 26], package[at + 27]]) as usize;
+            let name = std::str::from_utf8(&package[at + 30..at + 30 + name_length]).unwrap();
+            let data = &package[at + 30 + name_length..at + 30 + name_length + size];
+            assert_eq!(crc32(data), crc);
+            names.push(name.to_string());
+            if name == "3D/3dmodel.model" {
+                let model = std::str::from_utf8(data).unwrap();
+                assert_eq!(model.matches("<vertex ").count(), 4);
+                assert_eq!(model.matches("<triangle ").count(), 2);
+                assert!(model.contains(r#"<triangle v1="0" v2="2" v3="3"/>"#));
+            }
+            at += 30 + name_length + size;
+        }
+        assert_eq!(at, directory_offset);
+        assert_eq!(
+            names,
+            ["[Content_Types].xml", "_rels/.rels", "3D/3dmodel.model"]
+        );
+    }
+
+    fn word(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn glb_header_and_chunks() {
+        let pose = CameraPose {
+            eye: [0.0, 0.0, 5.0],
+            target: [0.0, 0.0, 0.0],
+            up: [0.0, 1.0, 0.0],
+        };
+        let track = CameraTrack::evenly_spaced(0.8, 1.5, 0.1, 100.0, vec![pose, pose], 2.0);
+        for camera in [None, Some(&track)] {
+            let glb = write_glb(&square(), camera);
+            assert_eq!(&glb[0..4], b"glTF");
+            assert_eq!(word(&glb, 4), 2);
+            assert_eq!(word(&glb, 8) as usize, glb.len());
+
+            let json_length = word(&glb, 12) as usize;
+            assert_eq!(&glb[16..20], b"JSON");
+            assert_eq!(json_length % 4, 0);
+            let json = std::str::from_utf8(&glb[20..20 + json_length]).unwrap();
+            assert!(json.starts_with('{') && json.trim_end().ends_with('}'));
+            assert_eq!(json.contains("fly-through"), camera.is_some());
+
+            let bin_at = 20 + json_length;
+            let bin_length = word(&glb, bin_at) as usize;
+            assert_eq!(&glb[bin_at + 4..bin_at + 8], b"BIN\0");
+            assert_eq!(bin_length % 4, 0);
+            assert_eq!(bin_at + 8 + bin_length, glb.len());
+        }
+    }
+
+    #[test]
+    fn glb_chunks_are_padded() {
+        let glb = glb_container(b"{}".to_vec(), vec![1, 2, 3, 4, 5]);
+        // JSON padded with spaces, BIN with zeros
+        assert_eq!(word(&glb, 12), 4);
+        assert_eq!(&glb[20..24], b"{}  ");
+        assert_eq!(word(&glb, 24), 8);
+        assert_eq!(&glb[32..40], &[1, 2, 3, 4, 5, 0, 0, 0]);
+        assert_eq!(glb.len(), 40);
+        assert_eq!(word(&glb, 8), 40);
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+/// How fog thickens with view-space depth
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FogMode {
+    /// No fog; the default, so the tunnel looks as it always has until
+    /// fog is asked for
+    #[default]
+    Off,
+    /// Clear up to `start`, solid from `end`
+    Linear,
+    /// Visibility exp(-density · depth)
+    Exponential,
+}
+
+/// Fog for the inside view. It mixes colour towards `color` and leaves
+/// opacity alone, since opaque walls keep occluding what is behind them.
+#[derive(Clone, Copy, Debug)]
+pub struct Fog {
+    pub mode: FogMode,
+    pub color: [f32; 3],
+    pub density: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Self {
+            mode: FogMode::default(),
+            // The clear colour
+            color: [0.02, 0.02, 0.05],
+            density: 0.06,
+            start: 5.0,
+            end: 40.0,
+        }
+    }
+}
+
+impl Fog {
+    /// This fog thinned to `amount` of its strength, 0 clear and 1 as set.
+    /// Scales depth in both modes, so linear fog pushes its range out by
+    /// the same factor that exponential fog drops its density.
+    pub fn scaled(&self, amount: f32) -> Fog {
+        if amount <= 0.0 {
+            return Fog {
+                mode: FogMode::Off,
+                ..*self
+            };
+        }
+        let amount = amount.min(1.0);
+        Fog {
+            density: self.density * amount,
+            start: self.start / amount,
+            end: self.end / amount,
+            ..*self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_fog_thins_towards_clear() {
+        let fog = Fog {
+            mode: FogMode::Exponential,
+            ..Fog::default()
+        };
+        assert_eq!(fog.scaled(0.0).mode, FogMode::Off);
+        assert_eq!(fog.scaled(1.0).density, fog.density);
+        let half = fog.scaled(0.5);
+        assert_eq!(half.mode, fog.mode);
+        assert!((half.density - 0.5 * fog.density).abs() < 1e-6);
+        assert!((half.start - 2.0 * fog.start).abs() < 1e-4);
+        assert!((half.end - 2.0 * fog.end).abs() < 1e-4);
+        // Never thicker than set
+        assert_eq!(fog.scaled(2.0).density, fog.density);
+    }
+}
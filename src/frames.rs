@@ -0,0 +1,192 @@
+use crate::curve::{frame_matrix, Frame, ParametricCurve};
+use crate::math::V3D;
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+// Samples per loop for the rotation-minimizing frame table
+const RMF_SAMPLES: usize = 2048;
+
+/// How the ring orientation is chosen along the curve
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FrameMode {
+    /// Normal points away from the origin (the curve's own `frame`)
+    #[default]
+    Radial,
+    /// Normal follows the curve's bending direction
+    Frenet,
+    /// Parallel transport, with the leftover twist spread over the loop
+    RotationMinimizing,
+}
+
+/// Frames for a curve in a given `FrameMode`.
+///
+/// Radial and Frenet frames are evaluated on demand. Rotation-minimizing
+/// frames depend on the whole path, so they are propagated once with the
+/// double-reflection method and interpolated afterwards.
+#[derive(Clone, Debug)]
+pub struct CurveFrames {
+    mode: FrameMode,
+    period: f64,
+    // Closed curves wrap t around the loop; open ones stop at the ends
+    closed: bool,
+    // RMF normals at `period * i / RMF_SAMPLES`, i = 0..=RMF_SAMPLES
+    normals: Vec<V3D>,
+}
+
+impl CurveFrames {
+    pub fn new<C: ParametricCurve + ?Sized>(curve: &C, mode: FrameMode) -> Self {
+        let period = curve.period();
+        let normals = match mode {
+            FrameMode::RotationMinimizing => rotation_minimizing_normals(curve, RMF_SAMPLES),
+            _ => Vec::new(),
+        };
+
+        Self {
+            mode,
+            period,
+            closed: curve.is_closed(),
+            normals,
+        }
+    }
+
+    pub fn mode(&self) -> FrameMode {
+        self.mode
+    }
+
+    pub fn frame<C: ParametricCurve + ?Sized>(&self, curve: &C, t: f64) -> Frame {
+        match self.mode {
+            FrameMode::Radial => curve.frame(t),
+            FrameMode::Frenet => frenet_frame(curve, t),
+            FrameMode::RotationMinimizing => {
+                let tangent = curve.tangent(t);
+                let fraction = if self.closed {
+                    (t / self.period).rem_euclid(1.0)
+                } else {
+                    (t / self.period).clamp(0.0, 1.0)
+                };
+                let x = fraction * RMF_SAMPLES as f64;
+                let i = (x.floor() as usize).min(RMF_SAMPLES - 1);
+                let f = x - i as f64;
+                let blended = self.normals[i] * (1.0 - f) + self.normals[i + 1] * f;
+                frame_from_normal(tangent, blended)
+            }
+        }
+    }
+
+    pub fn transform_matrix<C: ParametricCurve + ?Sized>(
+        &self,
+        curve: &C,
+        t: f64,
+    ) -> [[f64; 4]; 4] {
+        frame_matrix(&curve.position(t), &self.frame(curve, t))
+    }
+}
+
+// Complete a frame from a tangent and an approximate normal
+fn frame_from_normal(tangent: V3D, normal: V3D) -> Frame {
+    let normal = (normal - tangent * normal.dot(&tangent)).normalize();
+    Frame {
+        tangent,
+        normal,
+        binormal: tangent.cross(&normal),
+    }
+}
+
+/// Frenet frame: the normal is the part of r'' perpendicular to the
+/// tangent. Falls back to the curve's own frame on straight stretches,
+/// where the Frenet normal is undefined.
+pub fn frenet_frame<C: ParametricCurve + ?Sized>(curve: &C, t: f64) -> Frame {
+    let tangent = curve.tangent(t);
+    let acceleration = curve.acceleration(t);
+    let bend = acceleration - tangent * acceleration.dot(&tangent);
+
+    if bend.magnitude() < 1e-9 * acceleration.magnitude().max(1.0) {
+        return curve.frame(t);
+    }
+    frame_from_normal(tangent, bend)
+}
+
+// Double reflection (Wang et al. 2008), starting from the curve's own
+// frame at t = 0. On a closed curve the angle the propagated frame is off
+// by after one loop is removed gradually so the last ring lines up with
+// the first.
+fn rotation_minimizing_normals<C: ParametricCurve + ?Sized>(curve: &C, samples: usize) -> Vec<V3D> {
+    let period = curve.period();
+    let mut positions = Vec::with_capacity(samples + 1);
+    let mut tangents = Vec::with_capacity(samples + 1);
+    for i in 0..=samples {
+        let t = period * i as f64 / samples as f64;
+        positions.push(curve.position(t));
+        tangents.push(curve.tangent(t));
+    }
+
+    let mut normals = Vec::with_capacity(samples + 1);
+    normals.push(curve.frame(0.0).normal);
+
+    for i in 0..samples {
+        let r = normals[i];
+        let v1 = positions[i + 1] - positions[i];
+        let c1 = v1.dot(&v1);
+        if c1 < 1e-24 {
+            normals.push(r);
+            continue;
+        }
+        let r_l = r - v1 * (2.0 / c1 * v1.dot(&r));
+        let t_l = tangents[i] - v1 * (2.0 / c1 * v1.dot(&tangents[i]));
+
+        let v2 = tangents[i + 1] - t_l;
+        let c2 = v2.dot(&v2);
+        let next = if c2 < 1e-24 {
+            r_l
+        } else {
+            r_l - v2 * (2.0 / c2 * v2.dot(&r_l))
+        };
+        normals.push(frame_from_normal(tangents[i + 1], next).normal);
+    }
+
+    // Twist correction for the closed loop
+    if !curve.is_closed() {
+        return normals;
+    }
+    let axis = tangents[samples];
+    let end = normals[samples];
+    let start = frame_from_normal(axis, normals[0]).normal;
+    let twist = end.cross(&start).dot(&axis).atan2(end.dot(&start));
+
+    for (i, normal) in normals.iter_mut().enumerate() {
+        let fraction = i as f64 / samples as f64;
+        *normal = normal.rotate_about(&tangents[i], twist * fraction);
+    }
+
+    normals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lisa::Lissajou3D;
+
+    fn normal_jump<C: ParametricCurve>(curve: &C, frames: &CurveFrames, t: f64) -> f64 {
+        let h = 1e-6 * curve.period();
+        (frames.frame(curve, t).normal - frames.frame(curve, t - h).normal).magnitude()
+    }
+
+    #[test]
+    fn rmf_open_curve_has_no_jump_at_the_end() {
+        // Off the pole, so the ends are apart
+        let curve = Lissajou3D::new(1.0, std::f64::consts::SQRT_2, 10.0).with_phase(0.5, 0.0);
+        assert!(!curve.is_closed());
+        let frames = CurveFrames::new(&curve, FrameMode::RotationMinimizing);
+        assert!(normal_jump(&curve, &frames, curve.period()) < 1e-3);
+    }
+
+    #[test]
+    fn rmf_closed_curve_meets_itself() {
+        let curve = Lissajou3D::new(3.0, 2.0, 10.0);
+        let frames = CurveFrames::new(&curve, FrameMode::RotationMinimizing);
+        let start = frames.frame(&curve, 0.0).normal;
+        let end = frames.frame(&curve, curve.period() - 1e-9).normal;
+        assert!((start - end).magnitude() < 1e-3);
+    }
+}
@@ -0,0 +1,51 @@
+use web_sys::{WebGlProgram, WebGlRenderingContext as GL, WebGlShader};
+
+pub(crate) fn compile_shader(
+    gl: &GL,
+    shader_type: u32,
+    source: &str,
+) -> Result<WebGlShader, String> {
+    let shader = gl
+        .create_shader(shader_type)
+        .ok_or("unable to create shader")?;
+    gl.shader_source(&shader, source);
+    gl.compile_shader(&shader);
+    if gl
+        .get_shader_parameter(&shader, GL::COMPILE_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        Ok(shader)
+    } else {
+        Err(gl
+            .get_shader_info_log(&shader)
+            .unwrap_or_else(|| "Unknown error".into()))
+    }
+}
+
+pub(crate) fn link_program(
+    gl: &GL,
+    vert: &WebGlShader,
+    frag: &WebGlShader,
+) -> Result<WebGlProgram, String> {
+    let program = gl.create_program().ok_or("unable to create program")?;
+    gl.attach_shader(&program, vert);
+    gl.attach_shader(&program, frag);
+    gl.link_program(&program);
+    if gl
+        .get_program_parameter(&program, GL::LINK_STATUS)
+        .as_bool()
+        .unwrap_or(false)
+    {
+        Ok(program)
+    } else {
+        Err(gl
+            .get_program_info_log(&program)
+            .unwrap_or_else(|| "Unknown error".into()))
+    }
+}
+
+/// Attribute location, or `None` if the shader compiler dropped it as unused
+pub(crate) fn attrib_location(gl: &GL, program: &WebGlProgram, name: &str) -> Option<u32> {
+    u32::try_from(gl.get_attrib_location(program, name)).ok()
+}
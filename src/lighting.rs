@@ -0,0 +1,55 @@
+use crate::curve::ParametricCurve;
+
+/// Point lights the tunnel shader can take at once
+pub const MAX_POINT_LIGHTS: usize = 4;
+
+/// A light hanging on the curve at parameter `t`
+#[derive(Clone, Copy, Debug)]
+pub struct PointLight {
+    pub t: f64,
+    pub color: [f32; 3],
+}
+
+/// Blinn-Phong settings for the tunnel walls. Off by default, keeping the
+/// flat palette colours.
+#[derive(Clone, Debug)]
+pub struct Lighting {
+    pub enabled: bool,
+    pub ambient: f32,
+    pub diffuse: f32,
+    pub specular: f32,
+    pub shininess: f32,
+    /// Strength of the light attached to the camera
+    pub headlight: f32,
+    /// Glow where the walls turn edge-on to the camera
+    pub rim: f32,
+    pub point_lights: Vec<PointLight>,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ambient: 0.35,
+            diffuse: 0.65,
+            specular: 0.3,
+            shininess: 32.0,
+            headlight: 1.0,
+            rim: 0.25,
+            point_lights: Vec::new(),
+        }
+    }
+}
+
+impl Lighting {
+    /// World position of each point light, from `curve.position(t)`
+    pub fn light_positions<C: ParametricCurve + ?Sized>(&self, curve: &C) -> Vec<[f32; 3]> {
+        self.point_lights
+            .iter()
+            .map(|light| {
+                let p = curve.position(light.t);
+                [p.x as f32, p.y as f32, p.z as f32]
+            })
+            .collect()
+    }
+}
@@ -1,201 +1,260 @@
-use crate::math::V3D;
-use crate::polygon::Polygon3D;
+use crate::curve::{rational_approximation, ParametricCurve};
+use crate::math::{Jet, V3D};
+#[cfg(feature = "web")]
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen]
+// Largest denominator tried when looking for the a:b ratio
+const MAX_DENOMINATOR: u64 = 64;
+
+/// Spherical Lissajous curve
+///
+/// θ(t) = a·t + δθ, or θ(t) = c + A·sin(a·t + δθ) with a polar band
+/// φ(t) = b·t + δφ
+/// position = (rx sinθ cosφ, ry sinθ sinφ, rz cosθ)
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Debug)]
 pub struct Lissajou3D {
     a: f64,
     b: f64,
-    r: f64,
-}
-
-#[derive(Clone, Copy)]
-pub struct Vertex {
-    pub pos: [f32; 3],
-    pub color: [f32; 4],
-    // pub uv: [f32; 2],
+    phase_theta: f64,
+    phase_phi: f64,
+    // (centre, amplitude) of an oscillating polar angle
+    polar_band: Option<(f64, f64)>,
+    radii: V3D,
 }
 
-pub struct Mesh {
-    pub vertices: Vec<Vertex>,
-    pub triangles: Vec<u32>,
-    pub long_lines: Vec<u32>,
-    pub lat_lines: Vec<u32>,
-}
-
-#[wasm_bindgen]
+#[cfg_attr(feature = "web", wasm_bindgen)]
 impl Lissajou3D {
-    #[wasm_bindgen(constructor)]
-    pub fn new(a: f64, b: f64, r: f64) -> Self {
-        Self { a, b, r }
+    /// JS constructor. Everything after `r` is optional: phases for θ and φ,
+    /// a polar band (centre defaults to the equator, amplitude to π/2) and
+    /// an `[rx, ry, rz]` ellipsoid that replaces `r`.
+    #[cfg_attr(feature = "web", wasm_bindgen(constructor))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_options(
+        a: f64,
+        b: f64,
+        r: f64,
+        phase_theta: Option<f64>,
+        phase_phi: Option<f64>,
+        polar_center: Option<f64>,
+        polar_amplitude: Option<f64>,
+        radii: Option<Vec<f64>>,
+    ) -> Self {
+        let mut lisa =
+            Self::new(a, b, r).with_phase(phase_theta.unwrap_or(0.0), phase_phi.unwrap_or(0.0));
+        if polar_center.is_some() || polar_amplitude.is_some() {
+            let half_pi = std::f64::consts::FRAC_PI_2;
+            lisa = lisa.with_polar_band(
+                polar_center.unwrap_or(half_pi),
+                polar_amplitude.unwrap_or(half_pi),
+            );
+        }
+        if let Some(&[rx, ry, rz]) = radii.as_deref() {
+            lisa = lisa.with_radii(rx, ry, rz);
+        }
+        lisa
     }
 
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
     pub fn a(&self) -> f64 {
         self.a
     }
-    #[wasm_bindgen(getter)]
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
     pub fn b(&self) -> f64 {
         self.b
     }
-    #[wasm_bindgen(getter)]
+    /// Largest of the three radii
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
     pub fn r(&self) -> f64 {
-        self.r
+        self.radii.x.max(self.radii.y).max(self.radii.z)
+    }
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn phase_theta(&self) -> f64 {
+        self.phase_theta
+    }
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn phase_phi(&self) -> f64 {
+        self.phase_phi
+    }
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn radii(&self) -> Vec<f64> {
+        vec![self.radii.x, self.radii.y, self.radii.z]
+    }
+
+    /// Parametric speed |r'(t)|
+    pub fn speed(&self, t: f64) -> f64 {
+        ParametricCurve::speed(self, t)
+    }
+
+    /// Curvature κ(t)
+    pub fn curvature(&self, t: f64) -> f64 {
+        ParametricCurve::curvature(self, t)
+    }
+
+    /// Torsion τ(t)
+    pub fn torsion(&self, t: f64) -> f64 {
+        ParametricCurve::torsion(self, t)
+    }
+
+    /// Parameter span of one loop (see `is_closed`)
+    pub fn period(&self) -> f64 {
+        ParametricCurve::period(self)
+    }
+
+    /// Whether the curve returns to its start after `period`. False when
+    /// a:b has no rational approximation with a small denominator.
+    pub fn is_closed(&self) -> bool {
+        ParametricCurve::is_closed(self)
+    }
+
+    /// Distance between the start and the end of one period. Only the
+    /// positions are compared: an open curve can come back through its
+    /// starting point heading elsewhere, typically at a pole, and still
+    /// report a gap of about 0. Use `is_closed`, which also compares the
+    /// tangents, to decide whether the tunnel joins up.
+    pub fn closure_gap(&self) -> f64 {
+        let period = ParametricCurve::period(self);
+        (self.position(period) - self.position(0.0)).magnitude()
+    }
+
+    /// Unnormalized derivative of the given order (0 = position, up to 3)
+    /// as `[x, y, z]`
+    pub fn derivative(&self, t: f64, order: usize) -> Option<Vec<f64>> {
+        let d = self.derivatives(t).get(order).copied()?;
+        Some(vec![d.x, d.y, d.z])
     }
 }
 
 impl Lissajou3D {
-    // Lissajous position
-    pub fn position(&self, t: f64) -> V3D {
-        let at = self.a * t;
-        let bt = self.b * t;
-        self.r * V3D::new(at.sin() * bt.cos(), at.sin() * bt.sin(), at.cos())
+    pub fn new(a: f64, b: f64, r: f64) -> Self {
+        Self {
+            a,
+            b,
+            phase_theta: 0.0,
+            phase_phi: 0.0,
+            polar_band: None,
+            radii: V3D::new(r, r, r),
+        }
+    }
+
+    /// Phase offsets δθ and δφ
+    pub fn with_phase(mut self, phase_theta: f64, phase_phi: f64) -> Self {
+        self.phase_theta = phase_theta;
+        self.phase_phi = phase_phi;
+        self
+    }
+
+    /// Let θ oscillate as `center + amplitude * sin(a*t + δθ)` instead of
+    /// winding all the way round
+    pub fn with_polar_band(mut self, center: f64, amplitude: f64) -> Self {
+        self.polar_band = Some((center, amplitude));
+        self
+    }
+
+    /// Ellipsoid semi-axes along x, y and z
+    pub fn with_radii(mut self, rx: f64, ry: f64, rz: f64) -> Self {
+        self.radii = V3D::new(rx, ry, rz);
+        self
+    }
+
+    pub fn polar_band(&self) -> Option<(f64, f64)> {
+        self.polar_band
+    }
+
+    // θ(t) and φ(t) with their derivatives
+    fn angles(&self, t: f64) -> (Jet, Jet) {
+        let wound = Jet::linear(self.a * t + self.phase_theta, self.a);
+        let theta = match self.polar_band {
+            Some((center, amplitude)) => Jet::constant(center) + wound.sin() * amplitude,
+            None => wound,
+        };
+        let phi = Jet::linear(self.b * t + self.phase_phi, self.b);
+        (theta, phi)
     }
 
     // Tangent (normalized)
     pub fn d1(&self, t: f64) -> V3D {
-        let a = self.a;
-        let b = self.b;
-        let at = a * t;
-        let bt = b * t;
-
-        V3D::new(
-            a * at.cos() * bt.cos() - b * at.sin() * bt.sin(),
-            a * at.cos() * bt.sin() + b * at.sin() * bt.cos(),
-            -a * at.sin(),
-        )
-        .normalize()
+        self.tangent(t)
     }
 
     // Normal (radial frame - points outward from origin)
     // This creates a twist-free frame suitable for visualization
     pub fn d2(&self, t: f64) -> V3D {
-        let p = self.position(t);
-        let d1 = self.d1(t);
-
-        // Use radial direction from origin, make it orthogonal to tangent
-        let radial = p.normalize();
-        let tangent = d1;
-
-        // Project radial onto plane perpendicular to tangent
-        let dot = radial.dot(&tangent);
-        let projection = V3D::new(
-            radial.x - dot * tangent.x,
-            radial.y - dot * tangent.y,
-            radial.z - dot * tangent.z,
-        );
-
-        projection.normalize()
+        self.frame(t).normal
     }
 
     // Binormal
     pub fn d3(&self, t: f64) -> V3D {
-        let d1 = self.d1(t);
-        let d2 = self.d2(t);
-        d1.cross(&d2)
+        self.frame(t).binormal
     }
 
-    // Transform matrix from trihedron
-    pub fn transform_matrix(&self, t: f64) -> [[f64; 4]; 4] {
-        let pos = self.position(t);
-        let d1 = self.d1(t);
-        let d2 = self.d2(t);
-        let d3 = self.d3(t);
+    /// Exact r(t), r'(t), r''(t) and r'''(t)
+    pub fn derivatives(&self, t: f64) -> [V3D; 4] {
+        let (theta, phi) = self.angles(t);
+        let x = theta.sin() * phi.cos() * self.radii.x;
+        let y = theta.sin() * phi.sin() * self.radii.y;
+        let z = theta.cos() * self.radii.z;
 
         [
-            [d2.x, d3.x, d1.x, pos.x],
-            [d2.y, d3.y, d1.y, pos.y],
-            [d2.z, d3.z, d1.z, pos.z],
-            [0.0, 0.0, 0.0, 1.0],
+            V3D::new(x.v, y.v, z.v),
+            V3D::new(x.d1, y.d1, z.d1),
+            V3D::new(x.d2, y.d2, z.d2),
+            V3D::new(x.d3, y.d3, z.d3),
         ]
     }
+}
 
-    pub fn generate_tunnel_mesh(
-        &self,
-        polygon_radius: f64,
-        polygon_sides: usize,
-        num_polygons: usize,
-    ) -> Mesh {
-        let polygon = Polygon3D::new(polygon_radius, polygon_sides);
-        let rings = num_polygons + 1;
-        let mut all_polygons = Vec::new();
-
-        for i in 0..rings {
-            let t = 2.0 * std::f64::consts::PI * (i as f64) / (num_polygons as f64);
-            let matrix = self.transform_matrix(t);
-            all_polygons.push(polygon.transform(&matrix));
-        }
-
-        // Create vertices for all rings including the closing ring
-        let mut vertices = Vec::with_capacity(rings * polygon_sides);
-
-        // Pre-compute the first ring's color to reuse for the last ring
-        let first_rgb = hsv_to_rgb(0.0, 1.0, 1.0);
-
-        for i in 0..rings {
-            // Generate color based on position along curve
-            // The last ring must use the exact same color as the first ring
-            let rgb = if i == num_polygons {
-                first_rgb // Reuse first color for perfect loop closure
-            } else {
-                let delta = (i as f32 / num_polygons as f32) * 2.0 * std::f64::consts::PI as f32;
-                (
-                    0.5 + 0.5 * delta.sin(),
-                    0.35 + 0.35 * (3.0 * delta).cos(),
-                    0.75 + 0.25 * (4.0 * delta).sin(),
-                )
-            };
-
-            for j in 0..polygon_sides {
-                let p = &all_polygons[i][j];
-                vertices.push(Vertex {
-                    pos: [p.x as f32, p.y as f32, p.z as f32],
-                    color: [rgb.0, rgb.1, rgb.2, 0.5], // More opaque, less washed out
-                });
+impl ParametricCurve for Lissajou3D {
+    // θ and φ must both complete whole turns: a·T = 2πp and b·T = 2πq with
+    // p/q = a/b in lowest terms
+    fn period(&self) -> f64 {
+        let tau = 2.0 * std::f64::consts::PI;
+        let (a, b) = (self.a.abs(), self.b.abs());
+        match (a > 0.0, b > 0.0) {
+            (false, false) => tau,
+            (true, false) => tau / a,
+            (false, true) => tau / b,
+            (true, true) => {
+                let (p, _) = rational_approximation(a / b, MAX_DENOMINATOR);
+                tau * p.max(1) as f64 / a
             }
         }
+    }
 
-        // Triangles - connect each ring to the next (last ring connects to ring 0)
-        let mut triangles = Vec::new();
-        for i in 0..num_polygons {
-            for j in 0..polygon_sides {
-                let next_side = (j + 1) % polygon_sides;
-
-                let a = (i * polygon_sides + j) as u32;
-                let b = ((i + 1) * polygon_sides + j) as u32;
-                let c = (i * polygon_sides + next_side) as u32;
-                let d = ((i + 1) * polygon_sides + next_side) as u32;
-                triangles.extend_from_slice(&[a, b, c, b, d, c]);
-            }
-        }
+    fn is_closed(&self) -> bool {
+        let tolerance = 1e-6 * self.r().max(1.0);
+        let period = self.period();
+        let gap = (self.position(period) - self.position(0.0)).magnitude();
+        let turn = (self.tangent(period) - self.tangent(0.0)).magnitude();
+        gap <= tolerance && turn <= 1e-6
+    }
 
-        // Longitude lines - along the curve
-        let mut long_lines = Vec::new();
-        for j in 0..polygon_sides {
-            for i in 0..num_polygons {
-                let a = (i * polygon_sides + j) as u32;
-                let b = ((i + 1) * polygon_sides + j) as u32;
-                long_lines.extend_from_slice(&[a, b]);
-            }
-        }
+    // Lissajous position
+    fn position(&self, t: f64) -> V3D {
+        let (theta, phi) = self.angles(t);
+        let (at, bt) = (theta.v, phi.v);
+        let r = self.radii;
+        V3D::new(
+            r.x * at.sin() * bt.cos(),
+            r.y * at.sin() * bt.sin(),
+            r.z * at.cos(),
+        )
+    }
 
-        // Latitude lines - around each ring
-        let mut lat_lines = Vec::new();
-        for i in 0..rings {
-            for j in 0..polygon_sides {
-                let next_side = (j + 1) % polygon_sides;
-                let a = (i * polygon_sides + j) as u32;
-                let b = (i * polygon_sides + next_side) as u32;
-                lat_lines.extend_from_slice(&[a, b]);
-            }
-        }
+    fn tangent(&self, t: f64) -> V3D {
+        self.velocity(t).normalize()
+    }
 
-        Mesh {
-            vertices,
-            triangles,
-            long_lines,
-            lat_lines,
-        }
+    fn velocity(&self, t: f64) -> V3D {
+        self.derivatives(t)[1]
+    }
+
+    fn acceleration(&self, t: f64) -> V3D {
+        self.derivatives(t)[2]
+    }
+
+    fn jerk(&self, t: f64) -> V3D {
+        self.derivatives(t)[3]
     }
 }
 
@@ -210,3 +269,75 @@ impl std::ops::Mul<V3D> for f64 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curves() -> Vec<Lissajou3D> {
+        vec![
+            Lissajou3D::new(3.0, 2.0, 10.0),
+            Lissajou3D::new(5.0, 4.0, 3.0).with_phase(0.3, 1.1),
+            Lissajou3D::new(2.0, 7.0, 5.0).with_polar_band(1.2, 0.6),
+            Lissajou3D::new(1.0, 3.0, 1.0).with_radii(2.0, 1.0, 0.5),
+        ]
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        let h = 1e-5;
+        for curve in curves() {
+            for i in 0..20 {
+                let t = 0.37 * i as f64 + 0.05;
+                let (before, after) = (curve.derivatives(t - h), curve.derivatives(t + h));
+                let exact = curve.derivatives(t);
+                assert!((exact[0] - curve.position(t)).magnitude() < 1e-12);
+                for order in 0..3 {
+                    let numeric = (after[order] - before[order]) * (0.5 / h);
+                    let error = (numeric - exact[order + 1]).magnitude();
+                    let scale = exact[order + 1].magnitude().max(1.0);
+                    assert!(
+                        error < 1e-5 * scale,
+                        "{:?} order {} at t = {}: {}",
+                        curve,
+                        order + 1,
+                        t,
+                        error
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn period_completes_both_angles() {
+        let tau = 2.0 * std::f64::consts::PI;
+        assert!((Lissajou3D::new(3.0, 2.0, 1.0).period() - tau).abs() < 1e-12);
+        // 1.5 : 1 is 3 : 2, three turns of θ at 1.5 per unit t
+        assert!((Lissajou3D::new(1.5, 1.0, 1.0).period() - 2.0 * tau).abs() < 1e-12);
+        assert!((Lissajou3D::new(0.0, 4.0, 1.0).period() - tau / 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn irrational_ratios_stay_open() {
+        let sqrt2 = std::f64::consts::SQRT_2;
+        let curve = Lissajou3D::new(1.0, sqrt2, 10.0).with_phase(0.5, 0.0);
+        assert!(!curve.is_closed());
+        assert!(curve.closure_gap() > 1e-3);
+        // Starting at a pole the end comes back to the same point, but
+        // along a different tangent
+        let polar = Lissajou3D::new(1.0, sqrt2, 10.0);
+        assert!(polar.closure_gap() < 1e-9);
+        assert!(!polar.is_closed());
+    }
+
+    #[test]
+    fn integer_ratios_close() {
+        let scaled = [(1.5, 1.0), (0.5, 1.5), (5.0, 3.0)]
+            .map(|(a, b)| Lissajou3D::new(a, b, 4.0).with_phase(0.2, 0.7));
+        for curve in curves().into_iter().chain(scaled) {
+            assert!(curve.is_closed(), "{:?}", curve);
+            assert!(curve.closure_gap() < 1e-9);
+        }
+    }
+}
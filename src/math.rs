@@ -50,4 +50,402 @@ impl V3D {
 
         V3D::new(x, y, z)
     }
+
+    /// Rotate around a unit `axis` by `angle` radians (Rodrigues)
+    pub fn rotate_about(&self, axis: &V3D, angle: f64) -> V3D {
+        let (sin, cos) = angle.sin_cos();
+        *self * cos + axis.cross(self) * sin + *axis * (axis.dot(self) * (1.0 - cos))
+    }
+}
+
+impl std::ops::Add for V3D {
+    type Output = V3D;
+
+    fn add(self, rhs: V3D) -> V3D {
+        V3D::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl std::ops::Sub for V3D {
+    type Output = V3D;
+
+    fn sub(self, rhs: V3D) -> V3D {
+        V3D::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl std::ops::Mul<f64> for V3D {
+    type Output = V3D;
+
+    fn mul(self, rhs: f64) -> V3D {
+        V3D::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl std::ops::Neg for V3D {
+    type Output = V3D;
+
+    fn neg(self) -> V3D {
+        V3D::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// Unit quaternion `(x, y, z, w)` describing an orientation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl Quat {
+    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Rotation taking the coordinate axes onto the orthonormal, right-handed
+    /// `x_axis`, `y_axis` and `z_axis` (Shepperd's method)
+    pub fn from_axes(x_axis: &V3D, y_axis: &V3D, z_axis: &V3D) -> Quat {
+        let (m00, m11, m22) = (x_axis.x, y_axis.y, z_axis.z);
+        let trace = m00 + m11 + m22;
+
+        let q = if trace > 0.0 {
+            let s = 2.0 * (trace + 1.0).sqrt();
+            Quat::new(
+                (y_axis.z - z_axis.y) / s,
+                (z_axis.x - x_axis.z) / s,
+                (x_axis.y - y_axis.x) / s,
+                0.25 * s,
+            )
+        } else if m00 > m11 && m00 > m22 {
+            let s = 2.0 * (1.0 + m00 - m11 - m22).sqrt();
+            Quat::new(
+                0.25 * s,
+                (y_axis.x + x_axis.y) / s,
+                (z_axis.x + x_axis.z) / s,
+                (y_axis.z - z_axis.y) / s,
+            )
+        } else if m11 > m22 {
+            let s = 2.0 * (1.0 + m11 - m00 - m22).sqrt();
+            Quat::new(
+                (y_axis.x + x_axis.y) / s,
+                0.25 * s,
+                (z_axis.y + y_axis.z) / s,
+                (z_axis.x - x_axis.z) / s,
+            )
+        } else {
+            let s = 2.0 * (1.0 + m22 - m00 - m11).sqrt();
+            Quat::new(
+                (z_axis.x + x_axis.z) / s,
+                (z_axis.y + y_axis.z) / s,
+                0.25 * s,
+                (x_axis.y - y_axis.x) / s,
+            )
+        };
+        q.normalize()
+    }
+
+    pub fn dot(&self, other: &Quat) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn normalize(&self) -> Quat {
+        let len = self.dot(self).sqrt();
+        if len > 0.0 {
+            Quat::new(self.x / len, self.y / len, self.z / len, self.w / len)
+        } else {
+            *self
+        }
+    }
+
+    /// `v` rotated by this quaternion
+    pub fn rotate(&self, v: &V3D) -> V3D {
+        // v + 2w(q × v) + 2q × (q × v)
+        let q = V3D::new(self.x, self.y, self.z);
+        let t = q.cross(v) * 2.0;
+        *v + t * self.w + q.cross(&t)
+    }
+
+    /// Spherical interpolation from this orientation to `other` at constant
+    /// angular speed, along the shorter arc
+    pub fn slerp(&self, other: &Quat, f: f64) -> Quat {
+        let mut cos = self.dot(other);
+        let other = if cos < 0.0 {
+            cos = -cos;
+            -*other
+        } else {
+            *other
+        };
+        let (a, b) = if cos > 0.9995 {
+            // Nearly parallel: the arc is a line, and sin would vanish
+            (1.0 - f, f)
+        } else {
+            let angle = cos.acos();
+            let sin = angle.sin();
+            (((1.0 - f) * angle).sin() / sin, (f * angle).sin() / sin)
+        };
+        Quat::new(
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z,
+            a * self.w + b * other.w,
+        )
+        .normalize()
+    }
+}
+
+impl std::ops::Neg for Quat {
+    type Output = Quat;
+
+    fn neg(self) -> Quat {
+        Quat::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+/// A scalar function of t with its first three derivatives, for exact
+/// differentiation by the chain and product rules
+#[derive(Clone, Copy, Debug)]
+pub struct Jet {
+    pub v: f64,
+    pub d1: f64,
+    pub d2: f64,
+    pub d3: f64,
+}
+
+impl Jet {
+    pub fn constant(v: f64) -> Self {
+        Self {
+            v,
+            d1: 0.0,
+            d2: 0.0,
+            d3: 0.0,
+        }
+    }
+
+    /// `v + slope * (t - t0)` evaluated at t0
+    pub fn linear(v: f64, slope: f64) -> Self {
+        Self {
+            v,
+            d1: slope,
+            d2: 0.0,
+            d3: 0.0,
+        }
+    }
+
+    pub fn sin(self) -> Self {
+        let (s, c) = self.v.sin_cos();
+        let (u1, u2, u3) = (self.d1, self.d2, self.d3);
+        Self {
+            v: s,
+            d1: c * u1,
+            d2: -s * u1 * u1 + c * u2,
+            d3: -c * u1 * u1 * u1 - 3.0 * s * u1 * u2 + c * u3,
+        }
+    }
+
+    pub fn cos(self) -> Self {
+        let (s, c) = self.v.sin_cos();
+        let (u1, u2, u3) = (self.d1, self.d2, self.d3);
+        Self {
+            v: c,
+            d1: -s * u1,
+            d2: -c * u1 * u1 - s * u2,
+            d3: s * u1 * u1 * u1 - 3.0 * c * u1 * u2 - s * u3,
+        }
+    }
+}
+
+impl std::ops::Add for Jet {
+    type Output = Jet;
+
+    fn add(self, rhs: Jet) -> Jet {
+        Jet {
+            v: self.v + rhs.v,
+            d1: self.d1 + rhs.d1,
+            d2: self.d2 + rhs.d2,
+            d3: self.d3 + rhs.d3,
+        }
+    }
+}
+
+impl std::ops::Mul for Jet {
+    type Output = Jet;
+
+    fn mul(self, g: Jet) -> Jet {
+        let f = self;
+        Jet {
+            v: f.v * g.v,
+            d1: f.d1 * g.v + f.v * g.d1,
+            d2: f.d2 * g.v + 2.0 * f.d1 * g.d1 + f.v * g.d2,
+            d3: f.d3 * g.v + 3.0 * f.d2 * g.d1 + 3.0 * f.d1 * g.d2 + f.v * g.d3,
+        }
+    }
+}
+
+impl std::ops::Mul<f64> for Jet {
+    type Output = Jet;
+
+    fn mul(self, k: f64) -> Jet {
+        Jet {
+            v: self.v * k,
+            d1: self.d1 * k,
+            d2: self.d2 * k,
+            d3: self.d3 * k,
+        }
+    }
+}
+
+// Simple matrix struct
+pub struct Mat4 {
+    data: [f32; 16],
+}
+
+impl Mat4 {
+    pub fn perspective(fov: f32, aspect: f32, near: f32, far: f32) -> Self {
+        let f = 1.0 / (fov / 2.0).tan();
+        let nf = 1.0 / (near - far);
+
+        Mat4 {
+            data: [
+                f / aspect,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                f,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                (far + near) * nf,
+                -1.0,
+                0.0,
+                0.0,
+                2.0 * far * near * nf,
+                0.0,
+            ],
+        }
+    }
+
+    pub fn look_at(eye: [f32; 3], center: [f32; 3], up: [f32; 3]) -> Self {
+        let f = [center[0] - eye[0], center[1] - eye[1], center[2] - eye[2]];
+        let f_len = (f[0] * f[0] + f[1] * f[1] + f[2] * f[2]).sqrt();
+        let f = [f[0] / f_len, f[1] / f_len, f[2] / f_len];
+
+        let up_len = (up[0] * up[0] + up[1] * up[1] + up[2] * up[2]).sqrt();
+        let up = [up[0] / up_len, up[1] / up_len, up[2] / up_len];
+
+        let s = [
+            f[1] * up[2] - f[2] * up[1],
+            f[2] * up[0] - f[0] * up[2],
+            f[0] * up[1] - f[1] * up[0],
+        ];
+        let s_len = (s[0] * s[0] + s[1] * s[1] + s[2] * s[2]).sqrt();
+        let s = [s[0] / s_len, s[1] / s_len, s[2] / s_len];
+
+        let u = [
+            s[1] * f[2] - s[2] * f[1],
+            s[2] * f[0] - s[0] * f[2],
+            s[0] * f[1] - s[1] * f[0],
+        ];
+
+        Mat4 {
+            data: [
+                s[0],
+                u[0],
+                -f[0],
+                0.0,
+                s[1],
+                u[1],
+                -f[1],
+                0.0,
+                s[2],
+                u[2],
+                -f[2],
+                0.0,
+                -(s[0] * eye[0] + s[1] * eye[1] + s[2] * eye[2]),
+                -(u[0] * eye[0] + u[1] * eye[1] + u[2] * eye[2]),
+                f[0] * eye[0] + f[1] * eye[1] + f[2] * eye[2],
+                1.0,
+            ],
+        }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: &V3D, b: &V3D) {
+        assert!((*a - *b).magnitude() < 1e-9, "{:?} vs {:?}", a, b);
+    }
+
+    // Coordinate axes turned by `angle` about `axis`
+    fn rotated_axes(axis: &V3D, angle: f64) -> [V3D; 3] {
+        [
+            V3D::new(1.0, 0.0, 0.0),
+            V3D::new(0.0, 1.0, 0.0),
+            V3D::new(0.0, 0.0, 1.0),
+        ]
+        .map(|e| e.rotate_about(axis, angle))
+    }
+
+    #[test]
+    fn from_axes_reproduces_the_axes() {
+        let pi = std::f64::consts::PI;
+        let cases = [
+            // Positive trace
+            (V3D::new(0.3, -0.5, 0.8), 0.7),
+            // Half turns about each axis take the three negative-trace
+            // branches in turn
+            (V3D::new(1.0, 0.0, 0.0), pi),
+            (V3D::new(0.0, 1.0, 0.0), pi),
+            (V3D::new(0.0, 0.0, 1.0), pi),
+            // Large turns about skewed axes
+            (V3D::new(1.0, 0.2, 0.1), 2.8),
+            (V3D::new(0.1, 1.0, 0.3), 2.9),
+            (V3D::new(-0.2, 0.1, 1.0), 3.0),
+        ];
+        for (axis, angle) in cases {
+            let axis = axis.normalize();
+            let [x, y, z] = rotated_axes(&axis, angle);
+            let q = Quat::from_axes(&x, &y, &z);
+            assert!((q.dot(&q) - 1.0).abs() < 1e-12);
+            assert_near(&q.rotate(&V3D::new(1.0, 0.0, 0.0)), &x);
+            assert_near(&q.rotate(&V3D::new(0.0, 1.0, 0.0)), &y);
+            assert_near(&q.rotate(&V3D::new(0.0, 0.0, 1.0)), &z);
+        }
+    }
+
+    #[test]
+    fn slerp_hits_the_endpoints() {
+        let [x, y, z] = rotated_axes(&V3D::new(0.0, 0.6, 0.8), 1.2);
+        let a = Quat::new(0.0, 0.0, 0.0, 1.0);
+        let b = Quat::from_axes(&x, &y, &z);
+        let p = V3D::new(0.4, -1.0, 2.0);
+        assert_near(&a.slerp(&b, 0.0).rotate(&p), &p);
+        assert_near(&a.slerp(&b, 1.0).rotate(&p), &b.rotate(&p));
+        // Constant speed: halfway is half the angle
+        let half = rotated_axes(&V3D::new(0.0, 0.6, 0.8), 0.6);
+        assert_near(&a.slerp(&b, 0.5).rotate(&V3D::new(1.0, 0.0, 0.0)), &half[0]);
+    }
+
+    #[test]
+    fn slerp_takes_the_shorter_arc() {
+        let axis = V3D::new(0.0, 0.0, 1.0);
+        let a = Quat::new(0.0, 0.0, 0.0, 1.0);
+        let [x, y, z] = rotated_axes(&axis, std::f64::consts::FRAC_PI_2);
+        // The same orientation with the opposite sign, so the dot is negative
+        let b = -Quat::from_axes(&x, &y, &z);
+        assert!(a.dot(&b) < 0.0);
+        let e = V3D::new(1.0, 0.0, 0.0);
+        let quarter = e.rotate_about(&axis, std::f64::consts::FRAC_PI_4);
+        assert_near(&a.slerp(&b, 0.5).rotate(&e), &quarter);
+        assert_near(&a.slerp(&b, 1.0).rotate(&e), &x);
+    }
 }
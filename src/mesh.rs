@@ -0,0 +1,403 @@
+use crate::arclength::{ArcLengthTable, Parameterization};
+use crate::color::Palette;
+use crate::curve::ParametricCurve;
+use crate::frames::{CurveFrames, FrameMode};
+use crate::metric::{ring_metric, ColorMetric};
+use crate::polygon::Polygon3D;
+use crate::profile::Profile;
+use crate::track::Track;
+
+#[derive(Clone, Copy)]
+pub struct Vertex {
+    pub pos: [f32; 3],
+    pub color: [f32; 4],
+    /// Unit normal pointing away from the curve
+    pub normal: [f32; 3],
+    /// u around the profile, v along the curve
+    pub uv: [f32; 2],
+    /// Palette position of the ring, from 0 at the start to 1 at the end of
+    /// the tunnel, for colouring on the GPU
+    pub scalar: f32,
+}
+
+pub struct Mesh {
+    pub vertices: Vec<Vertex>,
+    pub triangles: Vec<u32>,
+    pub long_lines: Vec<u32>,
+    pub lat_lines: Vec<u32>,
+}
+
+impl Mesh {
+    pub fn empty() -> Self {
+        Self {
+            vertices: Vec::new(),
+            triangles: Vec::new(),
+            long_lines: Vec::new(),
+            lat_lines: Vec::new(),
+        }
+    }
+
+    /// Reverse the winding of every triangle. The tunnel winds its faces
+    /// towards the inside, where the camera flies, while its normals point
+    /// out; flipping makes the two agree for other tools.
+    pub fn flip_winding(&mut self) {
+        for triangle in self.triangles.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
+}
+
+/// Shape and sampling of a swept tunnel
+#[derive(Clone, Debug)]
+pub struct TunnelOptions {
+    pub profile: Profile,
+    pub num_polygons: usize,
+    pub frame_mode: FrameMode,
+    pub ring_spacing: Parameterization,
+    /// Uniform scale of the profile at each ring
+    pub radius_scale: Track,
+    /// Rotation of the profile around the tangent, in radians
+    pub twist: Track,
+    /// Extra scale along the frame normal and binormal
+    pub scale_x: Track,
+    pub scale_y: Track,
+    /// What the palette position of each ring follows
+    pub color_by: ColorMetric,
+}
+
+impl TunnelOptions {
+    /// A regular `polygon_sides`-gon swept through `num_polygons` rings
+    pub fn new(
+        polygon_radius: f64,
+        polygon_sides: usize,
+        num_polygons: usize,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            profile: Profile::regular(polygon_radius, polygon_sides)?,
+            num_polygons,
+            frame_mode: FrameMode::default(),
+            ring_spacing: Parameterization::default(),
+            radius_scale: Track::Constant(1.0),
+            twist: Track::Constant(0.0),
+            scale_x: Track::Constant(1.0),
+            scale_y: Track::Constant(1.0),
+            color_by: ColorMetric::default(),
+        })
+    }
+
+    /// Farthest extent of the ring at `t` from the curve
+    pub fn radius_at(&self, t: f64) -> f64 {
+        let scale = self.radius_scale.at(t).abs();
+        self.profile.radius() * scale * self.scale_x.at(t).abs().max(self.scale_y.at(t).abs())
+    }
+}
+
+/// Curve parameter of each ring, `num_polygons + 1` of them over one period
+pub(crate) fn ring_parameters<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    options: &TunnelOptions,
+    arc: &ArcLengthTable,
+) -> Vec<f64> {
+    let num_polygons = options.num_polygons;
+    let period = curve.period();
+    (0..=num_polygons)
+        .map(|i| {
+            let fraction = (i as f64) / (num_polygons as f64);
+            match options.ring_spacing {
+                Parameterization::ArcLength => arc.parameter(arc.length() * fraction),
+                Parameterization::Parameter => period * fraction,
+            }
+        })
+        .collect()
+}
+
+// Scale the profile in its own plane, rotate it by `twist`, then place it
+// with the frame matrix
+pub(crate) fn ring_matrix(
+    frame: [[f64; 4]; 4],
+    scale_x: f64,
+    scale_y: f64,
+    twist: f64,
+) -> [[f64; 4]; 4] {
+    let (sin, cos) = twist.sin_cos();
+    let mut m = frame;
+    for row in m.iter_mut().take(3) {
+        let (x, y) = (row[0], row[1]);
+        row[0] = scale_x * (cos * x + sin * y);
+        row[1] = scale_y * (cos * y - sin * x);
+    }
+    m
+}
+
+/// Sweep the cross-section profile along `curve`, one ring per step over one period.
+/// Closed curves end on a ring that coincides with ring 0; open curves get
+/// a flat cap at each end. Rings are coloured by `palette` at the
+/// `options.color_by` metric, by default `i / num_polygons`.
+///
+/// A sweep with no rings to join or a profile of fewer than 3 points has
+/// no surface, and gives an empty mesh.
+pub fn generate_tunnel_mesh<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    options: &TunnelOptions,
+    palette: &Palette,
+) -> Mesh {
+    let polygon = Polygon3D::from_profile(&options.profile);
+    let polygon_sides = polygon.sides();
+    let num_polygons = options.num_polygons;
+    if num_polygons == 0 || polygon_sides < 3 {
+        return Mesh::empty();
+    }
+    let frames = CurveFrames::new(curve, options.frame_mode);
+    let rings = num_polygons + 1;
+    let closed = curve.is_closed();
+    let arc = ArcLengthTable::new(curve);
+    let ring_params = ring_parameters(curve, options, &arc);
+    let ring_values = ring_metric(curve, options.color_by, &ring_params);
+    let mut all_polygons = Vec::with_capacity(rings);
+
+    for &t in &ring_params {
+        let scale = options.radius_scale.at(t);
+        let matrix = ring_matrix(
+            frames.transform_matrix(curve, t),
+            scale * options.scale_x.at(t),
+            scale * options.scale_y.at(t),
+            options.twist.at(t),
+        );
+        all_polygons.push(polygon.transform(&matrix));
+    }
+
+    // Texture u: distance around the profile, 0 to 1
+    let points = options.profile.points();
+    let mut perimeter = 0.0;
+    let mut u_coords = vec![0.0];
+    for j in 0..polygon_sides {
+        let (x0, y0) = points[j];
+        let (x1, y1) = points[(j + 1) % polygon_sides];
+        perimeter += (x1 - x0).hypot(y1 - y0);
+        u_coords.push(perimeter);
+    }
+    for u in u_coords.iter_mut() {
+        *u /= perimeter;
+    }
+
+    // Each ring repeats its first vertex at u = 1 so textures don't smear
+    // across the wrap
+    let ring_size = polygon_sides + 1;
+    let index = |i: usize, j: usize| (i * ring_size + j) as u32;
+
+    // Create vertices for all rings including the closing ring
+    let mut vertices = Vec::with_capacity(rings * ring_size + 2 * (polygon_sides + 1));
+
+    for (i, ring) in all_polygons.iter().enumerate() {
+        // On a closed loop the last ring must use the exact same color as the first ring
+        let color_index = if closed { i % num_polygons } else { i };
+        let rgb = palette.at(ring_values[color_index]);
+
+        // Neighbouring rings for the normals; ring 0 and the closing ring
+        // coincide on a closed loop
+        let (prev, next) = if closed {
+            let prev = if i == 0 { num_polygons - 1 } else { i - 1 };
+            let next = if i == num_polygons { 1 } else { i + 1 };
+            (prev, next)
+        } else {
+            (i.saturating_sub(1), (i + 1).min(num_polygons))
+        };
+
+        let tangent = curve.tangent(ring_params[i]);
+
+        // Texture v: distance along the curve in profile perimeters
+        let v = (arc.arc_length(ring_params[i]) / perimeter) as f32;
+
+        for (j, u) in u_coords.iter().enumerate() {
+            let side = j % polygon_sides;
+            let p = &ring[side];
+
+            // Smooth normal: around-the-ring direction × along-the-curve direction
+            let around =
+                ring[(side + 1) % polygon_sides] - ring[(side + polygon_sides - 1) % polygon_sides];
+            // Where the tube folds the chord runs backwards; the curve
+            // tangent is the better guess there
+            let chord = all_polygons[next][side] - all_polygons[prev][side];
+            let along = if chord.dot(&tangent) > 0.0 {
+                chord
+            } else {
+                tangent
+            };
+            let n = around.cross(&along).normalize();
+
+            vertices.push(Vertex {
+                pos: [p.x as f32, p.y as f32, p.z as f32],
+                color: [rgb[0], rgb[1], rgb[2], 0.5], // More opaque, less washed out
+                normal: [n.x as f32, n.y as f32, n.z as f32],
+                uv: [*u as f32, v],
+                // Unlike the colour, the closing ring keeps its own value
+                // so the shader doesn't interpolate back across the palette
+                scalar: ring_values[i],
+            });
+        }
+    }
+
+    // Triangles - connect each ring to the next (last ring connects to ring 0)
+    let mut triangles = Vec::new();
+    for i in 0..num_polygons {
+        for j in 0..polygon_sides {
+            let a = index(i, j);
+            let b = index(i + 1, j);
+            let c = index(i, j + 1);
+            let d = index(i + 1, j + 1);
+            triangles.extend_from_slice(&[a, b, c, b, d, c]);
+        }
+    }
+
+    // End caps - a fan around the curve point, wound to match the walls.
+    // Caps get their own vertices so they can have a flat normal.
+    if !closed {
+        let radius = options.profile.radius().max(f64::EPSILON);
+        for (ring, reversed) in [(0, false), (num_polygons, true)] {
+            let t = ring_params[ring];
+            let center = curve.position(t);
+            let tangent = frames.frame(curve, t).tangent;
+            let n = if reversed { tangent } else { -tangent };
+            let normal = [n.x as f32, n.y as f32, n.z as f32];
+            let base = vertices[index(ring, 0) as usize];
+
+            let c = vertices.len() as u32;
+            vertices.push(Vertex {
+                pos: [center.x as f32, center.y as f32, center.z as f32],
+                normal,
+                uv: [0.5, 0.5],
+                ..base
+            });
+            for (j, &(x, y)) in points.iter().enumerate() {
+                vertices.push(Vertex {
+                    normal,
+                    uv: [
+                        (0.5 + 0.5 * x / radius) as f32,
+                        (0.5 + 0.5 * y / radius) as f32,
+                    ],
+                    ..vertices[index(ring, j) as usize]
+                });
+            }
+
+            for j in 0..polygon_sides {
+                let a = c + 1 + j as u32;
+                let b = c + 1 + ((j + 1) % polygon_sides) as u32;
+                if reversed {
+                    triangles.extend_from_slice(&[c, b, a]);
+                } else {
+                    triangles.extend_from_slice(&[c, a, b]);
+                }
+            }
+        }
+    }
+
+    // Longitude lines - along the curve
+    let mut long_lines = Vec::new();
+    for j in 0..polygon_sides {
+        for i in 0..num_polygons {
+            long_lines.extend_from_slice(&[index(i, j), index(i + 1, j)]);
+        }
+    }
+
+    // Latitude lines - around each ring
+    let mut lat_lines = Vec::new();
+    for i in 0..rings {
+        for j in 0..polygon_sides {
+            lat_lines.extend_from_slice(&[index(i, j), index(i, j + 1)]);
+        }
+    }
+
+    Mesh {
+        vertices,
+        triangles,
+        long_lines,
+        lat_lines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lisa::Lissajou3D;
+    use crate::math::V3D;
+
+    #[test]
+    fn closed_tunnel_counts() {
+        let curve = Lissajou3D::new(3.0, 2.0, 10.0);
+        let (sides, rings) = (8, 50);
+        let mesh = generate_tunnel_mesh(
+            &curve,
+            &TunnelOptions::new(1.0, sides, rings).unwrap(),
+            &Palette::default(),
+        );
+        assert_eq!(mesh.vertices.len(), (rings + 1) * (sides + 1));
+        assert_eq!(mesh.triangles.len(), rings * sides * 6);
+        assert_eq!(mesh.long_lines.len(), sides * rings * 2);
+        assert_eq!(mesh.lat_lines.len(), (rings + 1) * sides * 2);
+        assert!(mesh
+            .triangles
+            .iter()
+            .all(|&i| (i as usize) < mesh.vertices.len()));
+    }
+
+    #[test]
+    fn open_tunnel_has_caps() {
+        let curve = Lissajou3D::new(1.0, std::f64::consts::SQRT_2, 10.0);
+        assert!(!curve.is_closed());
+        let (sides, rings) = (6, 40);
+        let mesh = generate_tunnel_mesh(
+            &curve,
+            &TunnelOptions::new(1.0, sides, rings).unwrap(),
+            &Palette::default(),
+        );
+        // Each cap adds a centre and its own copy of the ring
+        assert_eq!(
+            mesh.vertices.len(),
+            (rings + 1) * (sides + 1) + 2 * (sides + 1)
+        );
+        assert_eq!(mesh.triangles.len(), rings * sides * 6 + 2 * sides * 3);
+    }
+
+    #[test]
+    fn empty_sweeps_give_empty_meshes() {
+        let curve = Lissajou3D::new(3.0, 2.0, 10.0);
+        let mesh = generate_tunnel_mesh(
+            &curve,
+            &TunnelOptions::new(1.0, 8, 0).unwrap(),
+            &Palette::default(),
+        );
+        assert!(mesh.vertices.is_empty() && mesh.triangles.is_empty());
+    }
+
+    // Every face of a flipped mesh should face the way its vertex normals do
+    fn assert_faces_follow_normals(mesh: &Mesh) {
+        let pos = |i: u32| {
+            let p = mesh.vertices[i as usize].pos;
+            V3D::new(p[0] as f64, p[1] as f64, p[2] as f64)
+        };
+        for (k, tri) in mesh.triangles.chunks_exact(3).enumerate() {
+            let face = (pos(tri[1]) - pos(tri[0])).cross(&(pos(tri[2]) - pos(tri[0])));
+            for &i in tri {
+                let n = mesh.vertices[i as usize].normal;
+                let dot = face.x * n[0] as f64 + face.y * n[1] as f64 + face.z * n[2] as f64;
+                assert!(dot > 0.0, "triangle {} faces against vertex {}", k, i);
+            }
+        }
+    }
+
+    #[test]
+    fn flipped_faces_follow_normals() {
+        let closed = Lissajou3D::new(3.0, 2.0, 10.0);
+        let open = Lissajou3D::new(1.0, std::f64::consts::SQRT_2, 10.0).with_phase(0.5, 0.0);
+        // Rings close enough together that no quad is skewed past its normals
+        for curve in [closed, open] {
+            let mut mesh = generate_tunnel_mesh(
+                &curve,
+                &TunnelOptions::new(1.0, 8, 2000).unwrap(),
+                &Palette::default(),
+            );
+            mesh.flip_winding();
+            assert_faces_follow_normals(&mesh);
+        }
+    }
+}
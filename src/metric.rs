@@ -0,0 +1,298 @@
+use std::f64::consts::TAU;
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+use crate::curve::ParametricCurve;
+use crate::math::V3D;
+
+// Curve samples per loop for the proximity metric
+const PROXIMITY_SAMPLES: usize = 512;
+// The part of a metric's range spread over the palette
+const LOW_PERCENTILE: f64 = 0.02;
+const HIGH_PERCENTILE: f64 = 0.98;
+
+/// The scalar along the curve that ring colours follow
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMetric {
+    /// Position of the ring along the tunnel
+    #[default]
+    RingIndex,
+    /// κ; bright where the curve bends hardest
+    Curvature,
+    /// τ; how fast the curve leaves its osculating plane
+    Torsion,
+    /// Parametric speed |r'(t)|
+    Speed,
+    /// z of `position(t)`
+    Height,
+    /// Angle above the xy plane as seen from the origin
+    Latitude,
+    /// Angle around the z axis. Uses the palette's full range regardless
+    /// of the curve, so cyclic colormaps wrap without a seam.
+    Longitude,
+    /// Distance to the nearest other part of the curve; 0 at crossings
+    Proximity,
+}
+
+/// `metric` at each ring parameter, scaled to [0, 1] for a palette.
+///
+/// Ring index and longitude have natural ranges; the rest are stretched
+/// between their 2nd and 98th percentiles over `params`, and values beyond
+/// those clamp to 0 or 1.
+pub fn ring_metric<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    metric: ColorMetric,
+    params: &[f64],
+) -> Vec<f32> {
+    let last = params.len().saturating_sub(1).max(1) as f64;
+    let values: Vec<f64> = match metric {
+        ColorMetric::RingIndex => {
+            return (0..params.len())
+                .map(|i| (i as f64 / last) as f32)
+                .collect()
+        }
+        ColorMetric::Longitude => {
+            return params
+                .iter()
+                .map(|&t| {
+                    let p = curve.position(t);
+                    (p.y.atan2(p.x) / TAU).rem_euclid(1.0) as f32
+                })
+                .collect()
+        }
+        ColorMetric::Curvature => params.iter().map(|&t| curve.curvature(t)).collect(),
+        ColorMetric::Torsion => params.iter().map(|&t| curve.torsion(t)).collect(),
+        ColorMetric::Speed => params.iter().map(|&t| curve.speed(t)).collect(),
+        ColorMetric::Height => params.iter().map(|&t| curve.position(t).z).collect(),
+        ColorMetric::Latitude => params
+            .iter()
+            .map(|&t| {
+                let p = curve.position(t);
+                let r = p.magnitude();
+                if r > 0.0 {
+                    (p.z / r).clamp(-1.0, 1.0).asin()
+                } else {
+                    0.0
+                }
+            })
+            .collect(),
+        ColorMetric::Proximity => {
+            let samples = ProximitySamples::new(curve);
+            params
+                .iter()
+                .map(|&t| samples.nearest_other(curve, t))
+                .collect()
+        }
+    };
+    normalize(&values)
+}
+
+// Stretch `values` onto [0, 1] between their 2nd and 98th percentiles,
+// clamping the rest, so one spike near a pole doesn't squash every other
+// ring into a sliver of the palette. Falls back to the full range when the
+// percentiles coincide; a constant metric maps to 0.
+fn normalize(values: &[f64]) -> Vec<f32> {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    sorted.sort_by(f64::total_cmp);
+    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+        return vec![0.0; values.len()];
+    };
+    let (mut lo, mut hi) = (
+        percentile(&sorted, LOW_PERCENTILE),
+        percentile(&sorted, HIGH_PERCENTILE),
+    );
+    if hi <= lo {
+        (lo, hi) = (min, max);
+    }
+    let range = hi - lo;
+    // Rounding noise on a constant metric isn't worth a palette
+    let flat = range <= 1e-9 * lo.abs().max(hi.abs());
+    values
+        .iter()
+        .map(|&v| {
+            if !flat && v.is_finite() {
+                ((v.clamp(lo, hi) - lo) / range) as f32
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+// Linearly interpolated percentile `p` in [0, 1] of non-empty sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let x = p * (sorted.len() - 1) as f64;
+    let i = x.floor() as usize;
+    let j = (i + 1).min(sorted.len() - 1);
+    sorted[i] + (sorted[j] - sorted[i]) * (x - i as f64)
+}
+
+// The curve sampled once for the proximity metric
+struct ProximitySamples {
+    closed: bool,
+    period: f64,
+    positions: Vec<V3D>,
+}
+
+impl ProximitySamples {
+    fn new<C: ParametricCurve + ?Sized>(curve: &C) -> Self {
+        let period = curve.period();
+        let closed = curve.is_closed();
+        let count = if closed {
+            PROXIMITY_SAMPLES
+        } else {
+            PROXIMITY_SAMPLES + 1
+        };
+        Self {
+            closed,
+            period,
+            positions: (0..count)
+                .map(|i| curve.position(period * i as f64 / PROXIMITY_SAMPLES as f64))
+                .collect(),
+        }
+    }
+
+    // Distance from `position(t)` to the nearest other part of the curve:
+    // the smallest local minimum of the distance to the samples, skipping
+    // the trivial one at t itself. Neighbours along the curve approach
+    // t monotonically, so they never form a minimum of their own.
+    fn nearest_other<C: ParametricCurve + ?Sized>(&self, curve: &C, t: f64) -> f64 {
+        let p = curve.position(t);
+        let count = self.positions.len();
+        let distances: Vec<f64> = self
+            .positions
+            .iter()
+            .map(|q| (*q - p).magnitude())
+            .collect();
+
+        let own = (t / self.period * PROXIMITY_SAMPLES as f64).round() as i64;
+        let near_own = |j: usize| {
+            let offset = j as i64 - own;
+            if self.closed {
+                let offset = offset.rem_euclid(PROXIMITY_SAMPLES as i64);
+                offset.min(PROXIMITY_SAMPLES as i64 - offset) <= 1
+            } else {
+                offset.abs() <= 1
+            }
+        };
+        let neighbour = |j: usize, step: i64| -> Option<f64> {
+            let k = j as i64 + step;
+            if self.closed {
+                Some(distances[k.rem_euclid(count as i64) as usize])
+            } else {
+                distances.get(usize::try_from(k).ok()?).copied()
+            }
+        };
+
+        let nearest = (0..count)
+            .filter(|&j| !near_own(j))
+            .filter(|&j| {
+                let d = distances[j];
+                [-1, 1]
+                    .iter()
+                    .all(|&step| neighbour(j, step).is_none_or(|n| d <= n))
+            })
+            .map(|j| distances[j])
+            .fold(f64::INFINITY, f64::min);
+
+        if nearest.is_finite() {
+            nearest
+        } else {
+            // A simple arc has no other part; use its farthest point
+            distances.iter().copied().fold(0.0, f64::max)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lisa::Lissajou3D;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    // The equator of a sphere of radius 3
+    fn circle() -> Lissajou3D {
+        Lissajou3D::new(0.0, 1.0, 3.0).with_phase(FRAC_PI_2, 0.0)
+    }
+
+    fn params(curve: &Lissajou3D, count: usize) -> Vec<f64> {
+        (0..count)
+            .map(|i| curve.period() * i as f64 / count as f64)
+            .collect()
+    }
+
+    #[test]
+    fn spikes_clamp_instead_of_squashing() {
+        let mut values: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        values[50] = 1e6;
+        let scaled = normalize(&values);
+        assert_eq!(scaled[50], 1.0);
+        // The ordinary values still use most of the palette
+        assert!(scaled[10] < 0.1 && scaled[90] > 0.85);
+        assert!(scaled.iter().all(|v| (0.0..=1.0).contains(v)));
+        assert_eq!(scaled[0], 0.0);
+    }
+
+    #[test]
+    fn flat_and_undefined_values_map_to_zero() {
+        assert!(normalize(&[2.5; 10]).iter().all(|&v| v == 0.0));
+        assert!(normalize(&[]).is_empty());
+        let scaled = normalize(&[0.0, f64::NAN, 1.0]);
+        assert_eq!(scaled, vec![0.0, 0.0, 1.0]);
+        // Mostly flat with one outlier: the percentiles coincide, so the
+        // full range is used
+        let mut values = vec![1.0; 100];
+        values[3] = 2.0;
+        assert_eq!(normalize(&values)[3], 1.0);
+    }
+
+    #[test]
+    fn natural_ranges() {
+        let curve = circle();
+        let ts = params(&curve, 8);
+        let index = ring_metric(&curve, ColorMetric::RingIndex, &ts);
+        assert_eq!(index[0], 0.0);
+        assert_eq!(index[7], 1.0);
+        assert!((index[2] - 2.0 / 7.0).abs() < 1e-6);
+        // Around the equator longitude runs through the palette once
+        let longitude = ring_metric(&curve, ColorMetric::Longitude, &ts);
+        for (i, v) in longitude.iter().enumerate() {
+            assert!((v - i as f32 / 8.0).abs() < 1e-6, "ring {}: {}", i, v);
+        }
+    }
+
+    #[test]
+    fn stretched_metrics() {
+        // Constant curvature on a circle, up to rounding
+        let curve = circle();
+        let curvature = ring_metric(&curve, ColorMetric::Curvature, &params(&curve, 16));
+        assert!(curvature.iter().all(|&v| v == 0.0));
+        // z = r cos 3t tops out at t = 0 and bottoms out at t = π/3
+        let curve = Lissajou3D::new(3.0, 2.0, 10.0);
+        let height = ring_metric(&curve, ColorMetric::Height, &[0.0, PI / 3.0, PI / 6.0]);
+        assert_eq!(height, vec![1.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn nearest_other_part() {
+        // 2:1 passes through the north pole at t = 0 and again at t = π
+        let curve = Lissajou3D::new(2.0, 1.0, 5.0);
+        let samples = ProximitySamples::new(&curve);
+        assert!(samples.nearest_other(&curve, 0.0) < 1e-9);
+        // Away from the pole the other passes are well clear
+        assert!(samples.nearest_other(&curve, 0.5 * FRAC_PI_2) > 1.0);
+        // so the crossing gets the bottom of the palette
+        let proximity = ring_metric(&curve, ColorMetric::Proximity, &params(&curve, 64));
+        assert_eq!(proximity[0], 0.0);
+        assert_eq!(proximity[32], 0.0);
+        assert!(proximity[8] > 0.5);
+
+        // A circle has no other part; its neighbours are not minima
+        let curve = circle();
+        let samples = ProximitySamples::new(&curve);
+        for t in [0.0, 1.0, 4.0] {
+            assert!((samples.nearest_other(&curve, t) - 6.0).abs() < 1e-3);
+        }
+    }
+}
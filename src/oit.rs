@@ -0,0 +1,252 @@
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    WebGlBuffer, WebGlFramebuffer, WebGlProgram, WebGlRenderbuffer, WebGlRenderingContext as GL,
+    WebGlTexture, WebGlUniformLocation,
+};
+
+use crate::gl::{attrib_location, compile_shader, link_program};
+
+// From OES_texture_half_float, which WebGL1 doesn't define
+const HALF_FLOAT_OES: u32 = 0x8D61;
+
+/// How the translucent tunnel walls are composited
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transparency {
+    /// Back faces, then front faces, blended in triangle order
+    #[default]
+    TwoPass,
+    /// Weighted blended order-independent transparency (McGuire & Bavoil
+    /// 2013). Needs renderable float textures; falls back to `TwoPass`.
+    WeightedBlended,
+}
+
+/// Off-screen targets for weighted blended OIT.
+///
+/// WebGL1 has no multiple render targets, so the accumulation and
+/// revealage buffers are filled in two passes that share one depth buffer.
+pub(crate) struct OitTargets {
+    accum_framebuffer: WebGlFramebuffer,
+    reveal_framebuffer: WebGlFramebuffer,
+    accum: WebGlTexture,
+    reveal: WebGlTexture,
+    depth: WebGlRenderbuffer,
+    texel_type: u32,
+    width: i32,
+    height: i32,
+
+    program: WebGlProgram,
+    quad_buffer: WebGlBuffer,
+    position_attrib: Option<u32>,
+    accum_uniform: Option<WebGlUniformLocation>,
+    reveal_uniform: Option<WebGlUniformLocation>,
+    size_uniform: Option<WebGlUniformLocation>,
+}
+
+impl OitTargets {
+    /// Targets the size of the drawing buffer, or an error if this browser
+    /// can't render to float textures
+    pub(crate) fn new(gl: &GL) -> Result<Self, String> {
+        let texel_type = if gl
+            .get_extension("OES_texture_float")
+            .ok()
+            .flatten()
+            .is_some()
+        {
+            let _ = gl.get_extension("WEBGL_color_buffer_float");
+            GL::FLOAT
+        } else if gl
+            .get_extension("OES_texture_half_float")
+            .ok()
+            .flatten()
+            .is_some()
+        {
+            let _ = gl.get_extension("EXT_color_buffer_half_float");
+            HALF_FLOAT_OES
+        } else {
+            return Err("float textures are not supported".into());
+        };
+
+        let vert_shader = compile_shader(
+            gl,
+            GL::VERTEX_SHADER,
+            include_str!("shaders/oit_composite.vert"),
+        )?;
+        let frag_shader = compile_shader(
+            gl,
+            GL::FRAGMENT_SHADER,
+            include_str!("shaders/oit_composite.frag"),
+        )?;
+        let program = link_program(gl, &vert_shader, &frag_shader)?;
+        gl.delete_shader(Some(&vert_shader));
+        gl.delete_shader(Some(&frag_shader));
+
+        // Two triangles covering the screen
+        let quad_buffer = gl.create_buffer().ok_or("unable to create buffer")?;
+        gl.bind_buffer(GL::ARRAY_BUFFER, Some(&quad_buffer));
+        let corners: [f32; 12] = [
+            -1.0, -1.0, 1.0, -1.0, -1.0, 1.0, -1.0, 1.0, 1.0, -1.0, 1.0, 1.0,
+        ];
+        unsafe {
+            let array = js_sys::Float32Array::view(&corners);
+            gl.buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &array, GL::STATIC_DRAW);
+        }
+
+        let mut targets = Self {
+            accum_framebuffer: gl
+                .create_framebuffer()
+                .ok_or("unable to create framebuffer")?,
+            reveal_framebuffer: gl
+                .create_framebuffer()
+                .ok_or("unable to create framebuffer")?,
+            accum: gl.create_texture().ok_or("unable to create texture")?,
+            reveal: gl.create_texture().ok_or("unable to create texture")?,
+            depth: gl
+                .create_renderbuffer()
+                .ok_or("unable to create renderbuffer")?,
+            texel_type,
+            width: 0,
+            height: 0,
+            position_attrib: attrib_location(gl, &program, "position"),
+            accum_uniform: gl.get_uniform_location(&program, "u_accum"),
+            reveal_uniform: gl.get_uniform_location(&program, "u_reveal"),
+            size_uniform: gl.get_uniform_location(&program, "u_size"),
+            program,
+            quad_buffer,
+        };
+
+        let result = targets.resize(gl);
+        if result.is_err() {
+            targets.release(gl);
+        }
+        result.map(|_| targets)
+    }
+
+    // Reallocate the textures when the drawing buffer changed size
+    fn resize(&mut self, gl: &GL) -> Result<(), String> {
+        let (width, height) = (gl.drawing_buffer_width(), gl.drawing_buffer_height());
+        if (width, height) == (self.width, self.height) {
+            return Ok(());
+        }
+        self.width = width;
+        self.height = height;
+
+        for texture in [&self.accum, &self.reveal] {
+            gl.bind_texture(GL::TEXTURE_2D, Some(texture));
+            // Non-power-of-two sizes need clamping and no mipmaps
+            gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::NEAREST as i32);
+            gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::NEAREST as i32);
+            gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
+            gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+            gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                GL::TEXTURE_2D,
+                0,
+                GL::RGBA as i32,
+                width,
+                height,
+                0,
+                GL::RGBA,
+                self.texel_type,
+                None,
+            )
+            .map_err(|e| format!("{:?}", e))?;
+        }
+        gl.bind_texture(GL::TEXTURE_2D, None);
+
+        gl.bind_renderbuffer(GL::RENDERBUFFER, Some(&self.depth));
+        gl.renderbuffer_storage(GL::RENDERBUFFER, GL::DEPTH_COMPONENT16, width, height);
+        gl.bind_renderbuffer(GL::RENDERBUFFER, None);
+
+        for (framebuffer, texture) in [
+            (&self.accum_framebuffer, &self.accum),
+            (&self.reveal_framebuffer, &self.reveal),
+        ] {
+            gl.bind_framebuffer(GL::FRAMEBUFFER, Some(framebuffer));
+            gl.framebuffer_texture_2d(
+                GL::FRAMEBUFFER,
+                GL::COLOR_ATTACHMENT0,
+                GL::TEXTURE_2D,
+                Some(texture),
+                0,
+            );
+            gl.framebuffer_renderbuffer(
+                GL::FRAMEBUFFER,
+                GL::DEPTH_ATTACHMENT,
+                GL::RENDERBUFFER,
+                Some(&self.depth),
+            );
+            let status = gl.check_framebuffer_status(GL::FRAMEBUFFER);
+            if status != GL::FRAMEBUFFER_COMPLETE {
+                gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+                return Err(format!("float render target incomplete ({:#x})", status));
+            }
+        }
+        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+        Ok(())
+    }
+
+    /// Bind and clear the accumulation target. The depth buffer is cleared
+    /// too, ready for the opaque geometry's depth.
+    pub(crate) fn begin_accumulation(&mut self, gl: &GL) -> Result<(), String> {
+        self.resize(gl)?;
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&self.accum_framebuffer));
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
+        // Sum premultiplied, weighted colours
+        gl.blend_func(GL::ONE, GL::ONE);
+        Ok(())
+    }
+
+    /// Bind the revealage target, which keeps the accumulation depth
+    pub(crate) fn begin_revealage(&self, gl: &GL) {
+        gl.bind_framebuffer(GL::FRAMEBUFFER, Some(&self.reveal_framebuffer));
+        gl.clear_color(1.0, 1.0, 1.0, 1.0);
+        gl.clear(GL::COLOR_BUFFER_BIT);
+        // Multiply by (1 - alpha) of every layer
+        gl.blend_func(GL::ZERO, GL::ONE_MINUS_SRC_COLOR);
+    }
+
+    /// Blend the averaged layers over the screen, then restore the default
+    /// framebuffer, program and blending
+    pub(crate) fn composite(&self, gl: &GL, restore: &WebGlProgram) {
+        gl.bind_framebuffer(GL::FRAMEBUFFER, None);
+        gl.use_program(Some(&self.program));
+        gl.blend_func(GL::SRC_ALPHA, GL::ONE_MINUS_SRC_ALPHA);
+        gl.disable(GL::DEPTH_TEST);
+
+        gl.active_texture(GL::TEXTURE0);
+        gl.bind_texture(GL::TEXTURE_2D, Some(&self.accum));
+        gl.active_texture(GL::TEXTURE1);
+        gl.bind_texture(GL::TEXTURE_2D, Some(&self.reveal));
+        gl.uniform1i(self.accum_uniform.as_ref(), 0);
+        gl.uniform1i(self.reveal_uniform.as_ref(), 1);
+        gl.uniform2f(
+            self.size_uniform.as_ref(),
+            self.width as f32,
+            self.height as f32,
+        );
+
+        if let Some(position) = self.position_attrib {
+            gl.bind_buffer(GL::ARRAY_BUFFER, Some(&self.quad_buffer));
+            gl.enable_vertex_attrib_array(position);
+            gl.vertex_attrib_pointer_with_i32(position, 2, GL::FLOAT, false, 0, 0);
+            gl.draw_arrays(GL::TRIANGLES, 0, 6);
+        }
+
+        gl.bind_texture(GL::TEXTURE_2D, None);
+        gl.active_texture(GL::TEXTURE0);
+        gl.bind_texture(GL::TEXTURE_2D, None);
+        gl.enable(GL::DEPTH_TEST);
+        gl.use_program(Some(restore));
+    }
+
+    pub(crate) fn release(&self, gl: &GL) {
+        gl.delete_framebuffer(Some(&self.accum_framebuffer));
+        gl.delete_framebuffer(Some(&self.reveal_framebuffer));
+        gl.delete_texture(Some(&self.accum));
+        gl.delete_texture(Some(&self.reveal));
+        gl.delete_renderbuffer(Some(&self.depth));
+        gl.delete_buffer(Some(&self.quad_buffer));
+        gl.delete_program(Some(&self.program));
+    }
+}
@@ -0,0 +1,332 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::camera::CameraPose;
+use crate::math::V3D;
+
+// Damping is specified per 1/60 s so it feels the same at any frame rate
+const REFERENCE_RATE: f64 = 60.0;
+
+/// Which gesture a pointer drags
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Drag {
+    Rotate,
+    Pan,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Pointer {
+    id: i32,
+    x: f64,
+    y: f64,
+    drag: Drag,
+}
+
+/// Camera circling a target for the outside view, driven by pointer
+/// gestures: one pointer rotates (or pans, for the secondary buttons), two
+/// pinch to zoom and pan together, the wheel zooms.
+///
+/// Input accumulates as pending motion that `update` eases in, so the
+/// camera glides to a stop instead of halting with the pointer.
+#[derive(Clone, Debug)]
+pub struct OrbitCamera {
+    pub target: V3D,
+    pub distance: f64,
+    /// Angle around the y axis; 0 looks down -z
+    pub yaw: f64,
+    /// Angle above the xz plane
+    pub pitch: f64,
+    /// Vertical field of view, for framing and panning
+    pub fov: f64,
+
+    /// Fraction of pending motion still left after 1/60 s; 0 is immediate
+    pub damping: f64,
+    /// Radians per pixel dragged
+    pub rotate_speed: f64,
+    /// Zoom per wheel pixel, as a fraction of the distance
+    pub zoom_speed: f64,
+    /// Radians per second around the target while nobody is dragging
+    pub auto_rotate: f64,
+
+    pub min_pitch: f64,
+    pub max_pitch: f64,
+    /// Yaw limits; infinite by default so the camera can circle freely
+    pub min_yaw: f64,
+    pub max_yaw: f64,
+    pub min_distance: f64,
+    pub max_distance: f64,
+
+    // Motion not yet applied: yaw, pitch, log of the distance factor, and
+    // the target shift
+    pending_yaw: f64,
+    pending_pitch: f64,
+    pending_zoom: f64,
+    pending_pan: V3D,
+    pointers: Vec<Pointer>,
+    // Where `reset` goes back to
+    home: (V3D, f64, f64, f64),
+}
+
+impl OrbitCamera {
+    pub fn new(target: V3D, distance: f64, yaw: f64, pitch: f64, fov: f64) -> Self {
+        Self {
+            target,
+            distance,
+            yaw,
+            pitch,
+            fov,
+            damping: 0.85,
+            rotate_speed: 0.005,
+            zoom_speed: 0.001,
+            auto_rotate: 0.0,
+            min_pitch: -FRAC_PI_2 + 0.01,
+            max_pitch: FRAC_PI_2 - 0.01,
+            min_yaw: f64::NEG_INFINITY,
+            max_yaw: f64::INFINITY,
+            min_distance: 1e-3,
+            max_distance: f64::INFINITY,
+            pending_yaw: 0.0,
+            pending_pitch: 0.0,
+            pending_zoom: 0.0,
+            pending_pan: V3D::new(0.0, 0.0, 0.0),
+            pointers: Vec::new(),
+            home: (target, distance, yaw, pitch),
+        }
+    }
+
+    /// Look at a sphere so it just fills the narrower side of a view with
+    /// the given aspect ratio, and make that the pose `reset` returns to.
+    /// Zoom is limited to between a tenth and ten times that distance.
+    pub fn frame_sphere(&mut self, center: V3D, radius: f64, aspect: f64) {
+        let half_vertical = 0.5 * self.fov;
+        let half_horizontal = (half_vertical.tan() * aspect.max(1e-3)).atan();
+        let half = half_vertical.min(half_horizontal);
+        let distance = radius.max(1e-6) / half.sin();
+
+        self.min_distance = 0.1 * distance;
+        self.max_distance = 10.0 * distance;
+        self.home = (center, distance, self.home.2, self.home.3);
+        self.reset();
+    }
+
+    /// Jump back to the home pose and drop any motion in flight
+    pub fn reset(&mut self) {
+        let (target, distance, yaw, pitch) = self.home;
+        self.target = target;
+        self.distance = distance;
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.pending_yaw = 0.0;
+        self.pending_pitch = 0.0;
+        self.pending_zoom = 0.0;
+        self.pending_pan = V3D::new(0.0, 0.0, 0.0);
+    }
+
+    /// Turn by a drag of `dx`, `dy` pixels; dragging right swings the
+    /// camera left around the target, like turning the object
+    pub fn rotate(&mut self, dx: f64, dy: f64) {
+        self.pending_yaw -= dx * self.rotate_speed;
+        self.pending_pitch += dy * self.rotate_speed;
+    }
+
+    /// Move in (`amount` < 0) or out by `amount` wheel pixels
+    pub fn zoom(&mut self, amount: f64) {
+        self.pending_zoom += amount * self.zoom_speed;
+    }
+
+    /// Slide the target so the point under the pointer follows a drag of
+    /// `dx`, `dy` pixels in a view `viewport_height` pixels tall
+    pub fn pan(&mut self, dx: f64, dy: f64, viewport_height: f64) {
+        let (right, up) = self.screen_axes();
+        let per_pixel = 2.0 * self.distance * (0.5 * self.fov).tan() / viewport_height.max(1.0);
+        self.pending_pan = self.pending_pan + (right * -dx + up * dy) * per_pixel;
+    }
+
+    pub fn pointer_down(&mut self, id: i32, x: f64, y: f64, drag: Drag) {
+        self.pointers.retain(|p| p.id != id);
+        self.pointers.push(Pointer { id, x, y, drag });
+    }
+
+    /// Follow a pointer; a second pointer turns the gesture into pinch and pan
+    pub fn pointer_move(&mut self, id: i32, x: f64, y: f64, viewport_height: f64) {
+        let Some(index) = self.pointers.iter().position(|p| p.id == id) else {
+            return;
+        };
+        let before = self.pointers[index];
+        match self.pointers.as_slice() {
+            [single] => {
+                let (dx, dy) = (x - single.x, y - single.y);
+                match single.drag {
+                    Drag::Rotate => self.rotate(dx, dy),
+                    Drag::Pan => self.pan(dx, dy, viewport_height),
+                }
+            }
+            [a, b, ..] => {
+                let other = if index == 0 { *b } else { *a };
+                let span_before = (before.x - other.x).hypot(before.y - other.y);
+                let span_after = (x - other.x).hypot(y - other.y);
+                if span_before > 0.0 && span_after > 0.0 {
+                    self.pending_zoom += (span_before / span_after).ln();
+                }
+                // The midpoint moves half as far as the moving finger
+                self.pan(0.5 * (x - before.x), 0.5 * (y - before.y), viewport_height);
+            }
+            [] => {}
+        }
+        self.pointers[index].x = x;
+        self.pointers[index].y = y;
+    }
+
+    pub fn pointer_up(&mut self, id: i32) {
+        self.pointers.retain(|p| p.id != id);
+    }
+
+    /// Whether a drag is in progress
+    pub fn dragging(&self) -> bool {
+        !self.pointers.is_empty()
+    }
+
+    /// Ease in pending motion and auto-rotate for `dt` seconds
+    pub fn update(&mut self, dt: f64) {
+        let keep = if self.damping > 0.0 {
+            self.damping.clamp(0.0, 0.999).powf(dt * REFERENCE_RATE)
+        } else {
+            0.0
+        };
+        let apply = 1.0 - keep;
+
+        if !self.dragging() {
+            self.yaw += self.auto_rotate * dt;
+        }
+        self.yaw = (self.yaw + self.pending_yaw * apply).clamp(self.min_yaw, self.max_yaw);
+        self.pitch =
+            (self.pitch + self.pending_pitch * apply).clamp(self.min_pitch, self.max_pitch);
+        self.distance = (self.distance * (self.pending_zoom * apply).exp())
+            .clamp(self.min_distance, self.max_distance);
+        self.target = self.target + self.pending_pan * apply;
+
+        self.pending_yaw *= keep;
+        self.pending_pitch *= keep;
+        self.pending_zoom *= keep;
+        self.pending_pan = self.pending_pan * keep;
+    }
+
+    /// Eye position on the sphere around the target, +y up
+    pub fn eye(&self) -> V3D {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        self.target + V3D::new(cos_pitch * sin_yaw, sin_pitch, cos_pitch * cos_yaw) * self.distance
+    }
+
+    pub fn pose(&self) -> CameraPose {
+        let f = |v: V3D| [v.x as f32, v.y as f32, v.z as f32];
+        CameraPose {
+            eye: f(self.eye()),
+            target: f(self.target),
+            up: [0.0, 1.0, 0.0],
+        }
+    }
+
+    // Camera right and up in world space
+    fn screen_axes(&self) -> (V3D, V3D) {
+        let forward = (self.target - self.eye()).normalize();
+        let right = forward.cross(&V3D::new(0.0, 1.0, 0.0)).normalize();
+        (right, right.cross(&forward))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> OrbitCamera {
+        OrbitCamera::new(V3D::new(0.0, 0.0, 0.0), 10.0, 0.0, 0.2, 0.8)
+    }
+
+    #[test]
+    fn damping_is_frame_rate_independent() {
+        let mut once = camera();
+        once.rotate(40.0, -25.0);
+        once.zoom(300.0);
+        once.pan(30.0, 10.0, 600.0);
+        let mut twice = once.clone();
+
+        once.update(1.0 / 30.0);
+        twice.update(1.0 / 60.0);
+        twice.update(1.0 / 60.0);
+        assert!((once.yaw - twice.yaw).abs() < 1e-12);
+        assert!((once.pitch - twice.pitch).abs() < 1e-12);
+        assert!((once.distance - twice.distance).abs() < 1e-9);
+        assert!((once.target - twice.target).magnitude() < 1e-12);
+        // Something moved, and something is left to glide
+        assert!(once.yaw != 0.0 && once.pending_yaw != 0.0);
+    }
+
+    #[test]
+    fn angles_and_distance_stay_within_limits() {
+        let mut camera = camera();
+        camera.min_yaw = -0.5;
+        camera.max_yaw = 0.5;
+        camera.min_distance = 2.0;
+        camera.max_distance = 50.0;
+        for sign in [1.0, -1.0] {
+            camera.rotate(sign * 1e5, sign * 1e5);
+            camera.zoom(sign * 1e5);
+            for _ in 0..100 {
+                camera.update(1.0 / 60.0);
+            }
+            let yaw = if sign > 0.0 { -0.5 } else { 0.5 };
+            let pitch = if sign > 0.0 {
+                camera.max_pitch
+            } else {
+                camera.min_pitch
+            };
+            let distance = if sign > 0.0 { 50.0 } else { 2.0 };
+            assert_eq!(camera.yaw, yaw);
+            assert_eq!(camera.pitch, pitch);
+            assert_eq!(camera.distance, distance);
+        }
+    }
+
+    #[test]
+    fn pinch_zoom_stays_within_limits() {
+        let mut camera = camera();
+        camera.min_distance = 4.0;
+        camera.max_distance = 20.0;
+        // Spreading the fingers zooms in, as far as the limit
+        camera.pointer_down(1, 100.0, 100.0, Drag::Rotate);
+        camera.pointer_down(2, 110.0, 100.0, Drag::Rotate);
+        camera.pointer_move(2, 5000.0, 100.0, 600.0);
+        camera.update(10.0);
+        assert_eq!(camera.distance, 4.0);
+        // Pinching them together zooms out, as far as the other limit
+        camera.pointer_move(2, 100.5, 100.0, 600.0);
+        camera.update(10.0);
+        assert_eq!(camera.distance, 20.0);
+        camera.pointer_up(1);
+        camera.pointer_up(2);
+        assert!(!camera.dragging());
+    }
+
+    #[test]
+    fn frame_sphere_fits_the_narrower_side() {
+        let (center, radius) = (V3D::new(1.0, -2.0, 3.0), 5.0);
+        for aspect in [1.6, 1.0, 0.5] {
+            let mut camera = camera();
+            camera.frame_sphere(center, radius, aspect);
+            assert!((camera.target - center).magnitude() < 1e-12);
+            assert!(((camera.eye() - center).magnitude() - camera.distance).abs() < 1e-9);
+            // The sphere touches the edges of the narrower field of view
+            let half_vertical = 0.5 * camera.fov;
+            let half_horizontal = (half_vertical.tan() * aspect).atan();
+            let half = half_vertical.min(half_horizontal);
+            assert!((camera.distance * half.sin() - radius).abs() < 1e-9);
+            assert!((camera.min_distance - 0.1 * camera.distance).abs() < 1e-12);
+            assert!((camera.max_distance - 10.0 * camera.distance).abs() < 1e-9);
+        }
+        // A portrait view backs off further than a landscape one
+        let (mut wide, mut tall) = (camera(), camera());
+        wide.frame_sphere(center, radius, 1.6);
+        tall.frame_sphere(center, radius, 0.5);
+        assert!(tall.distance > wide.distance);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::math::V3D;
+use crate::profile::Profile;
 
 #[derive(Clone, Debug)]
 pub struct Polygon3D {
@@ -28,6 +29,29 @@ impl Polygon3D {
         }
     }
 
+    /// Polygon with the vertices of an arbitrary cross-section profile
+    pub fn from_profile(profile: &Profile) -> Self {
+        let vertices = profile
+            .points()
+            .iter()
+            .map(|&(x, y)| V3D::new(x, y, 0.0))
+            .collect();
+
+        Self {
+            radius: profile.radius(),
+            sides: profile.len(),
+            vertices,
+        }
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn sides(&self) -> usize {
+        self.sides
+    }
+
     /// Transform polygon vertices using a 4x4 transformation matrix
     pub fn transform(&self, matrix: &[[f64; 4]; 4]) -> Vec<V3D> {
         self.vertices.iter().map(|v| v.transform(matrix)).collect()
@@ -0,0 +1,498 @@
+use std::f64::consts::PI;
+#[cfg(feature = "web")]
+use wasm_bindgen::prelude::*;
+
+// Line segments used to flatten each SVG curve command
+const SVG_CURVE_SEGMENTS: usize = 12;
+
+/// Closed 2D cross-section swept along the curve.
+///
+/// Points live in the ring plane: x along the frame normal, y along the
+/// binormal. They are stored counter-clockwise without repeating the first
+/// point, so every profile produces the same triangle winding.
+#[cfg_attr(feature = "web", wasm_bindgen)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    points: Vec<(f64, f64)>,
+}
+
+#[cfg_attr(feature = "web", wasm_bindgen)]
+impl Profile {
+    /// Regular n-gon, first vertex on the x axis
+    pub fn regular(radius: f64, sides: usize) -> Result<Profile, String> {
+        let angle_step = 2.0 * PI / (sides as f64);
+        let points = (0..sides)
+            .map(|i| {
+                let angle = (i as f64) * angle_step;
+                (radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+        Profile::from_points(points)
+    }
+
+    /// Star alternating between `outer` and `inner` radius
+    pub fn star(outer: f64, inner: f64, tips: usize) -> Result<Profile, String> {
+        let angle_step = PI / (tips as f64);
+        let points = (0..2 * tips)
+            .map(|i| {
+                let angle = (i as f64) * angle_step;
+                let radius = if i % 2 == 0 { outer } else { inner };
+                (radius * angle.cos(), radius * angle.sin())
+            })
+            .collect();
+        Profile::from_points(points)
+    }
+
+    /// |x/a|^n + |y/b|^n = 1. n = 2 is an ellipse, large n tends to a rectangle
+    pub fn superellipse(a: f64, b: f64, exponent: f64, segments: usize) -> Result<Profile, String> {
+        if exponent <= 0.0 {
+            return Err("superellipse exponent must be positive".into());
+        }
+        let power = 2.0 / exponent;
+        let points = (0..segments)
+            .map(|i| {
+                let angle = 2.0 * PI * (i as f64) / (segments as f64);
+                let (sin, cos) = angle.sin_cos();
+                (
+                    a * cos.signum() * cos.abs().powf(power),
+                    b * sin.signum() * sin.abs().powf(power),
+                )
+            })
+            .collect();
+        Profile::from_points(points)
+    }
+
+    /// Rectangle centred on the origin with circular corners
+    pub fn rounded_rect(
+        width: f64,
+        height: f64,
+        corner_radius: f64,
+        corner_segments: usize,
+    ) -> Result<Profile, String> {
+        let (hw, hh) = (0.5 * width, 0.5 * height);
+        let r = corner_radius.clamp(0.0, hw.min(hh));
+        let corners = [
+            (hw - r, hh - r),
+            (r - hw, hh - r),
+            (r - hw, r - hh),
+            (hw - r, r - hh),
+        ];
+
+        let mut points = Vec::with_capacity(4 * (corner_segments + 1));
+        for (k, (cx, cy)) in corners.iter().enumerate() {
+            let start = (k as f64) * 0.5 * PI;
+            for i in 0..=corner_segments {
+                let angle = start + 0.5 * PI * (i as f64) / (corner_segments.max(1) as f64);
+                points.push((cx + r * angle.cos(), cy + r * angle.sin()));
+            }
+        }
+        // Square corners would repeat each corner point
+        points.dedup_by(|p, q| (p.0 - q.0).abs() < 1e-12 && (p.1 - q.1).abs() < 1e-12);
+
+        Profile::from_points(points)
+    }
+
+    /// Profile from interleaved `[x0, y0, x1, y1, ...]` coordinates
+    pub fn from_coords(coords: Vec<f64>) -> Result<Profile, String> {
+        if !coords.len().is_multiple_of(2) {
+            return Err("profile coordinates must come in x, y pairs".into());
+        }
+        Profile::from_points(coords.chunks(2).map(|c| (c[0], c[1])).collect())
+    }
+
+    /// Profile from the first subpath of an SVG path string, e.g.
+    /// `"M 0 0 L 10 0 Q 10 10 0 10 Z"`. SVG's y axis points down, so it is
+    /// flipped here.
+    pub fn from_svg_path(d: &str) -> Result<Profile, String> {
+        let points = parse_svg_path(d)?;
+        Profile::from_points(points.into_iter().map(|(x, y)| (x, -y)).collect())
+    }
+
+    /// Centre on the bounding box and scale so the farthest point is `radius`
+    pub fn fit(&self, radius: f64) -> Profile {
+        let (mut min, mut max) = ((f64::MAX, f64::MAX), (f64::MIN, f64::MIN));
+        for &(x, y) in &self.points {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        let center = (0.5 * (min.0 + max.0), 0.5 * (min.1 + max.1));
+        let centered: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|&(x, y)| (x - center.0, y - center.1))
+            .collect();
+        let extent = centered
+            .iter()
+            .map(|&(x, y)| x.hypot(y))
+            .fold(0.0, f64::max);
+        let scale = if extent > 0.0 { radius / extent } else { 1.0 };
+
+        Profile {
+            points: centered
+                .into_iter()
+                .map(|(x, y)| (x * scale, y * scale))
+                .collect(),
+        }
+    }
+
+    /// Distance from the origin to the farthest point
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn radius(&self) -> f64 {
+        self.points
+            .iter()
+            .map(|&(x, y)| x.hypot(y))
+            .fold(0.0, f64::max)
+    }
+
+    #[cfg_attr(feature = "web", wasm_bindgen(getter))]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Interleaved `[x0, y0, x1, y1, ...]`
+    pub fn coords(&self) -> Vec<f64> {
+        self.points.iter().flat_map(|&(x, y)| [x, y]).collect()
+    }
+}
+
+impl Profile {
+    /// Arbitrary closed outline. A repeated closing point is dropped and
+    /// clockwise input is reversed. Every constructor ends up here, so a
+    /// profile always has at least 3 finite points enclosing some area.
+    pub fn from_points(mut points: Vec<(f64, f64)>) -> Result<Profile, String> {
+        if points
+            .iter()
+            .any(|&(x, y)| !x.is_finite() || !y.is_finite())
+        {
+            return Err("profile points must be finite".into());
+        }
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        if points.len() < 3 {
+            return Err("a profile needs at least 3 points".into());
+        }
+
+        let twice_area: f64 = (0..points.len())
+            .map(|i| {
+                let (x0, y0) = points[i];
+                let (x1, y1) = points[(i + 1) % points.len()];
+                x0 * y1 - x1 * y0
+            })
+            .sum();
+        if twice_area.abs() < 1e-12 {
+            return Err("profile encloses no area".into());
+        }
+        if twice_area < 0.0 {
+            points.reverse();
+        }
+
+        Ok(Profile { points })
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+}
+
+// Flatten the first subpath into points. Supports M, L, H, V, C, S, Q, T
+// and Z in absolute and relative form.
+fn parse_svg_path(d: &str) -> Result<Vec<(f64, f64)>, String> {
+    let tokens = tokenize_svg_path(d)?;
+    let mut points: Vec<(f64, f64)> = Vec::new();
+    let mut current = (0.0, 0.0);
+    // Reflected control point for S/T
+    let mut last_control: Option<(char, (f64, f64))> = None;
+    let mut command: Option<char> = None;
+    let mut i = 0;
+
+    let number = |i: &mut usize| -> Result<f64, String> {
+        match tokens.get(*i) {
+            Some(SvgToken::Number(n)) => {
+                *i += 1;
+                Ok(*n)
+            }
+            _ => Err("expected a number in SVG path".into()),
+        }
+    };
+
+    while i < tokens.len() {
+        let cmd = match tokens[i] {
+            SvgToken::Command(c) => {
+                i += 1;
+                c
+            }
+            // Repeated arguments reuse the previous command; after M they mean L
+            SvgToken::Number(_) => match command {
+                Some('M') => 'L',
+                Some('m') => 'l',
+                Some(c) => c,
+                None => return Err("SVG path must start with a command".into()),
+            },
+        };
+        let relative = cmd.is_ascii_lowercase();
+        let offset = |p: (f64, f64)| {
+            if relative {
+                (current.0 + p.0, current.1 + p.1)
+            } else {
+                p
+            }
+        };
+
+        match cmd.to_ascii_uppercase() {
+            'M' => {
+                if !points.is_empty() {
+                    // Only the first subpath is used
+                    break;
+                }
+                let p = (number(&mut i)?, number(&mut i)?);
+                current = offset(p);
+                points.push(current);
+            }
+            'L' => {
+                let p = (number(&mut i)?, number(&mut i)?);
+                current = offset(p);
+                points.push(current);
+            }
+            'H' => {
+                let x = number(&mut i)?;
+                current.0 = if relative { current.0 + x } else { x };
+                points.push(current);
+            }
+            'V' => {
+                let y = number(&mut i)?;
+                current.1 = if relative { current.1 + y } else { y };
+                points.push(current);
+            }
+            'C' | 'S' => {
+                let c1 = if cmd.eq_ignore_ascii_case(&'C') {
+                    offset((number(&mut i)?, number(&mut i)?))
+                } else {
+                    match last_control {
+                        Some(('C', c)) => (2.0 * current.0 - c.0, 2.0 * current.1 - c.1),
+                        _ => current,
+                    }
+                };
+                let c2 = offset((number(&mut i)?, number(&mut i)?));
+                let end = offset((number(&mut i)?, number(&mut i)?));
+                for k in 1..=SVG_CURVE_SEGMENTS {
+                    let t = k as f64 / SVG_CURVE_SEGMENTS as f64;
+                    let u = 1.0 - t;
+                    let (w0, w1, w2, w3) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+                    points.push((
+                        w0 * current.0 + w1 * c1.0 + w2 * c2.0 + w3 * end.0,
+                        w0 * current.1 + w1 * c1.1 + w2 * c2.1 + w3 * end.1,
+                    ));
+                }
+                current = end;
+                last_control = Some(('C', c2));
+                command = Some(cmd);
+                continue;
+            }
+            'Q' | 'T' => {
+                let c = if cmd.eq_ignore_ascii_case(&'Q') {
+                    offset((number(&mut i)?, number(&mut i)?))
+                } else {
+                    match last_control {
+                        Some(('Q', c)) => (2.0 * current.0 - c.0, 2.0 * current.1 - c.1),
+                        _ => current,
+                    }
+                };
+                let end = offset((number(&mut i)?, number(&mut i)?));
+                for k in 1..=SVG_CURVE_SEGMENTS {
+                    let t = k as f64 / SVG_CURVE_SEGMENTS as f64;
+                    let u = 1.0 - t;
+                    let (w0, w1, w2) = (u * u, 2.0 * u * t, t * t);
+                    points.push((
+                        w0 * current.0 + w1 * c.0 + w2 * end.0,
+                        w0 * current.1 + w1 * c.1 + w2 * end.1,
+                    ));
+                }
+                current = end;
+                last_control = Some(('Q', c));
+                command = Some(cmd);
+                continue;
+            }
+            // The outline is closed implicitly
+            'Z' => break,
+            other => return Err(format!("unsupported SVG path command '{}'", other)),
+        }
+
+        last_control = None;
+        command = Some(cmd);
+    }
+
+    Ok(points)
+}
+
+enum SvgToken {
+    Command(char),
+    Number(f64),
+}
+
+fn tokenize_svg_path(d: &str) -> Result<Vec<SvgToken>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = d.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == ',' {
+            i += 1;
+        } else if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            tokens.push(SvgToken::Command(c));
+            i += 1;
+        } else {
+            // Number: sign, digits, one dot, optional exponent
+            let begin = i;
+            let mut seen_dot = false;
+            if chars[i] == '-' || chars[i] == '+' {
+                i += 1;
+            }
+            while i < chars.len() {
+                let ch = chars[i];
+                if ch.is_ascii_digit() {
+                    i += 1;
+                } else if ch == '.' && !seen_dot {
+                    seen_dot = true;
+                    i += 1;
+                } else if (ch == 'e' || ch == 'E') && i > begin {
+                    i += 1;
+                    if i < chars.len() && (chars[i] == '-' || chars[i] == '+') {
+                        i += 1;
+                    }
+                } else {
+                    break;
+                }
+            }
+            let text: String = chars[begin..i].iter().collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number '{}' in SVG path", text))?;
+            tokens.push(SvgToken::Number(value));
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constructors_reject_shapes_without_area() {
+        assert!(Profile::regular(1.0, 0).is_err());
+        assert!(Profile::regular(1.0, 2).is_err());
+        assert!(Profile::regular(0.0, 6).is_err());
+        assert!(Profile::star(1.0, 0.5, 0).is_err());
+        assert!(Profile::superellipse(1.0, 1.0, 0.0, 32).is_err());
+        assert!(Profile::superellipse(1.0, 1.0, f64::NAN, 32).is_err());
+        assert!(Profile::superellipse(1.0, 1.0, 2.0, 0).is_err());
+        assert!(Profile::rounded_rect(0.0, 1.0, 0.1, 4).is_err());
+        assert!(Profile::from_points(vec![(0.0, 0.0), (f64::NAN, 1.0), (1.0, 0.0)]).is_err());
+    }
+
+    #[test]
+    fn constructors_build_counter_clockwise_outlines() {
+        assert_eq!(Profile::regular(1.0, 6).unwrap().len(), 6);
+        assert_eq!(Profile::star(1.0, 0.5, 5).unwrap().len(), 10);
+        assert_eq!(Profile::superellipse(2.0, 1.0, 4.0, 32).unwrap().len(), 32);
+        // Square corners collapse to the four corner points
+        assert_eq!(Profile::rounded_rect(2.0, 1.0, 0.0, 4).unwrap().len(), 4);
+    }
+
+    fn twice_area(points: &[(f64, f64)]) -> f64 {
+        (0..points.len())
+            .map(|i| {
+                let (x0, y0) = points[i];
+                let (x1, y1) = points[(i + 1) % points.len()];
+                x0 * y1 - x1 * y0
+            })
+            .sum()
+    }
+
+    fn assert_points_eq(a: &[(f64, f64)], b: &[(f64, f64)]) {
+        assert_eq!(a.len(), b.len(), "{:?} vs {:?}", a, b);
+        for (p, q) in a.iter().zip(b) {
+            assert!(
+                (p.0 - q.0).abs() < 1e-12 && (p.1 - q.1).abs() < 1e-12,
+                "{:?} vs {:?}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn svg_relative_commands_match_absolute() {
+        let absolute = parse_svg_path("M 1 1 L 3 1 H 5 V 4 L 1 4 Z").unwrap();
+        let relative = parse_svg_path("m 1 1 l 2 0 h 2 v 3 l -4 0 z").unwrap();
+        assert_points_eq(
+            &absolute,
+            &[(1.0, 1.0), (3.0, 1.0), (5.0, 1.0), (5.0, 4.0), (1.0, 4.0)],
+        );
+        assert_points_eq(&absolute, &relative);
+    }
+
+    #[test]
+    fn svg_arguments_after_move_are_lines() {
+        let implicit = parse_svg_path("M 0 0 10 0 10 10").unwrap();
+        assert_points_eq(&implicit, &[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+        let relative = parse_svg_path("m 1 1 2 0 0 3").unwrap();
+        assert_points_eq(&relative, &[(1.0, 1.0), (3.0, 1.0), (3.0, 4.0)]);
+    }
+
+    #[test]
+    fn svg_smooth_curves_reflect_the_last_control_point() {
+        let smooth = parse_svg_path("M 0 0 C 0 1 1 1 1 0 S 2 -1 2 0").unwrap();
+        let explicit = parse_svg_path("M 0 0 C 0 1 1 1 1 0 C 1 -1 2 -1 2 0").unwrap();
+        assert_points_eq(&smooth, &explicit);
+
+        let smooth = parse_svg_path("M 0 0 Q 1 1 2 0 T 4 0").unwrap();
+        let explicit = parse_svg_path("M 0 0 Q 1 1 2 0 Q 3 -1 4 0").unwrap();
+        assert_points_eq(&smooth, &explicit);
+
+        // Without a preceding curve the control point is the current point
+        let alone = parse_svg_path("M 0 0 T 2 0").unwrap();
+        assert!(alone.iter().all(|p| p.1.abs() < 1e-12));
+    }
+
+    #[test]
+    fn svg_numbers_with_exponents_and_packed_signs() {
+        let points = parse_svg_path("M1e-3,0L2E1 0L-1.5e+1 3L0-1e-3").unwrap();
+        assert_points_eq(
+            &points,
+            &[(0.001, 0.0), (20.0, 0.0), (-15.0, 3.0), (0.0, -0.001)],
+        );
+    }
+
+    #[test]
+    fn svg_arcs_are_rejected() {
+        let error = parse_svg_path("M 0 0 A 1 1 0 0 1 2 0").unwrap_err();
+        assert!(error.contains("'A'"), "{}", error);
+        assert!(parse_svg_path("10 0").is_err());
+        assert!(parse_svg_path("M 0").is_err());
+    }
+
+    #[test]
+    fn clockwise_outlines_are_reversed() {
+        let clockwise = vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)];
+        assert!(twice_area(&clockwise) < 0.0);
+        let profile = Profile::from_points(clockwise.clone()).unwrap();
+        assert!(twice_area(profile.points()) > 0.0);
+        let mut reversed = clockwise;
+        reversed.reverse();
+        assert_points_eq(profile.points(), &reversed);
+
+        // Clockwise on screen, where y points down, is counter-clockwise
+        // in the ring plane; this one turns the other way and is reversed
+        let svg = Profile::from_svg_path("M 0 0 L 1 0 L 1 1 L 0 1 Z").unwrap();
+        assert!(twice_area(svg.points()) > 0.0);
+        assert_eq!(svg.len(), 4);
+    }
+}
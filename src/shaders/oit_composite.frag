@@ -0,0 +1,18 @@
+// Weighted sums can exceed the mediump range
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+precision highp float;
+#else
+precision mediump float;
+#endif
+
+uniform sampler2D u_accum;
+uniform sampler2D u_reveal;
+uniform vec2 u_size;
+
+void main() {
+    vec2 uv = gl_FragCoord.xy / u_size;
+    vec4 accum = texture2D(u_accum, uv);
+    float reveal = texture2D(u_reveal, uv).r;
+    // Weighted average colour, covering what the product of (1 - alpha) hides
+    gl_FragColor = vec4(accum.rgb / max(accum.a, 1e-5), 1.0 - reveal);
+}
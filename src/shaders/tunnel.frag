@@ -0,0 +1,140 @@
+precision mediump float;
+
+#define MAX_POINT_LIGHTS 4
+
+varying vec4 v_color;
+varying vec3 v_normal;
+varying vec2 v_uv;
+varying vec3 v_world;
+varying float v_depth;
+varying float v_scalar;
+
+// Blinn-Phong. Lines have no surface to light and skip it.
+uniform float u_lighting;
+uniform vec3 u_eye;
+uniform float u_ambient;
+uniform float u_diffuse;
+uniform float u_specular;
+uniform float u_shininess;
+uniform float u_headlight;
+uniform float u_rim;
+uniform int u_point_light_count;
+uniform vec3 u_point_light_position[MAX_POINT_LIGHTS];
+uniform vec3 u_point_light_color[MAX_POINT_LIGHTS];
+
+// Fog by view-space depth. Mode 0 is off, 1 linear, 2 exponential.
+uniform int u_fog_mode;
+uniform vec3 u_fog_color;
+uniform float u_fog_density;
+uniform float u_fog_start;
+uniform float u_fog_end;
+
+// Palette animation: colours looked up again from a wrapping 1D palette
+// texture at the vertex's palette position, shifted over time
+uniform float u_palette_animation;
+uniform sampler2D u_palette;
+uniform float u_palette_repeat;
+uniform float u_palette_offset;
+uniform float u_pulse_position;
+uniform float u_pulse_width;
+uniform float u_pulse_strength;
+
+// Wall texture from a video, image or canvas, wrapped by the mesh UVs.
+// Tiling wraps in the shader; non-power-of-two textures can't repeat.
+uniform sampler2D u_texture;
+uniform float u_texture_mix;
+uniform vec2 u_texture_tiling;
+uniform vec2 u_texture_offset;
+
+// Weighted blended OIT. Pass 0 draws normally, 1 accumulates weighted
+// premultiplied colour, 2 multiplies up the revealage.
+uniform int u_oit_pass;
+
+// Diffuse and specular contribution of one light
+vec2 blinn_phong(vec3 n, vec3 v, vec3 l) {
+    vec3 h = normalize(l + v);
+    float diffuse = max(dot(n, l), 0.0);
+    float specular = diffuse > 0.0 ? pow(max(dot(n, h), 0.0), u_shininess) : 0.0;
+    return vec2(diffuse, specular);
+}
+
+// Fraction of the surface that shows through the fog
+float fog_visibility(float depth) {
+    if (u_fog_mode == 1) {
+        return clamp((u_fog_end - depth) / max(u_fog_end - u_fog_start, 1e-4), 0.0, 1.0);
+    }
+    if (u_fog_mode == 2) {
+        return exp(-u_fog_density * max(depth, 0.0));
+    }
+    return 1.0;
+}
+
+// Nearer layers count for more (McGuire & Bavoil 2013, eq. 7)
+float oit_weight(float depth, float alpha) {
+    float d = depth / 5.0;
+    float far = depth / 200.0;
+    return alpha * clamp(10.0 / (1e-5 + d * d + far * far * far * far * far * far), 1e-2, 3e3);
+}
+
+vec3 shade(vec3 base) {
+    vec3 to_eye = u_eye - v_world;
+    vec3 v = normalize(to_eye);
+    // Walls are seen from both sides; light the side facing the camera
+    vec3 n = normalize(v_normal);
+    if (dot(n, v) < 0.0) {
+        n = -n;
+    }
+
+    // Headlight sits on the camera
+    vec2 head = blinn_phong(n, v, v) * u_headlight;
+    vec3 diffuse = vec3(head.x);
+    vec3 specular = vec3(head.y);
+
+    for (int i = 0; i < MAX_POINT_LIGHTS; i++) {
+        if (i >= u_point_light_count) {
+            break;
+        }
+        vec3 to_light = u_point_light_position[i] - v_world;
+        float falloff = 1.0 / (1.0 + 0.05 * dot(to_light, to_light));
+        vec2 lit = blinn_phong(n, v, normalize(to_light)) * falloff;
+        diffuse += lit.x * u_point_light_color[i];
+        specular += lit.y * u_point_light_color[i];
+    }
+
+    float rim = u_rim * pow(1.0 - max(dot(n, v), 0.0), 3.0);
+    return base * (u_ambient + u_diffuse * diffuse)
+        + u_specular * specular
+        + rim;
+}
+
+// Palette colour plus a bright pulse at u_pulse_position along the tunnel
+vec3 animated_color() {
+    vec2 st = vec2(v_scalar * u_palette_repeat - u_palette_offset, 0.5);
+    vec3 color = texture2D(u_palette, st).rgb;
+    // Signed distance to the pulse, wrapping around the tunnel
+    float d = fract(v_scalar - u_pulse_position + 0.5) - 0.5;
+    float x = d / max(u_pulse_width, 1e-4);
+    return color * (1.0 + u_pulse_strength * exp(-x * x));
+}
+
+void main() {
+    vec3 base = u_palette_animation < 0.5 ? v_color.rgb : animated_color();
+    if (u_texture_mix > 0.0) {
+        vec2 st = fract(v_uv * u_texture_tiling + u_texture_offset);
+        base = mix(base, texture2D(u_texture, st).rgb, u_texture_mix);
+    }
+    vec3 rgb = u_lighting < 0.5 ? base : shade(base);
+
+    // Fog only the colour: opaque walls still write depth, so fading their
+    // alpha would show the background without what they hide
+    float visibility = fog_visibility(v_depth);
+    vec4 color = vec4(mix(u_fog_color, rgb, visibility), v_color.a);
+
+    if (u_oit_pass == 1) {
+        gl_FragColor = vec4(color.rgb * color.a, color.a) * oit_weight(v_depth, color.a);
+    } else if (u_oit_pass == 2) {
+        gl_FragColor = vec4(color.a);
+    } else {
+        gl_FragColor = color;
+    }
+}
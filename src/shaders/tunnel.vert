@@ -0,0 +1,27 @@
+attribute vec3 position;
+attribute vec4 color;
+attribute vec3 normal;
+attribute vec2 uv;
+attribute float scalar;
+uniform mat4 u_projection;
+uniform mat4 u_view;
+uniform vec4 u_color;
+uniform float u_use_vertex_color;
+uniform float u_alpha_override;
+varying vec4 v_color;
+varying vec3 v_normal;
+varying vec2 v_uv;
+varying vec3 v_world;
+varying float v_depth;
+varying float v_scalar;
+void main() {
+    vec4 view_position = u_view * vec4(position, 1.0);
+    gl_Position = u_projection * view_position;
+    vec4 base_color = mix(u_color, color, u_use_vertex_color);
+    v_color = vec4(base_color.rgb, base_color.a * u_alpha_override);
+    v_normal = normal;
+    v_uv = uv;
+    v_world = position;
+    v_depth = -view_position.z;
+    v_scalar = scalar;
+}
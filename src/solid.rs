@@ -0,0 +1,337 @@
+use crate::analysis::proximity_ranges;
+use crate::arclength::ArcLengthTable;
+use crate::color::Palette;
+use crate::curve::ParametricCurve;
+use crate::frames::CurveFrames;
+use crate::math::V3D;
+use crate::mesh::{ring_matrix, ring_parameters, Mesh, TunnelOptions, Vertex};
+
+/// A closed tube wall ready for slicing, plus where it runs into itself
+pub struct SolidTube {
+    pub mesh: Mesh,
+    /// Parameter ranges where the tube collides with another part of itself
+    pub self_intersections: Vec<(f64, f64)>,
+}
+
+/// Sweep the profile as a solid wall `thickness` thick: the outer shell is
+/// the profile itself, the inner shell is offset inwards. Open curves get
+/// flat annular caps joining the shells.
+///
+/// Every vertex is shared by all faces around it, so each edge belongs to
+/// exactly two triangles, and all faces point out of the material as STL
+/// expects. UVs are not seamless; this mesh is meant for printing.
+///
+/// `thickness` must be positive and less than the distance from the curve
+/// to the nearest edge of the profile at every ring, after the scale
+/// tracks; a thicker wall would turn the inner shell inside out.
+pub fn generate_solid_tube<C: ParametricCurve + ?Sized>(
+    curve: &C,
+    options: &TunnelOptions,
+    thickness: f64,
+) -> Result<SolidTube, String> {
+    if thickness.is_nan() || thickness <= 0.0 {
+        return Err("wall thickness must be positive".into());
+    }
+    let points = options.profile.points();
+    let sides = points.len();
+    let num_polygons = options.num_polygons;
+    let closed = curve.is_closed();
+    let frames = CurveFrames::new(curve, options.frame_mode);
+    let arc = ArcLengthTable::new(curve);
+    // STL has no colour; OBJ exports of the solid get the default rings
+    let palette = Palette::default();
+
+    // A closed loop reuses ring 0 instead of a coincident closing ring
+    let mut ring_params = ring_parameters(curve, options, &arc);
+    if closed {
+        ring_params.pop();
+    }
+    let ring_count = ring_params.len();
+
+    // Ring i holds its outer vertices, then its inner ones
+    let outer = |i: usize, j: usize| (2 * sides * i + j % sides) as u32;
+    let inner = |i: usize, j: usize| (2 * sides * i + sides + j % sides) as u32;
+
+    let mut vertices = Vec::with_capacity(2 * sides * ring_count);
+    let mut max_thickness = f64::INFINITY;
+    let mut corners_flip = false;
+    for (i, &t) in ring_params.iter().enumerate() {
+        let scale = options.radius_scale.at(t);
+        let (sx, sy) = (scale * options.scale_x.at(t), scale * options.scale_y.at(t));
+        let matrix = ring_matrix(
+            frames.transform_matrix(curve, t),
+            1.0,
+            1.0,
+            options.twist.at(t),
+        );
+
+        // Scale first so the wall keeps its thickness in world units
+        let mut scaled: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (sx * x, sy * y)).collect();
+        // A mirroring scale turns the outline clockwise, which would turn
+        // the faces inwards. Reverse it, keeping point 0 so the rings
+        // still line up.
+        if sx * sy < 0.0 {
+            scaled[1..].reverse();
+        }
+        max_thickness = max_thickness.min(inset_limit(&scaled));
+        let offset = inset(&scaled, thickness);
+        corners_flip |= edges_reversed(&scaled, &offset);
+
+        let scalar = i as f32 / num_polygons as f32;
+        let rgb = palette.at(scalar);
+        let v = (i as f32) / (num_polygons as f32);
+        for shell in [&scaled, &offset] {
+            for (j, &(x, y)) in shell.iter().enumerate() {
+                let p = V3D::new(x, y, 0.0).transform(&matrix);
+                vertices.push(Vertex {
+                    pos: [p.x as f32, p.y as f32, p.z as f32],
+                    color: [rgb[0], rgb[1], rgb[2], 1.0],
+                    normal: [0.0; 3],
+                    uv: [(j as f32) / (sides as f32), v],
+                    scalar,
+                });
+            }
+        }
+    }
+
+    if thickness >= max_thickness {
+        return Err(format!(
+            "wall thickness {} must be less than {}, the closest the profile comes to the curve",
+            thickness, max_thickness
+        ));
+    }
+    if corners_flip {
+        return Err(format!(
+            "wall thickness {} is too much for the profile's corners",
+            thickness
+        ));
+    }
+
+    let mut triangles = Vec::new();
+    for i in 0..num_polygons {
+        let next = (i + 1) % ring_count;
+        for j in 0..sides {
+            // Outer shell faces away from the curve
+            let (a, b, c, d) = (
+                outer(i, j),
+                outer(next, j),
+                outer(i, j + 1),
+                outer(next, j + 1),
+            );
+            triangles.extend_from_slice(&[a, c, b, b, c, d]);
+            // Inner shell faces the hollow
+            let (a, b, c, d) = (
+                inner(i, j),
+                inner(next, j),
+                inner(i, j + 1),
+                inner(next, j + 1),
+            );
+            triangles.extend_from_slice(&[a, b, c, b, d, c]);
+        }
+    }
+
+    // Annular caps: the start faces back along the curve, the end forwards
+    if !closed {
+        for (ring, forward) in [(0, false), (ring_count - 1, true)] {
+            for j in 0..sides {
+                let (o0, o1) = (outer(ring, j), outer(ring, j + 1));
+                let (i0, i1) = (inner(ring, j), inner(ring, j + 1));
+                if forward {
+                    triangles.extend_from_slice(&[o0, o1, i1, o0, i1, i0]);
+                } else {
+                    triangles.extend_from_slice(&[o0, i1, o1, o0, i0, i1]);
+                }
+            }
+        }
+    }
+
+    // Area-weighted vertex normals follow the winding
+    let mut normals = vec![V3D::new(0.0, 0.0, 0.0); vertices.len()];
+    for tri in triangles.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|k| {
+            let p = vertices[tri[k] as usize].pos;
+            V3D::new(p[0] as f64, p[1] as f64, p[2] as f64)
+        });
+        let n = (b - a).cross(&(c - a));
+        for &k in tri {
+            normals[k as usize] = normals[k as usize] + n;
+        }
+    }
+    for (vertex, n) in vertices.iter_mut().zip(normals) {
+        let n = n.normalize();
+        vertex.normal = [n.x as f32, n.y as f32, n.z as f32];
+    }
+
+    Ok(SolidTube {
+        mesh: Mesh {
+            vertices,
+            triangles,
+            long_lines: Vec::new(),
+            lat_lines: Vec::new(),
+        },
+        self_intersections: proximity_ranges(curve, options),
+    })
+}
+
+// Distance from the curve, at the origin of the ring plane, to the nearest
+// edge of a counter-clockwise outline: how far it can be inset
+fn inset_limit(points: &[(f64, f64)]) -> f64 {
+    let n = points.len();
+    (0..n)
+        .map(|j| {
+            let (a, b) = (points[j], points[(j + 1) % n]);
+            let (ex, ey) = (b.0 - a.0, b.1 - a.1);
+            let length2 = ex * ex + ey * ey;
+            let f = if length2 > 0.0 {
+                (-(a.0 * ex + a.1 * ey) / length2).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (a.0 + ex * f).hypot(a.1 + ey * f)
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+// Whether any edge of the inset outline runs backwards, as it does where
+// neighbouring corners have been pushed past each other
+fn edges_reversed(outline: &[(f64, f64)], inset: &[(f64, f64)]) -> bool {
+    let n = outline.len();
+    (0..n).any(|j| {
+        let k = (j + 1) % n;
+        let e = (outline[k].0 - outline[j].0, outline[k].1 - outline[j].1);
+        let f = (inset[k].0 - inset[j].0, inset[k].1 - inset[j].1);
+        e.0 * f.0 + e.1 * f.1 <= 0.0
+    })
+}
+
+// Move each point of a counter-clockwise outline `distance` towards the
+// inside along its mitred vertex normal
+fn inset(points: &[(f64, f64)], distance: f64) -> Vec<(f64, f64)> {
+    let n = points.len();
+    let inward = |from: (f64, f64), to: (f64, f64)| {
+        let (ex, ey) = (to.0 - from.0, to.1 - from.1);
+        let len = ex.hypot(ey).max(f64::EPSILON);
+        (-ey / len, ex / len)
+    };
+
+    (0..n)
+        .map(|j| {
+            let p = points[j];
+            let before = inward(points[(j + n - 1) % n], p);
+            let after = inward(p, points[(j + 1) % n]);
+            let (mx, my) = (before.0 + after.0, before.1 + after.1);
+            let len = mx.hypot(my);
+            let (mx, my) = if len > 1e-9 {
+                (mx / len, my / len)
+            } else {
+                after
+            };
+            // Sharp corners would shoot the miter far away; cap it at 4x
+            let reach = distance / (mx * after.0 + my * after.1).max(0.25);
+            (p.0 + mx * reach, p.1 + my * reach)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lisa::Lissajou3D;
+    use crate::profile::Profile;
+    use crate::track::Track;
+    use std::collections::HashMap;
+
+    // One turn of a helix: open, and short enough to sweep with few rings
+    struct Helix;
+
+    impl ParametricCurve for Helix {
+        fn position(&self, t: f64) -> V3D {
+            V3D::new(10.0 * t.cos(), 10.0 * t.sin(), 2.0 * t)
+        }
+
+        fn tangent(&self, t: f64) -> V3D {
+            V3D::new(-10.0 * t.sin(), 10.0 * t.cos(), 2.0).normalize()
+        }
+
+        fn is_closed(&self) -> bool {
+            false
+        }
+    }
+
+    // Checks the mesh is closed and consistently wound, and returns its volume
+    fn assert_manifold(mesh: &Mesh) -> f64 {
+        // Each directed edge once, and its reverse once
+        let mut edges: HashMap<(u32, u32), usize> = HashMap::new();
+        for tri in mesh.triangles.chunks_exact(3) {
+            for k in 0..3 {
+                *edges.entry((tri[k], tri[(k + 1) % 3])).or_default() += 1;
+            }
+        }
+        for (&(a, b), &count) in &edges {
+            assert_eq!(count, 1, "edge {}-{} used {} times one way", a, b, count);
+            assert_eq!(edges.get(&(b, a)), Some(&1), "edge {}-{} is open", a, b);
+        }
+
+        let volume: f64 = mesh
+            .triangles
+            .chunks_exact(3)
+            .map(|tri| {
+                let [a, b, c] = [0, 1, 2].map(|k| {
+                    let p = mesh.vertices[tri[k] as usize].pos;
+                    V3D::new(p[0] as f64, p[1] as f64, p[2] as f64)
+                });
+                a.dot(&b.cross(&c)) / 6.0
+            })
+            .sum();
+        assert!(volume > 0.0, "signed volume {}", volume);
+        volume
+    }
+
+    #[test]
+    fn closed_tube_is_manifold() {
+        let curve = Lissajou3D::new(3.0, 2.0, 10.0);
+        let options = TunnelOptions::new(1.0, 8, 60).unwrap();
+        let solid = generate_solid_tube(&curve, &options, 0.2).unwrap();
+        assert_manifold(&solid.mesh);
+    }
+
+    #[test]
+    fn open_tube_with_caps_is_manifold() {
+        let curve = Helix;
+        let mut options = TunnelOptions::new(1.0, 6, 60).unwrap();
+        options.profile = Profile::star(1.0, 0.6, 5).unwrap();
+        let solid = generate_solid_tube(&curve, &options, 0.1).unwrap();
+        assert_manifold(&solid.mesh);
+    }
+
+    #[test]
+    fn mirrored_profile_still_faces_out() {
+        let curve = Helix;
+        let mut options = TunnelOptions::new(1.0, 8, 40).unwrap();
+        // The star isn't symmetric about the binormal, so mirroring changes it
+        options.profile = Profile::star(1.0, 0.6, 5).unwrap();
+        let plain = assert_manifold(&generate_solid_tube(&curve, &options, 0.1).unwrap().mesh);
+        options.scale_x = Track::Constant(-1.0);
+        let mirrored = assert_manifold(&generate_solid_tube(&curve, &options, 0.1).unwrap().mesh);
+        // Same wall, just mirrored; an inset towards the outside would
+        // give a thicker one
+        assert!(
+            (mirrored - plain).abs() < 0.01 * plain,
+            "{} vs {}",
+            mirrored,
+            plain
+        );
+    }
+
+    #[test]
+    fn walls_must_fit_inside_the_profile() {
+        let curve = Lissajou3D::new(3.0, 2.0, 10.0);
+        let options = TunnelOptions::new(1.0, 8, 20).unwrap();
+        assert!(generate_solid_tube(&curve, &options, 0.0).is_err());
+        assert!(generate_solid_tube(&curve, &options, -0.1).is_err());
+        assert!(generate_solid_tube(&curve, &options, f64::NAN).is_err());
+        // The octagon's edges are cos(π/8) from its centre
+        assert!(generate_solid_tube(&curve, &options, 0.92).is_ok());
+        assert!(generate_solid_tube(&curve, &options, 0.93).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use web_sys::{
+    HtmlCanvasElement, HtmlImageElement, HtmlVideoElement, WebGlRenderingContext as GL,
+    WebGlTexture,
+};
+
+// HTMLMediaElement.HAVE_CURRENT_DATA: a frame is available to draw
+const HAVE_CURRENT_DATA: u16 = 2;
+
+/// Where the wall texture's pixels come from
+pub(crate) enum TextureSource {
+    /// Re-uploaded every frame while it has data
+    Video(HtmlVideoElement),
+    /// Uploaded once, as soon as it has loaded
+    Image(HtmlImageElement),
+    /// Re-uploaded every frame, so it can be drawn into live
+    Canvas(HtmlCanvasElement),
+}
+
+/// A texture streamed from a page element and wrapped onto the tunnel
+/// walls through the mesh UVs: u around the profile, v along the curve.
+///
+/// Video frames are rarely power-of-two sized, which WebGL1 can only sample
+/// clamped and without mipmaps, so tiling wraps the UVs in the shader.
+pub(crate) struct WallTexture {
+    pub(crate) texture: WebGlTexture,
+    source: Option<TextureSource>,
+    // Whether the source's pixels have reached the GPU
+    uploaded: bool,
+    /// Repeats around the profile and along the curve
+    pub(crate) tiling: [f32; 2],
+    /// UV units per second
+    pub(crate) scroll: [f32; 2],
+    /// Accumulated scroll, kept in [0, 1)
+    pub(crate) offset: [f32; 2],
+    /// 0 keeps the ring colours, 1 shows only the texture
+    pub(crate) mix: f32,
+}
+
+impl WallTexture {
+    pub(crate) fn new(gl: &GL) -> Result<Self, String> {
+        let texture = gl.create_texture().ok_or("unable to create texture")?;
+        gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::LINEAR as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::CLAMP_TO_EDGE as i32);
+        gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+        gl.bind_texture(GL::TEXTURE_2D, None);
+
+        Ok(Self {
+            texture,
+            source: None,
+            uploaded: false,
+            tiling: [1.0, 1.0],
+            scroll: [0.0, 0.0],
+            offset: [0.0, 0.0],
+            mix: 1.0,
+        })
+    }
+
+    pub(crate) fn set_source(&mut self, source: Option<TextureSource>) {
+        self.source = source;
+        self.uploaded = false;
+    }
+
+    /// Whether the walls should sample the texture this frame
+    pub(crate) fn active(&self) -> bool {
+        self.source.is_some() && self.uploaded && self.mix > 0.0
+    }
+
+    /// Advance the scroll by `delta_time` seconds and copy the source's
+    /// current pixels to the GPU if they may have changed
+    pub(crate) fn update(&mut self, gl: &GL, delta_time: f32) {
+        for (offset, speed) in self.offset.iter_mut().zip(self.scroll) {
+            *offset = (*offset + speed * delta_time).rem_euclid(1.0);
+        }
+
+        let (ready, stale) = match &self.source {
+            None => return,
+            Some(TextureSource::Video(video)) => (video.ready_state() >= HAVE_CURRENT_DATA, true),
+            Some(TextureSource::Image(image)) => (
+                image.complete() && image.natural_width() > 0,
+                !self.uploaded,
+            ),
+            Some(TextureSource::Canvas(canvas)) => {
+                (canvas.width() > 0 && canvas.height() > 0, true)
+            }
+        };
+        if !ready || !stale {
+            return;
+        }
+
+        gl.bind_texture(GL::TEXTURE_2D, Some(&self.texture));
+        let level = 0;
+        let format = GL::RGBA;
+        let result = match self.source.as_ref() {
+            Some(TextureSource::Video(video)) => gl.tex_image_2d_with_u32_and_u32_and_video(
+                GL::TEXTURE_2D,
+                level,
+                format as i32,
+                format,
+                GL::UNSIGNED_BYTE,
+                video,
+            ),
+            Some(TextureSource::Image(image)) => gl.tex_image_2d_with_u32_and_u32_and_image(
+                GL::TEXTURE_2D,
+                level,
+                format as i32,
+                format,
+                GL::UNSIGNED_BYTE,
+                image,
+            ),
+            Some(TextureSource::Canvas(canvas)) => gl.tex_image_2d_with_u32_and_u32_and_canvas(
+                GL::TEXTURE_2D,
+                level,
+                format as i32,
+                format,
+                GL::UNSIGNED_BYTE,
+                canvas,
+            ),
+            None => Ok(()),
+        };
+        gl.bind_texture(GL::TEXTURE_2D, None);
+
+        match result {
+            Ok(()) => self.uploaded = true,
+            // Cross-origin media without CORS headers can't be uploaded
+            Err(e) => {
+                web_sys::console::warn_1(&format!("Unable to upload wall texture: {:?}", e).into());
+                self.set_source(None);
+            }
+        }
+    }
+}
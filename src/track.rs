@@ -0,0 +1,119 @@
+use std::fmt;
+use std::rc::Rc;
+
+/// A value that varies with the curve parameter t, such as the ring radius
+/// scale or twist angle along a tunnel
+#[derive(Clone)]
+pub enum Track {
+    Constant(f64),
+    /// `(t, value)` pairs sorted by t. Linear in between, held past the ends.
+    Keyframes(Vec<(f64, f64)>),
+    Function(Rc<dyn Fn(f64) -> f64>),
+}
+
+impl Track {
+    /// Keyframes in any order
+    pub fn keyframes(mut pairs: Vec<(f64, f64)>) -> Self {
+        pairs.sort_by(|p, q| p.0.total_cmp(&q.0));
+        Track::Keyframes(pairs)
+    }
+
+    pub fn function(f: impl Fn(f64) -> f64 + 'static) -> Self {
+        Track::Function(Rc::new(f))
+    }
+
+    /// Keyframes from interleaved `[t0, v0, t1, v1, ...]`. No pairs gives
+    /// `Constant(default)`.
+    pub fn from_flat(values: &[f64], default: f64) -> Result<Self, String> {
+        if !values.len().is_multiple_of(2) {
+            return Err("keyframes must come in (t, value) pairs".into());
+        }
+        if values.is_empty() {
+            return Ok(Track::Constant(default));
+        }
+        Ok(Track::keyframes(
+            values.chunks(2).map(|p| (p[0], p[1])).collect(),
+        ))
+    }
+
+    pub fn at(&self, t: f64) -> f64 {
+        match self {
+            Track::Constant(v) => *v,
+            Track::Function(f) => f(t),
+            Track::Keyframes(keys) => {
+                let (first, last) = match (keys.first(), keys.last()) {
+                    (Some(first), Some(last)) => (first, last),
+                    _ => return 0.0,
+                };
+                if t <= first.0 {
+                    return first.1;
+                }
+                if t >= last.0 {
+                    return last.1;
+                }
+                let j = keys.partition_point(|k| k.0 <= t);
+                let (t0, v0) = keys[j - 1];
+                let (t1, v1) = keys[j];
+                if t1 > t0 {
+                    v0 + (v1 - v0) * (t - t0) / (t1 - t0)
+                } else {
+                    v1
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Track::Constant(v) => f.debug_tuple("Constant").field(v).finish(),
+            Track::Keyframes(keys) => f.debug_tuple("Keyframes").field(keys).finish(),
+            Track::Function(_) => f.write_str("Function(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyframes_interpolate_linearly() {
+        let track = Track::keyframes(vec![(0.0, 1.0), (2.0, 3.0), (3.0, -1.0)]);
+        assert_eq!(track.at(0.0), 1.0);
+        assert_eq!(track.at(0.5), 1.5);
+        assert_eq!(track.at(2.0), 3.0);
+        assert_eq!(track.at(2.25), 2.0);
+        assert_eq!(track.at(3.0), -1.0);
+    }
+
+    #[test]
+    fn keyframes_hold_past_the_ends() {
+        let track = Track::keyframes(vec![(1.0, 4.0), (2.0, 6.0)]);
+        assert_eq!(track.at(-10.0), 4.0);
+        assert_eq!(track.at(0.999), 4.0);
+        assert_eq!(track.at(2.001), 6.0);
+        assert_eq!(track.at(1e9), 6.0);
+        assert_eq!(Track::keyframes(Vec::new()).at(1.0), 0.0);
+    }
+
+    #[test]
+    fn unsorted_keyframes_are_sorted() {
+        let track = Track::keyframes(vec![(3.0, 0.0), (1.0, 2.0), (2.0, 4.0)]);
+        assert_eq!(track.at(1.5), 3.0);
+        assert_eq!(track.at(2.5), 2.0);
+        let flat = Track::from_flat(&[3.0, 0.0, 1.0, 2.0, 2.0, 4.0], 0.0).unwrap();
+        assert_eq!(flat.at(1.5), 3.0);
+        assert_eq!(flat.at(2.5), 2.0);
+    }
+
+    #[test]
+    fn from_flat_needs_pairs() {
+        assert!(Track::from_flat(&[0.0, 1.0, 2.0], 0.0).is_err());
+        match Track::from_flat(&[], 7.0) {
+            Ok(Track::Constant(v)) => assert_eq!(v, 7.0),
+            other => panic!("expected the default constant, got {:?}", other),
+        }
+    }
+}
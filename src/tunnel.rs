@@ -0,0 +1,1813 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Event, HtmlCanvasElement, HtmlImageElement, HtmlVideoElement, PointerEvent, WebGlBuffer,
+    WebGlProgram, WebGlRenderingContext as GL, WebGlTexture, WebGlUniformLocation, WheelEvent,
+};
+
+use crate::analysis::{analyze_tunnel, proximity_ranges, TunnelReport};
+use crate::arclength::{ArcLengthTable, Parameterization};
+use crate::camera::{self, CameraMode, CameraPose, CameraTransition};
+use crate::color::{Colormap, Gradient, Palette, PaletteAnimation};
+use crate::export::{self, CameraTrack};
+use crate::fog::{Fog, FogMode};
+use crate::frames::{CurveFrames, FrameMode};
+use crate::gl::{attrib_location, compile_shader, link_program};
+use crate::lighting::{Lighting, PointLight, MAX_POINT_LIGHTS};
+use crate::lisa::Lissajou3D;
+use crate::math::{Mat4, V3D};
+use crate::mesh::{generate_tunnel_mesh, Mesh, TunnelOptions, Vertex};
+use crate::metric::ColorMetric;
+use crate::oit::{OitTargets, Transparency};
+use crate::orbit::{Drag, OrbitCamera};
+use crate::profile::Profile;
+use crate::solid::generate_solid_tube;
+use crate::texture::{TextureSource, WallTexture};
+use crate::track::Track;
+use crate::wire::{generate_wire_grid, WireGrid};
+
+// Perspective projection shared by the renderer and the glTF camera
+const FIELD_OF_VIEW: f32 = std::f32::consts::PI / 4.0;
+const NEAR: f32 = 0.1;
+const FAR: f32 = 1000.0;
+
+// Curve samples for the bounding sphere the orbit camera frames
+const BOUNDS_SAMPLES: usize = 256;
+// Orbit camera elevation before anyone drags it, about the old fixed
+// orbit's 5 up for 15 out
+const ORBIT_PITCH: f64 = 0.32;
+
+// Seconds a switch between camera modes takes
+const TRANSITION_DURATION: f64 = 1.5;
+
+// Sides of each grid tube
+const WIRE_SIDES: usize = 6;
+
+// Texels in the palette lookup texture, and the unit it is bound to; the
+// wall texture uses unit 0
+const PALETTE_SIZE: i32 = 256;
+const PALETTE_UNIT: u32 = 2;
+
+// Per-tunnel settings driven from JS
+struct TunnelState {
+    speed: f32,
+    time: f32,
+    show_longitude: bool,
+    show_latitude: bool,
+    show_tunnel: bool,
+    camera_mode: CameraMode,
+    // Seconds to blend between camera modes; 0 cuts
+    transition_duration: f64,
+    // Chase camera offset behind and above the curve, in tube radii
+    chase_distance: f64,
+    chase_height: f64,
+    camera_travel: Parameterization,
+    // Diameter of the grid tubes; 0 draws the grid with GL_LINES
+    wire_width: f32,
+    lighting: Lighting,
+    fog: Fog,
+    transparency: Transparency,
+    // Alpha of the tunnel walls
+    wall_opacity: f32,
+    palette: Palette,
+    palette_animation: PaletteAnimation,
+    // Accumulated palette shift and pulse position, both in [0, 1)
+    palette_offset: f32,
+    pulse_position: f32,
+}
+
+impl Default for TunnelState {
+    fn default() -> Self {
+        Self {
+            speed: 0.02,
+            time: 0.0,
+            show_longitude: true,
+            show_latitude: true,
+            show_tunnel: true,
+            camera_mode: CameraMode::default(),
+            transition_duration: TRANSITION_DURATION,
+            chase_distance: 6.0,
+            chase_height: 2.0,
+            camera_travel: Parameterization::default(),
+            wire_width: 0.0,
+            lighting: Lighting::default(),
+            fog: Fog::default(),
+            transparency: Transparency::default(),
+            wall_opacity: 0.05,
+            palette: Palette::default(),
+            palette_animation: PaletteAnimation::default(),
+            palette_offset: 0.0,
+            pulse_position: 0.0,
+        }
+    }
+}
+
+// Everything one canvas needs to draw a frame
+struct Tunnel {
+    gl: GL,
+    state: TunnelState,
+    lisa: Lissajou3D,
+    options: TunnelOptions,
+    frames: CurveFrames,
+    arc: ArcLengthTable,
+    // Outside view camera
+    orbit: OrbitCamera,
+    // Blend in progress after a camera mode switch
+    transition: Option<CameraTransition>,
+    // Pose of the last frame drawn, where the next transition starts
+    last_pose: CameraPose,
+    // Fog strength of the last frame drawn and at the start of the transition
+    last_fog: f32,
+    transition_fog: f32,
+    // Set when `options` changed and the buffers hold a stale mesh
+    mesh_dirty: bool,
+    // Set when the palette texture no longer matches `state.palette`
+    palette_dirty: bool,
+
+    program: WebGlProgram,
+    vertex_buffer: WebGlBuffer,
+    tri_buffer: WebGlBuffer,
+    long_buffer: WebGlBuffer,
+    lat_buffer: WebGlBuffer,
+    wire_vertex_buffer: WebGlBuffer,
+    wire_long_buffer: WebGlBuffer,
+    wire_lat_buffer: WebGlBuffer,
+
+    pos_attrib: u32,
+    color_attrib: u32,
+    // Unused attributes are compiled out of the shader and have no location
+    normal_attrib: Option<u32>,
+    uv_attrib: Option<u32>,
+    scalar_attrib: Option<u32>,
+    view_uniform: WebGlUniformLocation,
+    use_vertex_color_uniform: WebGlUniformLocation,
+    alpha_override_uniform: WebGlUniformLocation,
+    lighting_uniforms: LightingUniforms,
+    fog_uniforms: FogUniforms,
+    oit_pass_uniform: Option<WebGlUniformLocation>,
+    texture_uniforms: TextureUniforms,
+    wall_texture: WallTexture,
+    palette_uniforms: PaletteUniforms,
+    palette_texture: WebGlTexture,
+    // Created on first use of `Transparency::WeightedBlended`
+    oit: Option<OitTargets>,
+
+    // Mesh element counts for dynamic updates
+    cached_tri_count: usize,
+    cached_long_count: usize,
+    cached_lat_count: usize,
+    cached_wire_long_count: usize,
+    cached_wire_lat_count: usize,
+
+    // Track time for proper delta calculation
+    last_timestamp: f64,
+}
+
+// Locations of the lighting uniforms; `None` where the compiler dropped one
+struct LightingUniforms {
+    lighting: Option<WebGlUniformLocation>,
+    eye: Option<WebGlUniformLocation>,
+    ambient: Option<WebGlUniformLocation>,
+    diffuse: Option<WebGlUniformLocation>,
+    specular: Option<WebGlUniformLocation>,
+    shininess: Option<WebGlUniformLocation>,
+    headlight: Option<WebGlUniformLocation>,
+    rim: Option<WebGlUniformLocation>,
+    point_light_count: Option<WebGlUniformLocation>,
+    point_light_position: Option<WebGlUniformLocation>,
+    point_light_color: Option<WebGlUniformLocation>,
+}
+
+impl LightingUniforms {
+    fn new(gl: &GL, program: &WebGlProgram) -> Self {
+        let location = |name: &str| gl.get_uniform_location(program, name);
+        Self {
+            lighting: location("u_lighting"),
+            eye: location("u_eye"),
+            ambient: location("u_ambient"),
+            diffuse: location("u_diffuse"),
+            specular: location("u_specular"),
+            shininess: location("u_shininess"),
+            headlight: location("u_headlight"),
+            rim: location("u_rim"),
+            point_light_count: location("u_point_light_count"),
+            point_light_position: location("u_point_light_position"),
+            point_light_color: location("u_point_light_color"),
+        }
+    }
+
+    fn upload(&self, gl: &GL, lighting: &Lighting, eye: [f32; 3], lights: &[[f32; 3]]) {
+        gl.uniform3fv_with_f32_array(self.eye.as_ref(), &eye);
+        gl.uniform1f(self.ambient.as_ref(), lighting.ambient);
+        gl.uniform1f(self.diffuse.as_ref(), lighting.diffuse);
+        gl.uniform1f(self.specular.as_ref(), lighting.specular);
+        gl.uniform1f(self.shininess.as_ref(), lighting.shininess);
+        gl.uniform1f(self.headlight.as_ref(), lighting.headlight);
+        gl.uniform1f(self.rim.as_ref(), lighting.rim);
+
+        let count = lights.len().min(MAX_POINT_LIGHTS);
+        gl.uniform1i(self.point_light_count.as_ref(), count as i32);
+        if count > 0 {
+            let positions: Vec<f32> = lights[..count].iter().flatten().copied().collect();
+            let colors: Vec<f32> = lighting.point_lights[..count]
+                .iter()
+                .flat_map(|light| light.color)
+                .collect();
+            gl.uniform3fv_with_f32_array(self.point_light_position.as_ref(), &positions);
+            gl.uniform3fv_with_f32_array(self.point_light_color.as_ref(), &colors);
+        }
+    }
+
+    // Lines have no surface to shade
+    fn enable(&self, gl: &GL, enabled: bool) {
+        gl.uniform1f(self.lighting.as_ref(), if enabled { 1.0 } else { 0.0 });
+    }
+}
+
+// Locations of the fog uniforms
+struct FogUniforms {
+    mode: Option<WebGlUniformLocation>,
+    color: Option<WebGlUniformLocation>,
+    density: Option<WebGlUniformLocation>,
+    start: Option<WebGlUniformLocation>,
+    end: Option<WebGlUniformLocation>,
+}
+
+impl FogUniforms {
+    fn new(gl: &GL, program: &WebGlProgram) -> Self {
+        let location = |name: &str| gl.get_uniform_location(program, name);
+        Self {
+            mode: location("u_fog_mode"),
+            color: location("u_fog_color"),
+            density: location("u_fog_density"),
+            start: location("u_fog_start"),
+            end: location("u_fog_end"),
+        }
+    }
+
+    fn upload(&self, gl: &GL, fog: &Fog) {
+        let mode = match fog.mode {
+            FogMode::Off => 0,
+            FogMode::Linear => 1,
+            FogMode::Exponential => 2,
+        };
+        gl.uniform1i(self.mode.as_ref(), mode);
+        gl.uniform3fv_with_f32_array(self.color.as_ref(), &fog.color);
+        gl.uniform1f(self.density.as_ref(), fog.density);
+        gl.uniform1f(self.start.as_ref(), fog.start);
+        gl.uniform1f(self.end.as_ref(), fog.end);
+    }
+}
+
+// Locations of the wall texture uniforms
+struct TextureUniforms {
+    sampler: Option<WebGlUniformLocation>,
+    mix: Option<WebGlUniformLocation>,
+    tiling: Option<WebGlUniformLocation>,
+    offset: Option<WebGlUniformLocation>,
+}
+
+impl TextureUniforms {
+    fn new(gl: &GL, program: &WebGlProgram) -> Self {
+        let location = |name: &str| gl.get_uniform_location(program, name);
+        Self {
+            sampler: location("u_texture"),
+            mix: location("u_texture_mix"),
+            tiling: location("u_texture_tiling"),
+            offset: location("u_texture_offset"),
+        }
+    }
+
+    // Sample `texture` on texture unit 0, or nothing
+    fn bind(&self, gl: &GL, texture: Option<&WallTexture>) {
+        match texture.filter(|texture| texture.active()) {
+            Some(texture) => {
+                gl.active_texture(GL::TEXTURE0);
+                gl.bind_texture(GL::TEXTURE_2D, Some(&texture.texture));
+                gl.uniform1i(self.sampler.as_ref(), 0);
+                gl.uniform1f(self.mix.as_ref(), texture.mix);
+                gl.uniform2fv_with_f32_array(self.tiling.as_ref(), &texture.tiling);
+                gl.uniform2fv_with_f32_array(self.offset.as_ref(), &texture.offset);
+            }
+            None => gl.uniform1f(self.mix.as_ref(), 0.0),
+        }
+    }
+}
+
+// Locations of the palette animation uniforms
+struct PaletteUniforms {
+    enabled: Option<WebGlUniformLocation>,
+    sampler: Option<WebGlUniformLocation>,
+    repeat: Option<WebGlUniformLocation>,
+    offset: Option<WebGlUniformLocation>,
+    pulse_position: Option<WebGlUniformLocation>,
+    pulse_width: Option<WebGlUniformLocation>,
+    pulse_strength: Option<WebGlUniformLocation>,
+}
+
+impl PaletteUniforms {
+    fn new(gl: &GL, program: &WebGlProgram) -> Self {
+        let location = |name: &str| gl.get_uniform_location(program, name);
+        Self {
+            enabled: location("u_palette_animation"),
+            sampler: location("u_palette"),
+            repeat: location("u_palette_repeat"),
+            offset: location("u_palette_offset"),
+            pulse_position: location("u_pulse_position"),
+            pulse_width: location("u_pulse_width"),
+            pulse_strength: location("u_pulse_strength"),
+        }
+    }
+
+    fn upload(&self, gl: &GL, state: &TunnelState, texture: &WebGlTexture) {
+        let animation = &state.palette_animation;
+        gl.uniform1f(
+            self.enabled.as_ref(),
+            if animation.enabled { 1.0 } else { 0.0 },
+        );
+        if !animation.enabled {
+            return;
+        }
+        gl.active_texture(GL::TEXTURE0 + PALETTE_UNIT);
+        gl.bind_texture(GL::TEXTURE_2D, Some(texture));
+        gl.active_texture(GL::TEXTURE0);
+        gl.uniform1i(self.sampler.as_ref(), PALETTE_UNIT as i32);
+        gl.uniform1f(self.repeat.as_ref(), animation.repeat);
+        gl.uniform1f(self.offset.as_ref(), state.palette_offset);
+        gl.uniform1f(self.pulse_position.as_ref(), state.pulse_position);
+        gl.uniform1f(self.pulse_width.as_ref(), animation.pulse_width);
+        gl.uniform1f(self.pulse_strength.as_ref(), animation.pulse_strength);
+    }
+}
+
+// A wrapping 1D texture for the palette lookups
+fn create_palette_texture(gl: &GL) -> Result<WebGlTexture, String> {
+    let texture = gl.create_texture().ok_or("unable to create texture")?;
+    gl.bind_texture(GL::TEXTURE_2D, Some(&texture));
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MIN_FILTER, GL::LINEAR as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_MAG_FILTER, GL::LINEAR as i32);
+    // Power-of-two wide, so it may repeat
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_S, GL::REPEAT as i32);
+    gl.tex_parameteri(GL::TEXTURE_2D, GL::TEXTURE_WRAP_T, GL::CLAMP_TO_EDGE as i32);
+    gl.bind_texture(GL::TEXTURE_2D, None);
+    Ok(texture)
+}
+
+impl Tunnel {
+    // The mesh for the current curve and options, as uploaded on the next frame
+    fn mesh(&self) -> Mesh {
+        generate_tunnel_mesh(&self.lisa, &self.options, &self.state.palette)
+    }
+
+    // The mesh for files: faces wound outward to agree with the normals
+    fn export_mesh(&self) -> Mesh {
+        let mut mesh = self.mesh();
+        mesh.flip_winding();
+        mesh
+    }
+
+    fn upload_mesh(&mut self, mesh: &Mesh) {
+        self.upload_vertices(&self.vertex_buffer, &mesh.vertices);
+        for (buffer, indices) in [
+            (&self.tri_buffer, &mesh.triangles),
+            (&self.long_buffer, &mesh.long_lines),
+            (&self.lat_buffer, &mesh.lat_lines),
+        ] {
+            self.upload_indices(buffer, indices);
+        }
+
+        self.cached_tri_count = mesh.triangles.len();
+        self.cached_long_count = mesh.long_lines.len();
+        self.cached_lat_count = mesh.lat_lines.len();
+
+        // The tube grid is only built when it will be drawn
+        let wire = if self.state.wire_width > 0.0 {
+            generate_wire_grid(mesh, self.state.wire_width as f64, WIRE_SIDES)
+        } else {
+            WireGrid {
+                vertices: Vec::new(),
+                longitude: Vec::new(),
+                latitude: Vec::new(),
+            }
+        };
+        self.upload_vertices(&self.wire_vertex_buffer, &wire.vertices);
+        for (buffer, indices) in [
+            (&self.wire_long_buffer, &wire.longitude),
+            (&self.wire_lat_buffer, &wire.latitude),
+        ] {
+            self.upload_indices(buffer, indices);
+        }
+        self.cached_wire_long_count = wire.longitude.len();
+        self.cached_wire_lat_count = wire.latitude.len();
+    }
+
+    // Switch camera mode, blending from the current pose
+    fn set_camera_mode(&mut self, mode: CameraMode) {
+        if mode == self.state.camera_mode {
+            return;
+        }
+        self.state.camera_mode = mode;
+        self.transition_fog = self.last_fog;
+        self.transition = (self.state.transition_duration > 0.0)
+            .then(|| CameraTransition::new(self.last_pose, self.state.transition_duration));
+    }
+
+    // Point the orbit camera at the whole curve and make that its home
+    fn frame_curve(&mut self) {
+        let (center, radius) = camera::bounding_sphere(&self.lisa, BOUNDS_SAMPLES);
+        let gl = &self.gl;
+        let aspect = gl.drawing_buffer_width() as f64 / gl.drawing_buffer_height().max(1) as f64;
+        self.orbit.frame_sphere(center, radius, aspect);
+    }
+
+    // A new palette only needs the lookup texture while the shader does the
+    // colouring; the baked vertex colours catch up when animation stops
+    fn recolor(&mut self) {
+        self.palette_dirty = true;
+        if !self.state.palette_animation.enabled {
+            self.mesh_dirty = true;
+        }
+    }
+
+    fn upload_palette(&self) {
+        let gl = &self.gl;
+        let texels = self.state.palette.texels(PALETTE_SIZE as usize);
+        gl.bind_texture(GL::TEXTURE_2D, Some(&self.palette_texture));
+        let _ = gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            GL::TEXTURE_2D,
+            0,
+            GL::RGBA as i32,
+            PALETTE_SIZE,
+            1,
+            0,
+            GL::RGBA,
+            GL::UNSIGNED_BYTE,
+            Some(&texels),
+        );
+        gl.bind_texture(GL::TEXTURE_2D, None);
+    }
+
+    fn upload_vertices(&self, buffer: &WebGlBuffer, vertices: &[Vertex]) {
+        // Flatten vertex data into interleaved format:
+        // [pos.xyz, color.rgba, normal.xyz, uv.uv, scalar]
+        let vertex_data: Vec<f32> = vertices
+            .iter()
+            .flat_map(|v| {
+                vec![
+                    v.pos[0],
+                    v.pos[1],
+                    v.pos[2],
+                    v.color[0],
+                    v.color[1],
+                    v.color[2],
+                    v.color[3],
+                    v.normal[0],
+                    v.normal[1],
+                    v.normal[2],
+                    v.uv[0],
+                    v.uv[1],
+                    v.scalar,
+                ]
+            })
+            .collect();
+
+        self.gl.bind_buffer(GL::ARRAY_BUFFER, Some(buffer));
+        unsafe {
+            let array = js_sys::Float32Array::view(&vertex_data);
+            self.gl
+                .buffer_data_with_array_buffer_view(GL::ARRAY_BUFFER, &array, GL::STATIC_DRAW);
+        }
+    }
+
+    fn upload_indices(&self, buffer: &WebGlBuffer, indices: &[u32]) {
+        self.gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(buffer));
+        unsafe {
+            let array = js_sys::Uint32Array::view(indices);
+            self.gl.buffer_data_with_array_buffer_view(
+                GL::ELEMENT_ARRAY_BUFFER,
+                &array,
+                GL::STATIC_DRAW,
+            );
+        }
+    }
+
+    // Point the vertex attributes at an interleaved vertex buffer
+    fn bind_vertices(&self, buffer: &WebGlBuffer) {
+        let gl = &self.gl;
+        // Interleaved: pos(3) + color(4) + normal(3) + uv(2) + scalar(1) = 13 floats, stride = 52 bytes
+        let stride = 13 * 4; // 13 floats * 4 bytes per float
+        gl.bind_buffer(GL::ARRAY_BUFFER, Some(buffer));
+        gl.enable_vertex_attrib_array(self.pos_attrib);
+        gl.vertex_attrib_pointer_with_i32(self.pos_attrib, 3, GL::FLOAT, false, stride, 0);
+        gl.enable_vertex_attrib_array(self.color_attrib);
+        gl.vertex_attrib_pointer_with_i32(self.color_attrib, 4, GL::FLOAT, false, stride, 12);
+        if let Some(normal_attrib) = self.normal_attrib {
+            gl.enable_vertex_attrib_array(normal_attrib);
+            gl.vertex_attrib_pointer_with_i32(normal_attrib, 3, GL::FLOAT, false, stride, 28);
+        }
+        if let Some(uv_attrib) = self.uv_attrib {
+            gl.enable_vertex_attrib_array(uv_attrib);
+            gl.vertex_attrib_pointer_with_i32(uv_attrib, 2, GL::FLOAT, false, stride, 40);
+        }
+        if let Some(scalar_attrib) = self.scalar_attrib {
+            gl.enable_vertex_attrib_array(scalar_attrib);
+            gl.vertex_attrib_pointer_with_i32(scalar_attrib, 1, GL::FLOAT, false, stride, 48);
+        }
+    }
+
+    fn frame(&mut self, timestamp: f64) {
+        // Calculate delta time in seconds
+        let delta_time = if self.last_timestamp == 0.0 {
+            0.016 // First frame fallback
+        } else {
+            (timestamp - self.last_timestamp) / 1000.0 // Convert ms to seconds
+        };
+        self.last_timestamp = timestamp;
+
+        // Update time
+        let step = self.state.speed as f64 * delta_time;
+        self.state.time = match self.state.camera_travel {
+            Parameterization::Parameter => self.state.time + step as f32,
+            Parameterization::ArcLength => {
+                // Same average pace as parameter travel, but constant speed
+                let scale = self.arc.length() / self.lisa.period();
+                let s = self.arc.arc_length(self.state.time as f64) + step * scale;
+                self.arc.parameter(s) as f32
+            }
+        };
+        let t = self.state.time as f64;
+
+        self.wall_texture.update(&self.gl, delta_time as f32);
+
+        let animation = self.state.palette_animation;
+        if animation.enabled {
+            let dt = delta_time as f32;
+            self.state.palette_offset =
+                (self.state.palette_offset + animation.speed * dt).rem_euclid(1.0);
+            self.state.pulse_position =
+                (self.state.pulse_position + animation.pulse_speed * dt).rem_euclid(1.0);
+        }
+        if self.palette_dirty {
+            self.upload_palette();
+            self.palette_dirty = false;
+        }
+
+        if self.mesh_dirty {
+            let mesh = self.mesh();
+            self.upload_mesh(&mesh);
+            self.mesh_dirty = false;
+        }
+
+        let gl = &self.gl;
+        gl.use_program(Some(&self.program));
+        gl.clear_color(0.02, 0.02, 0.05, 1.0);
+        gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
+
+        // Camera mode
+        let pose = match self.state.camera_mode {
+            // Inside view: camera follows curve
+            CameraMode::Inside => camera::follow_pose(&self.lisa, &self.frames, t),
+            // Chase view: trails the current point, scaled to the tube
+            CameraMode::Chase => {
+                let radius = self.options.profile.radius();
+                camera::chase_pose(
+                    &self.lisa,
+                    &self.frames,
+                    t,
+                    self.state.chase_distance * radius,
+                    self.state.chase_height * radius,
+                )
+            }
+            // Outside view: orbit around the curve, steered by the pointer
+            CameraMode::Outside => {
+                self.orbit.update(delta_time);
+                self.orbit.pose()
+            }
+        };
+        // Fog is for flying through; the orbit view shows the whole curve
+        let fog = match self.state.camera_mode {
+            CameraMode::Outside => 0.0,
+            CameraMode::Inside | CameraMode::Chase => 1.0,
+        };
+        // Glide from wherever the camera was when the mode changed, fading
+        // the fog along with it
+        let (pose, fog) = match self.transition.as_mut() {
+            Some(transition) => {
+                transition.advance(delta_time);
+                let blended = transition.pose(&pose);
+                let f = transition.progress() as f32;
+                if transition.finished() {
+                    self.transition = None;
+                }
+                (
+                    blended,
+                    self.transition_fog + (fog - self.transition_fog) * f,
+                )
+            }
+            None => (pose, fog),
+        };
+        self.last_pose = pose;
+        self.last_fog = fog;
+        let (view, eye) = (pose.view_matrix(), pose.eye);
+
+        gl.uniform_matrix4fv_with_f32_array(Some(&self.view_uniform), false, view.as_slice());
+
+        let lighting = &self.state.lighting;
+        let lights = lighting.light_positions(&self.lisa);
+        self.lighting_uniforms.upload(gl, lighting, eye, &lights);
+        self.fog_uniforms.upload(gl, &self.state.fog.scaled(fog));
+        // Only the walls are textured
+        self.texture_uniforms.bind(gl, None);
+        self.palette_uniforms
+            .upload(gl, &self.state, &self.palette_texture);
+
+        if self.state.wire_width > 0.0 {
+            // Tube grid: solid geometry, so it writes depth
+            self.bind_vertices(&self.wire_vertex_buffer);
+            self.lighting_uniforms.enable(gl, lighting.enabled);
+            gl.uniform1f(Some(&self.use_vertex_color_uniform), 1.0); // Use vertex colors
+            gl.uniform1f(Some(&self.alpha_override_uniform), 1.0); // Full opacity for lines
+            for (show, buffer, count) in [
+                (
+                    self.state.show_longitude,
+                    &self.wire_long_buffer,
+                    self.cached_wire_long_count,
+                ),
+                (
+                    self.state.show_latitude,
+                    &self.wire_lat_buffer,
+                    self.cached_wire_lat_count,
+                ),
+            ] {
+                if show {
+                    gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(buffer));
+                    gl.draw_elements_with_i32(GL::TRIANGLES, count as i32, GL::UNSIGNED_INT, 0);
+                }
+            }
+        }
+
+        self.bind_vertices(&self.vertex_buffer);
+        self.lighting_uniforms.enable(gl, false);
+
+        // Draw longitude - use vertex colors with alpha=1.0
+        if self.state.show_longitude && self.state.wire_width <= 0.0 {
+            gl.uniform1f(Some(&self.use_vertex_color_uniform), 1.0); // Use vertex colors
+            gl.uniform1f(Some(&self.alpha_override_uniform), 1.0); // Full opacity for lines
+            gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&self.long_buffer));
+            gl.depth_mask(false);
+            gl.draw_elements_with_i32(
+                GL::LINES,
+                self.cached_long_count as i32,
+                GL::UNSIGNED_INT,
+                0,
+            );
+            gl.depth_mask(true);
+        }
+
+        // Draw latitude - use vertex colors with alpha=1.0
+        if self.state.show_latitude && self.state.wire_width <= 0.0 {
+            gl.uniform1f(Some(&self.use_vertex_color_uniform), 1.0); // Use vertex colors
+            gl.uniform1f(Some(&self.alpha_override_uniform), 1.0); // Full opacity for lines
+            gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&self.lat_buffer));
+            gl.depth_mask(false);
+            gl.draw_elements_with_i32(GL::LINES, self.cached_lat_count as i32, GL::UNSIGNED_INT, 0);
+            gl.depth_mask(true);
+        }
+
+        // Draw tunnel with per-vertex colors and alpha=0.3
+        if self.state.show_tunnel {
+            gl.uniform1f(Some(&self.use_vertex_color_uniform), 1.0); // Use vertex colors
+            gl.uniform1f(Some(&self.alpha_override_uniform), self.state.wall_opacity);
+            self.lighting_uniforms.enable(gl, lighting.enabled);
+            self.texture_uniforms.bind(gl, Some(&self.wall_texture));
+
+            if self.state.transparency == Transparency::WeightedBlended {
+                if let Some(oit) = self.oit.as_mut() {
+                    match oit.begin_accumulation(gl) {
+                        Ok(()) => {
+                            self.draw_walls_weighted_blended();
+                            return;
+                        }
+                        // e.g. a resize the float targets can't follow
+                        Err(e) => {
+                            web_sys::console::warn_1(
+                                &format!("Falling back to two-pass transparency: {}", e).into(),
+                            );
+                            oit.release(gl);
+                            self.oit = None;
+                            self.state.transparency = Transparency::TwoPass;
+                        }
+                    }
+                }
+            }
+            self.draw_walls_two_pass();
+        }
+    }
+
+    // Back faces, then front faces, blended in triangle order
+    fn draw_walls_two_pass(&self) {
+        let gl = &self.gl;
+        gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&self.tri_buffer));
+
+        gl.enable(GL::CULL_FACE);
+        gl.depth_mask(false); // transparent: test depth but don't write
+
+        // Pass 1: back faces first (cull front)
+        gl.cull_face(GL::FRONT);
+        gl.draw_elements_with_i32(
+            GL::TRIANGLES,
+            self.cached_tri_count as i32,
+            GL::UNSIGNED_INT,
+            0,
+        );
+
+        // Pass 2: front faces
+        gl.cull_face(GL::BACK);
+        gl.draw_elements_with_i32(
+            GL::TRIANGLES,
+            self.cached_tri_count as i32,
+            GL::UNSIGNED_INT,
+            0,
+        );
+
+        gl.depth_mask(true); // restore
+        gl.disable(GL::CULL_FACE); // optional restore
+    }
+
+    // Weighted blended OIT into the off-screen targets, then composited over
+    // the frame. The accumulation target must already be bound.
+    fn draw_walls_weighted_blended(&self) {
+        let gl = &self.gl;
+        let Some(oit) = &self.oit else {
+            return;
+        };
+
+        // The grid tubes hide the walls behind them; lay down their depth
+        if self.state.wire_width > 0.0 {
+            self.bind_vertices(&self.wire_vertex_buffer);
+            gl.color_mask(false, false, false, false);
+            for (show, buffer, count) in [
+                (
+                    self.state.show_longitude,
+                    &self.wire_long_buffer,
+                    self.cached_wire_long_count,
+                ),
+                (
+                    self.state.show_latitude,
+                    &self.wire_lat_buffer,
+                    self.cached_wire_lat_count,
+                ),
+            ] {
+                if show {
+                    gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(buffer));
+                    gl.draw_elements_with_i32(GL::TRIANGLES, count as i32, GL::UNSIGNED_INT, 0);
+                }
+            }
+            gl.color_mask(true, true, true, true);
+            self.bind_vertices(&self.vertex_buffer);
+        }
+
+        // Every layer at once, in any order: no culling, no depth writes
+        gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&self.tri_buffer));
+        gl.depth_mask(false);
+        for pass in [1, 2] {
+            if pass == 2 {
+                oit.begin_revealage(gl);
+            }
+            gl.uniform1i(self.oit_pass_uniform.as_ref(), pass);
+            gl.draw_elements_with_i32(
+                GL::TRIANGLES,
+                self.cached_tri_count as i32,
+                GL::UNSIGNED_INT,
+                0,
+            );
+        }
+        gl.uniform1i(self.oit_pass_uniform.as_ref(), 0);
+        gl.depth_mask(true);
+
+        oit.composite(gl, &self.program);
+    }
+
+    // Free the GPU objects owned by this tunnel
+    fn release(&self) {
+        let gl = &self.gl;
+        gl.delete_buffer(Some(&self.vertex_buffer));
+        gl.delete_buffer(Some(&self.tri_buffer));
+        gl.delete_buffer(Some(&self.long_buffer));
+        gl.delete_buffer(Some(&self.lat_buffer));
+        gl.delete_buffer(Some(&self.wire_vertex_buffer));
+        gl.delete_buffer(Some(&self.wire_long_buffer));
+        gl.delete_buffer(Some(&self.wire_lat_buffer));
+        gl.delete_program(Some(&self.program));
+        gl.delete_texture(Some(&self.wall_texture.texture));
+        gl.delete_texture(Some(&self.palette_texture));
+        if let Some(oit) = &self.oit {
+            oit.release(gl);
+        }
+    }
+}
+
+type FrameCallback = Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>;
+type EventListener = (&'static str, Closure<dyn FnMut(Event)>);
+
+// Feed pointer and wheel events on the canvas to the orbit camera. They
+// only steer while the outside view is showing.
+fn orbit_listeners(canvas: &HtmlCanvasElement, tunnel: &Rc<RefCell<Tunnel>>) -> Vec<EventListener> {
+    let outside =
+        |tunnel: &Rc<RefCell<Tunnel>>| tunnel.borrow().state.camera_mode == CameraMode::Outside;
+    let mut listeners: Vec<EventListener> = Vec::new();
+
+    let (down_tunnel, down_canvas) = (tunnel.clone(), canvas.clone());
+    listeners.push((
+        "pointerdown",
+        Closure::new(move |event: Event| {
+            let Some(event) = event.dyn_ref::<PointerEvent>() else {
+                return;
+            };
+            if !outside(&down_tunnel) {
+                return;
+            }
+            event.prevent_default();
+            // Keep receiving moves when the pointer leaves the canvas
+            let _ = down_canvas.set_pointer_capture(event.pointer_id());
+            // Right, middle or shift-drag pans
+            let drag = if event.button() == 0 && !event.shift_key() {
+                Drag::Rotate
+            } else {
+                Drag::Pan
+            };
+            down_tunnel.borrow_mut().orbit.pointer_down(
+                event.pointer_id(),
+                event.client_x() as f64,
+                event.client_y() as f64,
+                drag,
+            );
+        }),
+    ));
+
+    let (move_tunnel, move_canvas) = (tunnel.clone(), canvas.clone());
+    listeners.push((
+        "pointermove",
+        Closure::new(move |event: Event| {
+            if let Some(event) = event.dyn_ref::<PointerEvent>() {
+                move_tunnel.borrow_mut().orbit.pointer_move(
+                    event.pointer_id(),
+                    event.client_x() as f64,
+                    event.client_y() as f64,
+                    move_canvas.client_height() as f64,
+                );
+            }
+        }),
+    ));
+
+    for name in ["pointerup", "pointercancel"] {
+        let up_tunnel = tunnel.clone();
+        listeners.push((
+            name,
+            Closure::new(move |event: Event| {
+                if let Some(event) = event.dyn_ref::<PointerEvent>() {
+                    up_tunnel.borrow_mut().orbit.pointer_up(event.pointer_id());
+                }
+            }),
+        ));
+    }
+
+    let wheel_tunnel = tunnel.clone();
+    listeners.push((
+        "wheel",
+        Closure::new(move |event: Event| {
+            let Some(event) = event.dyn_ref::<WheelEvent>() else {
+                return;
+            };
+            if !outside(&wheel_tunnel) {
+                return;
+            }
+            // Don't scroll the page while zooming
+            event.prevent_default();
+            let pixels = match event.delta_mode() {
+                WheelEvent::DOM_DELTA_LINE => 16.0,
+                WheelEvent::DOM_DELTA_PAGE => 400.0,
+                _ => 1.0,
+            };
+            wheel_tunnel
+                .borrow_mut()
+                .orbit
+                .zoom(event.delta_y() * pixels);
+        }),
+    ));
+
+    // The right button pans instead of opening a menu
+    let menu_tunnel = tunnel.clone();
+    listeners.push((
+        "contextmenu",
+        Closure::new(move |event: Event| {
+            if outside(&menu_tunnel) {
+                event.prevent_default();
+            }
+        }),
+    ));
+
+    for (name, listener) in &listeners {
+        let _ = canvas.add_event_listener_with_callback(name, listener.as_ref().unchecked_ref());
+    }
+    listeners
+}
+
+/// A running tunnel animation on one canvas.
+///
+/// Each handle owns its own settings, so several canvases can run side by
+/// side. Calling `stop()` (or `free()`) cancels the animation loop and
+/// deletes the GL buffers.
+#[wasm_bindgen]
+pub struct TunnelHandle {
+    tunnel: Rc<RefCell<Tunnel>>,
+    callback: FrameCallback,
+    frame_id: Rc<RefCell<Option<i32>>>,
+    canvas: HtmlCanvasElement,
+    listeners: RefCell<Vec<EventListener>>,
+}
+
+#[wasm_bindgen]
+impl TunnelHandle {
+    pub fn set_speed(&self, speed: f32) {
+        self.tunnel.borrow_mut().state.speed = speed;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn speed(&self) -> f32 {
+        self.tunnel.borrow().state.speed
+    }
+
+    pub fn set_time(&self, time: f32) {
+        self.tunnel.borrow_mut().state.time = time;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn time(&self) -> f32 {
+        self.tunnel.borrow().state.time
+    }
+
+    pub fn set_show_longitude(&self, show: bool) {
+        self.tunnel.borrow_mut().state.show_longitude = show;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn show_longitude(&self) -> bool {
+        self.tunnel.borrow().state.show_longitude
+    }
+
+    pub fn set_show_latitude(&self, show: bool) {
+        self.tunnel.borrow_mut().state.show_latitude = show;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn show_latitude(&self) -> bool {
+        self.tunnel.borrow().state.show_latitude
+    }
+
+    pub fn set_show_tunnel(&self, show: bool) {
+        self.tunnel.borrow_mut().state.show_tunnel = show;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn show_tunnel(&self) -> bool {
+        self.tunnel.borrow().state.show_tunnel
+    }
+
+    pub fn set_num_polygons(&self, num: usize) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        let num = num.clamp(10, 1000);
+        if tunnel.options.num_polygons != num {
+            tunnel.options.num_polygons = num;
+            tunnel.mesh_dirty = true;
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn num_polygons(&self) -> usize {
+        self.tunnel.borrow().options.num_polygons
+    }
+
+    /// Follow a different curve, e.g. one built with phases or a polar band
+    pub fn set_curve(&self, curve: &Lissajou3D) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.lisa = curve.clone();
+        tunnel.frames = CurveFrames::new(curve, tunnel.options.frame_mode);
+        tunnel.arc = ArcLengthTable::new(curve);
+        tunnel.mesh_dirty = true;
+        tunnel.frame_curve();
+    }
+
+    /// Sweep a different cross-section, e.g. `Profile.star(1.2, 0.6, 5)`
+    pub fn set_profile(&self, profile: &Profile) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.options.profile = profile.clone();
+        tunnel.mesh_dirty = true;
+    }
+
+    /// Radius scale along the curve as `[t0, s0, t1, s1, ...]`. An empty
+    /// array restores a constant tube.
+    pub fn set_radius_keyframes(&self, keys: Vec<f64>) -> Result<(), JsValue> {
+        let track = Track::from_flat(&keys, 1.0)?;
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.options.radius_scale = track;
+        tunnel.mesh_dirty = true;
+        Ok(())
+    }
+
+    /// Twist around the tangent in radians as `[t0, angle0, t1, angle1, ...]`
+    pub fn set_twist_keyframes(&self, keys: Vec<f64>) -> Result<(), JsValue> {
+        let track = Track::from_flat(&keys, 0.0)?;
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.options.twist = track;
+        tunnel.mesh_dirty = true;
+        Ok(())
+    }
+
+    /// Scale along the frame normal and binormal as `[t0, s0, t1, s1, ...]`
+    pub fn set_scale_keyframes(&self, keys_x: Vec<f64>, keys_y: Vec<f64>) -> Result<(), JsValue> {
+        let scale_x = Track::from_flat(&keys_x, 1.0)?;
+        let scale_y = Track::from_flat(&keys_y, 1.0)?;
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.options.scale_x = scale_x;
+        tunnel.options.scale_y = scale_y;
+        tunnel.mesh_dirty = true;
+        Ok(())
+    }
+
+    /// Ring orientation for both the mesh and the inside camera
+    pub fn set_frame_mode(&self, mode: FrameMode) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        if tunnel.options.frame_mode != mode {
+            tunnel.options.frame_mode = mode;
+            tunnel.frames = CurveFrames::new(&tunnel.lisa, mode);
+            tunnel.mesh_dirty = true;
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn frame_mode(&self) -> FrameMode {
+        self.tunnel.borrow().options.frame_mode
+    }
+
+    /// Place rings at equal steps of t or of distance along the curve
+    pub fn set_ring_spacing(&self, spacing: Parameterization) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        if tunnel.options.ring_spacing != spacing {
+            tunnel.options.ring_spacing = spacing;
+            tunnel.mesh_dirty = true;
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn ring_spacing(&self) -> Parameterization {
+        self.tunnel.borrow().options.ring_spacing
+    }
+
+    /// Advance the camera at constant speed in t or in distance
+    pub fn set_camera_travel(&self, travel: Parameterization) {
+        self.tunnel.borrow_mut().state.camera_travel = travel;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn camera_travel(&self) -> Parameterization {
+        self.tunnel.borrow().state.camera_travel
+    }
+
+    /// Switch between the inside, chase and outside cameras. The view
+    /// glides to the new camera over `transition_duration` seconds.
+    pub fn set_camera_mode(&self, mode: CameraMode) {
+        self.tunnel.borrow_mut().set_camera_mode(mode);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn camera_mode(&self) -> CameraMode {
+        self.tunnel.borrow().state.camera_mode
+    }
+
+    /// Outside view on, or back to the inside view
+    pub fn set_outside_view(&self, outside: bool) {
+        self.set_camera_mode(if outside {
+            CameraMode::Outside
+        } else {
+            CameraMode::Inside
+        });
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn outside_view(&self) -> bool {
+        self.camera_mode() == CameraMode::Outside
+    }
+
+    /// Seconds a camera mode switch takes; 0 cuts straight to the new view
+    pub fn set_transition_duration(&self, seconds: f64) {
+        self.tunnel.borrow_mut().state.transition_duration = seconds.max(0.0);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn transition_duration(&self) -> f64 {
+        self.tunnel.borrow().state.transition_duration
+    }
+
+    /// How far the chase camera trails behind the current point and rises
+    /// above it, in tube radii
+    pub fn set_chase_offset(&self, distance: f64, height: f64) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.state.chase_distance = distance.max(0.1);
+        tunnel.state.chase_height = height;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn chase_distance(&self) -> f64 {
+        self.tunnel.borrow().state.chase_distance
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn chase_height(&self) -> f64 {
+        self.tunnel.borrow().state.chase_height
+    }
+
+    /// Current mesh as Wavefront OBJ text
+    pub fn export_obj(&self) -> String {
+        export::write_obj(&self.tunnel.borrow().export_mesh())
+    }
+
+    /// Current mesh as binary STL, returned to JS as a `Uint8Array`
+    pub fn export_stl_binary(&self) -> Vec<u8> {
+        export::write_stl_binary(&self.tunnel.borrow().export_mesh())
+    }
+
+    /// Current mesh as ASCII STL text
+    pub fn export_stl_ascii(&self) -> String {
+        export::write_stl_ascii(&self.tunnel.borrow().export_mesh(), "tunnel")
+    }
+
+    /// Current tube as a watertight solid with walls `thickness` thick, as
+    /// binary STL for printing. Throws if the wall is too thick for the
+    /// profile.
+    pub fn export_solid_stl(&self, thickness: f64) -> Result<Vec<u8>, JsValue> {
+        let tunnel = self.tunnel.borrow();
+        let solid = generate_solid_tube(&tunnel.lisa, &tunnel.options, thickness)?;
+        Ok(export::write_stl_binary(&solid.mesh))
+    }
+
+    /// Same solid as `export_solid_stl`, as a 3MF package in millimetres
+    pub fn export_solid_3mf(&self, thickness: f64) -> Result<Vec<u8>, JsValue> {
+        let tunnel = self.tunnel.borrow();
+        let solid = generate_solid_tube(&tunnel.lisa, &tunnel.options, thickness)?;
+        Ok(export::write_3mf(&solid.mesh))
+    }
+
+    /// Parameter ranges where the tube collides with itself, as
+    /// `[t_start0, t_end0, t_start1, t_end1, ...]`
+    pub fn self_intersections(&self) -> Vec<f64> {
+        let tunnel = self.tunnel.borrow();
+        proximity_ranges(&tunnel.lisa, &tunnel.options)
+            .into_iter()
+            .flat_map(|(start, end)| [start, end])
+            .collect()
+    }
+
+    /// Folds, self-proximity and mesh defects for the current parameters,
+    /// with the largest radius that avoids them
+    pub fn analyze(&self) -> TunnelReport {
+        let tunnel = self.tunnel.borrow();
+        analyze_tunnel(&tunnel.lisa, &tunnel.options, &tunnel.mesh())
+    }
+
+    /// Current mesh plus an animated camera replaying one loop of the inside
+    /// view over `duration` seconds, as a glTF binary (.glb)
+    pub fn export_glb(&self, duration: f32, samples: usize) -> Vec<u8> {
+        let tunnel = self.tunnel.borrow();
+        let poses = camera::follow_path(
+            &tunnel.lisa,
+            &tunnel.frames,
+            &tunnel.arc,
+            tunnel.state.camera_travel,
+            samples.max(1),
+        );
+        let gl = &tunnel.gl;
+        let aspect = gl.drawing_buffer_width() as f32 / gl.drawing_buffer_height().max(1) as f32;
+        let track = CameraTrack::evenly_spaced(FIELD_OF_VIEW, aspect, NEAR, FAR, poses, duration);
+        export::write_glb(&tunnel.export_mesh(), Some(&track))
+    }
+
+    /// Draw the grid as tubes `width` across instead of 1px lines. 0
+    /// switches back to lines.
+    pub fn set_wire_width(&self, width: f32) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        let width = width.max(0.0);
+        if tunnel.state.wire_width != width {
+            tunnel.state.wire_width = width;
+            tunnel.mesh_dirty = true;
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn wire_width(&self) -> f32 {
+        self.tunnel.borrow().state.wire_width
+    }
+
+    /// Turn the Blinn-Phong shading of walls and grid tubes on or off
+    pub fn set_lighting(&self, enabled: bool) {
+        self.tunnel.borrow_mut().state.lighting.enabled = enabled;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn lighting(&self) -> bool {
+        self.tunnel.borrow().state.lighting.enabled
+    }
+
+    pub fn set_ambient(&self, ambient: f32) {
+        self.tunnel.borrow_mut().state.lighting.ambient = ambient;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn ambient(&self) -> f32 {
+        self.tunnel.borrow().state.lighting.ambient
+    }
+
+    pub fn set_diffuse(&self, diffuse: f32) {
+        self.tunnel.borrow_mut().state.lighting.diffuse = diffuse;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn diffuse(&self) -> f32 {
+        self.tunnel.borrow().state.lighting.diffuse
+    }
+
+    pub fn set_specular(&self, specular: f32) {
+        self.tunnel.borrow_mut().state.lighting.specular = specular;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn specular(&self) -> f32 {
+        self.tunnel.borrow().state.lighting.specular
+    }
+
+    /// Blinn-Phong exponent; higher is a tighter highlight
+    pub fn set_shininess(&self, shininess: f32) {
+        self.tunnel.borrow_mut().state.lighting.shininess = shininess.max(1.0);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn shininess(&self) -> f32 {
+        self.tunnel.borrow().state.lighting.shininess
+    }
+
+    /// Strength of the light attached to the camera
+    pub fn set_headlight(&self, intensity: f32) {
+        self.tunnel.borrow_mut().state.lighting.headlight = intensity;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn headlight(&self) -> f32 {
+        self.tunnel.borrow().state.lighting.headlight
+    }
+
+    /// Glow where the walls turn edge-on to the camera
+    pub fn set_rim(&self, rim: f32) {
+        self.tunnel.borrow_mut().state.lighting.rim = rim;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rim(&self) -> f32 {
+        self.tunnel.borrow().state.lighting.rim
+    }
+
+    /// Up to 4 point lights on the curve at parameters `params`, with
+    /// colours `[r0, g0, b0, r1, ...]`. No colours means white lights.
+    pub fn set_point_lights(&self, params: Vec<f64>, colors: Vec<f32>) -> Result<(), JsValue> {
+        if params.len() > MAX_POINT_LIGHTS {
+            return Err(format!("at most {} point lights", MAX_POINT_LIGHTS).into());
+        }
+        if !colors.is_empty() && colors.len() != 3 * params.len() {
+            return Err("point light colours must be one r, g, b triple per light".into());
+        }
+        let lights = params
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| PointLight {
+                t,
+                color: colors
+                    .get(3 * i..3 * i + 3)
+                    .map_or([1.0; 3], |c| [c[0], c[1], c[2]]),
+            })
+            .collect();
+        self.tunnel.borrow_mut().state.lighting.point_lights = lights;
+        Ok(())
+    }
+
+    /// Fog for the inside view: off, linear or exponential in depth
+    pub fn set_fog_mode(&self, mode: FogMode) {
+        self.tunnel.borrow_mut().state.fog.mode = mode;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fog_mode(&self) -> FogMode {
+        self.tunnel.borrow().state.fog.mode
+    }
+
+    pub fn set_fog_color(&self, r: f32, g: f32, b: f32) {
+        self.tunnel.borrow_mut().state.fog.color = [r, g, b];
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fog_color(&self) -> Vec<f32> {
+        self.tunnel.borrow().state.fog.color.to_vec()
+    }
+
+    /// Exponential fog: visibility exp(-density · depth)
+    pub fn set_fog_density(&self, density: f32) {
+        self.tunnel.borrow_mut().state.fog.density = density.max(0.0);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fog_density(&self) -> f32 {
+        self.tunnel.borrow().state.fog.density
+    }
+
+    /// Linear fog: clear up to `start`, solid from `end`, in view-space depth
+    pub fn set_fog_range(&self, start: f32, end: f32) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.state.fog.start = start;
+        tunnel.state.fog.end = end;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fog_start(&self) -> f32 {
+        self.tunnel.borrow().state.fog.start
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn fog_end(&self) -> f32 {
+        self.tunnel.borrow().state.fog.end
+    }
+
+    /// How the translucent walls are blended. `WeightedBlended` composites
+    /// crossing loops correctly but needs float render targets; without
+    /// them the walls stay on `TwoPass` and a warning is logged.
+    pub fn set_transparency(&self, mode: Transparency) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        if mode == Transparency::WeightedBlended && tunnel.oit.is_none() {
+            match OitTargets::new(&tunnel.gl) {
+                Ok(oit) => tunnel.oit = Some(oit),
+                Err(e) => {
+                    web_sys::console::warn_1(
+                        &format!("Order-independent transparency unavailable: {}", e).into(),
+                    );
+                    return;
+                }
+            }
+        }
+        tunnel.state.transparency = mode;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn transparency(&self) -> Transparency {
+        self.tunnel.borrow().state.transparency
+    }
+
+    /// Colour the rings with a named colour scale
+    pub fn set_colormap(&self, colormap: Colormap) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.state.palette = Palette::Colormap(colormap);
+        tunnel.recolor();
+    }
+
+    /// What the ring colours follow along the curve, e.g.
+    /// `ColorMetric.Curvature` to pick out the tight bends
+    pub fn set_color_by(&self, metric: ColorMetric) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        if tunnel.options.color_by != metric {
+            tunnel.options.color_by = metric;
+            tunnel.mesh_dirty = true;
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn color_by(&self) -> ColorMetric {
+        self.tunnel.borrow().options.color_by
+    }
+
+    /// Colour the rings with a gradient through `[x0, r0, g0, b0, x1, ...]`,
+    /// positions in [0, 1] along the curve
+    pub fn set_gradient(&self, stops: Vec<f32>) -> Result<(), JsValue> {
+        let gradient = Gradient::from_flat(&stops)?;
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.state.palette = Palette::Gradient(gradient);
+        tunnel.recolor();
+        Ok(())
+    }
+
+    /// Colour on the GPU from the palette texture, so palettes flow, pulse
+    /// and swap without rebuilding the mesh
+    pub fn set_palette_animation(&self, enabled: bool) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        if tunnel.state.palette_animation.enabled && !enabled {
+            // Palettes set meanwhile never reached the vertex colours
+            tunnel.mesh_dirty = true;
+        }
+        tunnel.state.palette_animation.enabled = enabled;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn palette_animation(&self) -> bool {
+        self.tunnel.borrow().state.palette_animation.enabled
+    }
+
+    /// Palette lengths per second that the colours flow along the tunnel;
+    /// negative flows backwards
+    pub fn set_palette_speed(&self, speed: f32) {
+        self.tunnel.borrow_mut().state.palette_animation.speed = speed;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn palette_speed(&self) -> f32 {
+        self.tunnel.borrow().state.palette_animation.speed
+    }
+
+    /// Times the palette repeats along the tunnel while animating
+    pub fn set_palette_repeat(&self, repeat: f32) {
+        self.tunnel.borrow_mut().state.palette_animation.repeat = repeat;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn palette_repeat(&self) -> f32 {
+        self.tunnel.borrow().state.palette_animation.repeat
+    }
+
+    /// A bright band racing along the tunnel at `speed` trips per second,
+    /// `width` of the tunnel wide, brightening by up to `strength`. A
+    /// strength of 0 turns it off.
+    pub fn set_pulse(&self, speed: f32, width: f32, strength: f32) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        let animation = &mut tunnel.state.palette_animation;
+        animation.pulse_speed = speed;
+        animation.pulse_width = width.max(0.0);
+        animation.pulse_strength = strength.max(0.0);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn pulse_speed(&self) -> f32 {
+        self.tunnel.borrow().state.palette_animation.pulse_speed
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn pulse_width(&self) -> f32 {
+        self.tunnel.borrow().state.palette_animation.pulse_width
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn pulse_strength(&self) -> f32 {
+        self.tunnel.borrow().state.palette_animation.pulse_strength
+    }
+
+    /// Opacity of the tunnel walls, 0.05 by default. Textured walls usually
+    /// want more.
+    pub fn set_wall_opacity(&self, opacity: f32) {
+        self.tunnel.borrow_mut().state.wall_opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn wall_opacity(&self) -> f32 {
+        self.tunnel.borrow().state.wall_opacity
+    }
+
+    /// Stream a `<video>` onto the walls, re-uploading its current frame
+    /// every frame. The video must be same-origin or served with CORS.
+    pub fn set_wall_video(&self, video: &HtmlVideoElement) {
+        let source = TextureSource::Video(video.clone());
+        self.tunnel
+            .borrow_mut()
+            .wall_texture
+            .set_source(Some(source));
+    }
+
+    /// Put an `<img>` on the walls once it has loaded
+    pub fn set_wall_image(&self, image: &HtmlImageElement) {
+        let source = TextureSource::Image(image.clone());
+        self.tunnel
+            .borrow_mut()
+            .wall_texture
+            .set_source(Some(source));
+    }
+
+    /// Stream a `<canvas>` onto the walls every frame, e.g. a 2D sketch
+    pub fn set_wall_canvas(&self, canvas: &HtmlCanvasElement) {
+        let source = TextureSource::Canvas(canvas.clone());
+        self.tunnel
+            .borrow_mut()
+            .wall_texture
+            .set_source(Some(source));
+    }
+
+    /// Go back to plain ring colours
+    pub fn clear_wall_texture(&self) {
+        self.tunnel.borrow_mut().wall_texture.set_source(None);
+    }
+
+    /// Repeats of the texture around the profile (`u`) and along the curve (`v`)
+    pub fn set_texture_tiling(&self, u: f32, v: f32) {
+        self.tunnel.borrow_mut().wall_texture.tiling = [u, v];
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn texture_tiling(&self) -> Vec<f32> {
+        self.tunnel.borrow().wall_texture.tiling.to_vec()
+    }
+
+    /// Scroll the texture by `u` and `v` texture widths per second
+    pub fn set_texture_scroll(&self, u: f32, v: f32) {
+        self.tunnel.borrow_mut().wall_texture.scroll = [u, v];
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn texture_scroll(&self) -> Vec<f32> {
+        self.tunnel.borrow().wall_texture.scroll.to_vec()
+    }
+
+    /// Blend between the ring colours (0) and the texture (1)
+    pub fn set_texture_mix(&self, mix: f32) {
+        self.tunnel.borrow_mut().wall_texture.mix = mix.clamp(0.0, 1.0);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn texture_mix(&self) -> f32 {
+        self.tunnel.borrow().wall_texture.mix
+    }
+
+    /// Spin the outside view around the curve at `speed` radians per
+    /// second whenever nobody is dragging; 0 stops
+    pub fn set_auto_rotate(&self, speed: f64) {
+        self.tunnel.borrow_mut().orbit.auto_rotate = speed;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn auto_rotate(&self) -> f64 {
+        self.tunnel.borrow().orbit.auto_rotate
+    }
+
+    /// How long the orbit camera glides after a drag: the fraction of the
+    /// motion left after 1/60 s, from 0 (stops dead) to just under 1
+    pub fn set_orbit_damping(&self, damping: f64) {
+        self.tunnel.borrow_mut().orbit.damping = damping.clamp(0.0, 0.99);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn orbit_damping(&self) -> f64 {
+        self.tunnel.borrow().orbit.damping
+    }
+
+    /// Elevation limits of the orbit camera in radians, within ±π/2
+    pub fn set_orbit_pitch_limits(&self, min: f64, max: f64) {
+        let limit = std::f64::consts::FRAC_PI_2 - 0.01;
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.orbit.min_pitch = min.clamp(-limit, limit);
+        tunnel.orbit.max_pitch = max.clamp(min.clamp(-limit, limit), limit);
+    }
+
+    /// Limits of the angle around the vertical axis in radians. Infinite
+    /// bounds (the default) let the camera circle freely.
+    pub fn set_orbit_yaw_limits(&self, min: f64, max: f64) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.orbit.min_yaw = min;
+        tunnel.orbit.max_yaw = max.max(min);
+    }
+
+    /// Nearest and farthest the orbit camera may zoom, in world units.
+    /// Framing a curve (`set_curve`, `reset_orbit`) sets them to a tenth and
+    /// ten times the framing distance.
+    pub fn set_orbit_distance_limits(&self, min: f64, max: f64) {
+        let mut tunnel = self.tunnel.borrow_mut();
+        tunnel.orbit.min_distance = min.max(1e-6);
+        tunnel.orbit.max_distance = max.max(min);
+    }
+
+    /// Frame the whole curve again in the outside view
+    pub fn reset_orbit(&self) {
+        self.tunnel.borrow_mut().frame_curve();
+    }
+
+    /// Put the camera back at the start of the curve
+    pub fn reset(&self) {
+        self.tunnel.borrow_mut().state.time = 0.0;
+    }
+
+    /// Whether the animation loop is still scheduled
+    #[wasm_bindgen(getter)]
+    pub fn running(&self) -> bool {
+        self.callback.borrow().is_some()
+    }
+
+    /// Cancel the animation loop and release the GL buffers. Safe to call twice.
+    pub fn stop(&self) {
+        if let Some(id) = self.frame_id.borrow_mut().take() {
+            if let Some(window) = web_sys::window() {
+                let _ = window.cancel_animation_frame(id);
+            }
+        }
+        for (name, listener) in self.listeners.borrow_mut().drain(..) {
+            let _ = self
+                .canvas
+                .remove_event_listener_with_callback(name, listener.as_ref().unchecked_ref());
+        }
+        // Dropping the closure also breaks its reference cycle
+        if self.callback.borrow_mut().take().is_some() {
+            self.tunnel.borrow().release();
+        }
+    }
+}
+
+impl Drop for TunnelHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[wasm_bindgen]
+pub fn start_simple_tunnel(
+    canvas_id: &str,
+    a: f64,
+    b: f64,
+    r: f64,
+    polygon_radius: f64,
+    polygon_sides: usize,
+    num_polygons: usize,
+) -> Result<TunnelHandle, JsValue> {
+    console_error_panic_hook::set_once();
+
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    let document = window
+        .document()
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let canvas: HtmlCanvasElement = document
+        .get_element_by_id(canvas_id)
+        .ok_or_else(|| JsValue::from_str(&format!("no element with id '{}'", canvas_id)))?
+        .dyn_into()?;
+    let gl: GL = canvas
+        .get_context("webgl")?
+        .ok_or_else(|| JsValue::from_str("WebGL is not available"))?
+        .dyn_into()?;
+
+    // Enable OES_element_index_uint extension for 32-bit indices
+    let _ = gl.get_extension("OES_element_index_uint").map_err(|e| {
+        web_sys::console::error_1(&format!("Failed to get extension: {:?}", e).into());
+        e
+    })?;
+
+    gl.enable(GL::DEPTH_TEST);
+    gl.enable(GL::BLEND);
+    gl.blend_func(GL::SRC_ALPHA, GL::ONE_MINUS_SRC_ALPHA);
+
+    // Enable culling with proper triangle winding
+    gl.disable(GL::CULL_FACE);
+    // gl.cull_face(GL::BACK);
+
+    // Per-vertex color with alpha override, Blinn-Phong lighting
+    let vert_code = include_str!("shaders/tunnel.vert");
+    let frag_code = include_str!("shaders/tunnel.frag");
+
+    let vert_shader = compile_shader(&gl, GL::VERTEX_SHADER, vert_code)?;
+    let frag_shader = compile_shader(&gl, GL::FRAGMENT_SHADER, frag_code)?;
+    let program = link_program(&gl, &vert_shader, &frag_shader)?;
+    gl.use_program(Some(&program));
+    // The linked program keeps what it needs
+    gl.delete_shader(Some(&vert_shader));
+    gl.delete_shader(Some(&frag_shader));
+
+    // Get attribute/uniform locations
+    let pos_attrib = gl.get_attrib_location(&program, "position") as u32;
+    let color_attrib = gl.get_attrib_location(&program, "color") as u32;
+    let normal_attrib = attrib_location(&gl, &program, "normal");
+    let uv_attrib = attrib_location(&gl, &program, "uv");
+    let scalar_attrib = attrib_location(&gl, &program, "scalar");
+    let projection_uniform = gl.get_uniform_location(&program, "u_projection").unwrap();
+    let view_uniform = gl.get_uniform_location(&program, "u_view").unwrap();
+    let use_vertex_color_uniform = gl
+        .get_uniform_location(&program, "u_use_vertex_color")
+        .unwrap();
+    let alpha_override_uniform = gl
+        .get_uniform_location(&program, "u_alpha_override")
+        .unwrap();
+    let lighting_uniforms = LightingUniforms::new(&gl, &program);
+    let fog_uniforms = FogUniforms::new(&gl, &program);
+    let oit_pass_uniform = gl.get_uniform_location(&program, "u_oit_pass");
+    let texture_uniforms = TextureUniforms::new(&gl, &program);
+    let wall_texture = WallTexture::new(&gl)?;
+    let palette_uniforms = PaletteUniforms::new(&gl, &program);
+    let palette_texture = create_palette_texture(&gl)?;
+
+    // Setup projection
+    let aspect = canvas.width() as f32 / canvas.height() as f32;
+    let projection = Mat4::perspective(FIELD_OF_VIEW, aspect, NEAR, FAR);
+    gl.uniform_matrix4fv_with_f32_array(Some(&projection_uniform), false, projection.as_slice());
+
+    // Create Lissajou - mesh generation will be dynamic
+    let lisa = Lissajou3D::new(a, b, r);
+    // Same limits as the setters, so JS can't ask for an empty sweep
+    let options = TunnelOptions::new(
+        polygon_radius,
+        polygon_sides.max(3),
+        num_polygons.clamp(10, 1000),
+    )?;
+    let frames = CurveFrames::new(&lisa, options.frame_mode);
+    let arc = ArcLengthTable::new(&lisa);
+    let mut orbit = OrbitCamera::new(
+        V3D::new(0.0, 0.0, 0.0),
+        1.0,
+        0.0,
+        ORBIT_PITCH,
+        FIELD_OF_VIEW as f64,
+    );
+    let (center, radius) = camera::bounding_sphere(&lisa, BOUNDS_SAMPLES);
+    orbit.frame_sphere(center, radius, aspect as f64);
+    let last_pose = camera::follow_pose(&lisa, &frames, 0.0);
+    let state = TunnelState::default();
+    let mesh = generate_tunnel_mesh(&lisa, &options, &state.palette);
+
+    let mut tunnel = Tunnel {
+        vertex_buffer: gl.create_buffer().ok_or("unable to create buffer")?,
+        tri_buffer: gl.create_buffer().ok_or("unable to create buffer")?,
+        long_buffer: gl.create_buffer().ok_or("unable to create buffer")?,
+        lat_buffer: gl.create_buffer().ok_or("unable to create buffer")?,
+        wire_vertex_buffer: gl.create_buffer().ok_or("unable to create buffer")?,
+        wire_long_buffer: gl.create_buffer().ok_or("unable to create buffer")?,
+        wire_lat_buffer: gl.create_buffer().ok_or("unable to create buffer")?,
+        gl,
+        state,
+        lisa,
+        options,
+        frames,
+        arc,
+        orbit,
+        transition: None,
+        last_pose,
+        last_fog: 1.0,
+        transition_fog: 1.0,
+        mesh_dirty: false,
+        palette_dirty: true,
+        program,
+        pos_attrib,
+        color_attrib,
+        normal_attrib,
+        uv_attrib,
+        scalar_attrib,
+        view_uniform,
+        use_vertex_color_uniform,
+        alpha_override_uniform,
+        lighting_uniforms,
+        fog_uniforms,
+        oit_pass_uniform,
+        oit: None,
+        texture_uniforms,
+        wall_texture,
+        palette_uniforms,
+        palette_texture,
+        cached_tri_count: 0,
+        cached_long_count: 0,
+        cached_lat_count: 0,
+        cached_wire_long_count: 0,
+        cached_wire_lat_count: 0,
+        last_timestamp: 0.0,
+    };
+    tunnel.upload_mesh(&mesh);
+
+    let tunnel = Rc::new(RefCell::new(tunnel));
+    let frame_id = Rc::new(RefCell::new(None::<i32>));
+
+    // Animation loop
+    let f: FrameCallback = Rc::new(RefCell::new(None));
+    let g = f.clone();
+
+    let loop_tunnel = tunnel.clone();
+    let loop_frame_id = frame_id.clone();
+    *g.borrow_mut() = Some(Closure::wrap(Box::new(move |timestamp: f64| {
+        loop_tunnel.borrow_mut().frame(timestamp);
+
+        // `f` is emptied by `stop()`, which ends the loop
+        if let Some(callback) = f.borrow().as_ref() {
+            let id = web_sys::window()
+                .unwrap()
+                .request_animation_frame(callback.as_ref().unchecked_ref())
+                .unwrap();
+            *loop_frame_id.borrow_mut() = Some(id);
+        }
+    }) as Box<dyn FnMut(f64)>));
+
+    let id =
+        window.request_animation_frame(g.borrow().as_ref().unwrap().as_ref().unchecked_ref())?;
+    *frame_id.borrow_mut() = Some(id);
+
+    let listeners = RefCell::new(orbit_listeners(&canvas, &tunnel));
+
+    Ok(TunnelHandle {
+        tunnel,
+        callback: g,
+        frame_id,
+        canvas,
+        listeners,
+    })
+}
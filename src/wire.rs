@@ -0,0 +1,206 @@
+use crate::curve::{frame_matrix, Frame};
+use crate::math::V3D;
+use crate::mesh::{Mesh, Vertex};
+use crate::polygon::Polygon3D;
+
+/// Longitude and latitude lines as thin tubes, for a grid that keeps its
+/// width where `GL_LINES` would be clamped to one pixel
+pub struct WireGrid {
+    pub vertices: Vec<Vertex>,
+    /// Triangles of the longitude tubes
+    pub longitude: Vec<u32>,
+    /// Triangles of the latitude tubes
+    pub latitude: Vec<u32>,
+}
+
+/// Sweep a `sides`-gon of diameter `width` along every grid line of `mesh`.
+/// Tubes take the colour of the line vertices they follow, fully opaque.
+pub fn generate_wire_grid(mesh: &Mesh, width: f64, sides: usize) -> WireGrid {
+    let polygon = Polygon3D::new(0.5 * width, sides.max(3));
+    let mut vertices = Vec::new();
+    let mut longitude = Vec::new();
+    let mut latitude = Vec::new();
+
+    for (lines, triangles) in [
+        (&mesh.long_lines, &mut longitude),
+        (&mesh.lat_lines, &mut latitude),
+    ] {
+        for polyline in polylines(lines) {
+            sweep_polyline(mesh, &polyline, &polygon, &mut vertices, triangles);
+        }
+    }
+
+    WireGrid {
+        vertices,
+        longitude,
+        latitude,
+    }
+}
+
+// Chain line segments into polylines. Consecutive segments that share an
+// endpoint continue the same polyline, which is how the mesh emits them.
+fn polylines(lines: &[u32]) -> Vec<Vec<u32>> {
+    let mut chains: Vec<Vec<u32>> = Vec::new();
+    for segment in lines.chunks_exact(2) {
+        match chains.last_mut() {
+            Some(chain) if chain.last() == Some(&segment[0]) => chain.push(segment[1]),
+            _ => chains.push(segment.to_vec()),
+        }
+    }
+    chains
+}
+
+fn sweep_polyline(
+    mesh: &Mesh,
+    polyline: &[u32],
+    polygon: &Polygon3D,
+    vertices: &mut Vec<Vertex>,
+    triangles: &mut Vec<u32>,
+) {
+    let point = |i: u32| {
+        let p = mesh.vertices[i as usize].pos;
+        V3D::new(p[0] as f64, p[1] as f64, p[2] as f64)
+    };
+    let mut points: Vec<V3D> = polyline.iter().map(|&i| point(i)).collect();
+
+    // Rings and seams repeat their first point; drop it and wrap around
+    let gap = (points[0] - points[points.len() - 1]).magnitude();
+    let closed = points.len() > 2 && gap < 1e-5 * points[0].magnitude().max(1.0);
+    if closed {
+        points.pop();
+    }
+    let count = points.len();
+    if count < 2 {
+        return;
+    }
+
+    let direction = |k: usize| {
+        let (before, after) = if closed {
+            (points[(k + count - 1) % count], points[(k + 1) % count])
+        } else {
+            (points[k.saturating_sub(1)], points[(k + 1).min(count - 1)])
+        };
+        (after - before).normalize()
+    };
+
+    // Parallel transport keeps the small tubes from twisting
+    let first = direction(0);
+    let helper = if first.x.abs() < 0.9 {
+        V3D::new(1.0, 0.0, 0.0)
+    } else {
+        V3D::new(0.0, 1.0, 0.0)
+    };
+    let sides = polygon.sides();
+    let rings = if closed { count + 1 } else { count };
+    let mut normals = Vec::with_capacity(rings);
+    let mut normal = (helper - first * helper.dot(&first)).normalize();
+    for k in 0..rings {
+        let tangent = direction(k % count);
+        normal = (normal - tangent * normal.dot(&tangent)).normalize();
+        normals.push(normal);
+    }
+
+    // Round a loop the transported frame comes back turned; spread that
+    // angle along the loop, as the rotation-minimizing frames do, so the
+    // closing ring matches the first
+    if closed {
+        let end = normals[count];
+        let start = normals[0];
+        let twist = end.cross(&start).dot(&first).atan2(end.dot(&start));
+        for (k, normal) in normals.iter_mut().enumerate() {
+            let fraction = k as f64 / count as f64;
+            *normal = normal.rotate_about(&direction(k % count), twist * fraction);
+        }
+    }
+
+    let base = vertices.len() as u32;
+    for (k, &normal) in normals.iter().enumerate() {
+        let p = points[k % count];
+        let tangent = direction(k % count);
+        let frame = Frame {
+            tangent,
+            normal,
+            binormal: tangent.cross(&normal),
+        };
+        // The closing ring takes the polyline's repeated end, which keeps
+        // its own palette position
+        let source = mesh.vertices[polyline[k] as usize];
+        let [r, g, b, _] = source.color;
+        let color = [r, g, b, 1.0];
+        let v = k as f32 / (rings - 1) as f32;
+
+        for (j, q) in polygon
+            .transform(&frame_matrix(&p, &frame))
+            .iter()
+            .enumerate()
+        {
+            let n = (*q - p).normalize();
+            vertices.push(Vertex {
+                pos: [q.x as f32, q.y as f32, q.z as f32],
+                color,
+                normal: [n.x as f32, n.y as f32, n.z as f32],
+                uv: [j as f32 / sides as f32, v],
+                scalar: source.scalar,
+            });
+        }
+    }
+
+    for k in 0..rings - 1 {
+        for j in 0..sides {
+            let a = base + (k * sides + j) as u32;
+            let b = base + ((k + 1) * sides + j) as u32;
+            let c = base + (k * sides + (j + 1) % sides) as u32;
+            let d = base + ((k + 1) * sides + (j + 1) % sides) as u32;
+            // Outward facing, like the solid tube's outer shell
+            triangles.extend_from_slice(&[a, c, b, b, c, d]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A closed loop that isn't planar, so transport comes back twisted
+    fn saddle_loop(samples: usize) -> Mesh {
+        let vertex = |i: usize| {
+            let s = std::f64::consts::TAU * i as f64 / samples as f64;
+            Vertex {
+                pos: [
+                    s.cos() as f32,
+                    s.sin() as f32,
+                    (0.6 * (2.0 * s).sin()) as f32,
+                ],
+                color: [1.0; 4],
+                normal: [0.0; 3],
+                uv: [0.0; 2],
+                scalar: 0.0,
+            }
+        };
+        let mut vertices: Vec<Vertex> = (0..samples).map(vertex).collect();
+        // Rings repeat their first vertex, like the tunnel mesh
+        vertices.push(vertex(0));
+        let lat_lines = (0..samples as u32).flat_map(|i| [i, i + 1]).collect();
+        Mesh {
+            vertices,
+            triangles: Vec::new(),
+            long_lines: Vec::new(),
+            lat_lines,
+        }
+    }
+
+    #[test]
+    fn closed_tubes_meet_without_a_seam() {
+        let samples = 48;
+        let grid = generate_wire_grid(&saddle_loop(samples), 0.1, 6);
+        assert!(grid.longitude.is_empty());
+        let sides = 6;
+        assert_eq!(grid.vertices.len(), (samples + 1) * sides);
+        let last = samples * sides;
+        for j in 0..sides {
+            let (a, b) = (grid.vertices[j].pos, grid.vertices[last + j].pos);
+            let gap = (0..3).map(|k| (a[k] - b[k]).abs()).fold(0.0, f32::max);
+            assert!(gap < 1e-5, "side {} is off by {}", j, gap);
+        }
+    }
+}